@@ -0,0 +1,34 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use mdkit::parser::{ParseOptions, Parser};
+use mdkit::{markdown, renderer};
+
+// Parsing arbitrary text must never panic or overflow the stack, with the
+// default limits and with tight ones that also enable the optional syntax.
+fuzz_target!(|data: &[u8]| {
+    let Ok(input) = std::str::from_utf8(data) else {
+        return;
+    };
+
+    let tight = ParseOptions {
+        max_depth: 4,
+        max_input_len: 4096,
+        max_nodes: 256,
+        highlight: true,
+        superscript: true,
+        subscript: true,
+        inserted: true,
+        wiki_links: true,
+        mentions: true,
+        issues: true,
+        commits: true,
+        hashtags: true,
+        ..ParseOptions::default()
+    };
+    for options in [ParseOptions::default(), tight] {
+        let result = Parser::with_options(input, options).parse_with_diagnostics();
+        renderer::render(&result.nodes);
+        markdown::render(&result.nodes);
+    }
+});
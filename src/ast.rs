@@ -1,13 +1,17 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 use std::collections::HashMap;
 
-#[derive(Debug, Clone, PartialEq, Serialize)]
+pub mod typescript;
+pub mod visit;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum ListType {
     Ordered,
     Unordered,
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ListItem {
     pub content: Vec<Node>,
@@ -15,14 +19,40 @@ pub struct ListItem {
     pub checked: Option<bool>,
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TableCell {
     pub children: Vec<Node>,
     pub alignment: TableAlignment,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FrontMatterFormat {
+    /// Between `---` lines.
+    Yaml,
+    /// Between `+++` lines.
+    Toml,
+}
+
+impl FrontMatterFormat {
+    pub fn delimiter(self) -> &'static str {
+        match self {
+            FrontMatterFormat::Yaml => "---",
+            FrontMatterFormat::Toml => "+++",
+        }
+    }
+}
+
+/// Initial state of a foldable callout, from `[!kind]+` or `[!kind]-`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Fold {
+    Open,
+    Closed,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum TableAlignment {
     Left,
     Center,
@@ -30,9 +60,17 @@ pub enum TableAlignment {
     None,
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "camelCase")]
 pub enum Node {
+    /// Metadata block at the very start of a document.
+    FrontMatter {
+        format: FrontMatterFormat,
+        /// Source between the delimiter lines.
+        raw: String,
+        /// `raw` decoded; empty when it is not a valid mapping.
+        metadata: Map<String, Value>,
+    },
     Heading {
         level: usize,
         id: String,
@@ -48,10 +86,22 @@ pub enum Node {
     Link {
         text: Vec<Node>,
         url: String,
+        #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+        attributes: HashMap<String, String>,
+    },
+    /// `[[Page]]`, `[[Page#Heading]]` or `[[Page|alias]]`, with
+    /// [`ParseOptions::wiki_links`](crate::parser::ParseOptions::wiki_links).
+    /// An empty target links to a heading of the same page.
+    WikiLink {
+        target: String,
+        heading: Option<String>,
+        alias: Option<String>,
     },
     Image {
         alt: String,
         url: String,
+        #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+        attributes: HashMap<String, String>,
     },
 
     Bold {
@@ -66,10 +116,58 @@ pub enum Node {
     Underline {
         children: Vec<Node>,
     },
+    /// `==text==`, with [`ParseOptions::highlight`](crate::parser::ParseOptions::highlight).
+    Highlight {
+        children: Vec<Node>,
+    },
+    /// `^text^`, with [`ParseOptions::superscript`](crate::parser::ParseOptions::superscript).
+    Superscript {
+        children: Vec<Node>,
+    },
+    /// `~text~`, with [`ParseOptions::subscript`](crate::parser::ParseOptions::subscript).
+    Subscript {
+        children: Vec<Node>,
+    },
+    /// `++text++`, with [`ParseOptions::inserted`](crate::parser::ParseOptions::inserted).
+    Inserted {
+        children: Vec<Node>,
+    },
     Text {
         content: String,
     },
 
+    /// `:name[label]{attributes}` within text.
+    InlineDirective {
+        name: String,
+        label: Vec<Node>,
+        attributes: HashMap<String, String>,
+    },
+    /// `@username`, with [`ParseOptions::mentions`](crate::parser::ParseOptions::mentions).
+    Mention {
+        username: String,
+    },
+    /// `#123` or `owner/repo#123`, with
+    /// [`ParseOptions::issues`](crate::parser::ParseOptions::issues).
+    IssueReference {
+        repository: Option<String>,
+        number: u64,
+    },
+    /// A commit SHA, optionally as `owner/repo@sha`, with
+    /// [`ParseOptions::commits`](crate::parser::ParseOptions::commits).
+    CommitReference {
+        repository: Option<String>,
+        sha: String,
+    },
+    /// `#tag`, with [`ParseOptions::hashtags`](crate::parser::ParseOptions::hashtags).
+    Hashtag {
+        tag: String,
+    },
+    /// `:shortcode:`, recognized with the `emoji` feature.
+    Emoji {
+        shortcode: String,
+        unicode: String,
+    },
+
     InlineMath {
         content: String,
     },
@@ -89,19 +187,420 @@ pub enum Node {
     BlockQuote {
         children: Vec<Node>,
     },
+    /// A blockquote starting with `[!kind]`, e.g. GitHub's `> [!NOTE]`.
+    Callout {
+        /// Lowercased, e.g. `note` or `warning`.
+        kind: String,
+        /// Inline title after the marker; empty to use the kind.
+        title: Vec<Node>,
+        foldable: Option<Fold>,
+        children: Vec<Node>,
+    },
     List {
         kind: ListType,
         items: Vec<ListItem>,
     },
+    /// Terms each followed by their descriptions.
+    DefinitionList {
+        children: Vec<Node>,
+    },
+    /// A term in a definition list, as inline nodes.
+    DefinitionTerm {
+        children: Vec<Node>,
+    },
+    /// One `: ` definition of the term before it, as blocks.
+    DefinitionDescription {
+        children: Vec<Node>,
+    },
 
     Table {
         header: Vec<TableCell>,
         rows: Vec<Vec<TableCell>>,
     },
 
+    /// `::name[label]{attributes}` on a line of its own.
+    LeafDirective {
+        name: String,
+        label: Vec<Node>,
+        attributes: HashMap<String, String>,
+    },
+    /// Container directive, `:::name[label]{attributes}` up to a closing
+    /// `:::`.
     CustomBlock {
         name: String,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        label: Vec<Node>,
+        /// `#id` is stored as `id` and `.class` names as a space-separated
+        /// `class`.
         attributes: HashMap<String, String>,
         children: Vec<Node>,
     },
 }
+
+/// A document's nodes together with its front matter metadata.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Document {
+    pub metadata: Map<String, Value>,
+    /// All nodes, including the [`Node::FrontMatter`] the metadata came from.
+    pub children: Vec<Node>,
+}
+
+impl Document {
+    pub fn new(children: Vec<Node>) -> Self {
+        let metadata = match children.first() {
+            Some(Node::FrontMatter { metadata, .. }) => metadata.clone(),
+            _ => Map::new(),
+        };
+        Self { metadata, children }
+    }
+
+    /// String metadata value, e.g. `title`.
+    pub fn metadata_str(&self, key: &str) -> Option<&str> {
+        self.metadata.get(key)?.as_str()
+    }
+}
+
+/// Concatenates the text, code and math content of `nodes`, e.g. for
+/// heading slugs.
+pub fn plain_text(nodes: &[Node]) -> String {
+    struct PlainText(String);
+
+    impl visit::Visitor for PlainText {
+        fn visit_node(&mut self, node: &Node) {
+            match node {
+                Node::Text { content }
+                | Node::InlineCode { content }
+                | Node::InlineMath { content }
+                | Node::Emoji {
+                    unicode: content, ..
+                } => self.0.push_str(content),
+                Node::Mention { .. }
+                | Node::IssueReference { .. }
+                | Node::CommitReference { .. }
+                | Node::Hashtag { .. } => {
+                    self.0.push_str(&reference_text(node).unwrap_or_default())
+                }
+                Node::WikiLink {
+                    target,
+                    heading,
+                    alias,
+                } => self.0.push_str(&wiki_link_text(
+                    target,
+                    heading.as_deref(),
+                    alias.as_deref(),
+                )),
+                _ => visit::walk_node(self, node),
+            }
+        }
+    }
+
+    let mut collector = PlainText(String::new());
+    visit::walk_nodes(&mut collector, nodes);
+    collector.0
+}
+
+/// Text shown for a wiki link: the alias, or the page and heading as
+/// `Page > Heading`.
+pub fn wiki_link_text(target: &str, heading: Option<&str>, alias: Option<&str>) -> String {
+    match (alias, heading) {
+        (Some(alias), _) => alias.to_string(),
+        (None, Some(heading)) if target.is_empty() => heading.to_string(),
+        (None, Some(heading)) => format!("{} > {}", target, heading),
+        (None, None) => target.to_string(),
+    }
+}
+
+/// Source text of a mention, issue or commit reference or hashtag, such as
+/// `@octocat` or `owner/repo#12`.
+pub fn reference_text(node: &Node) -> Option<String> {
+    let repository = |repository: &Option<String>| repository.clone().unwrap_or_default();
+    Some(match node {
+        Node::Mention { username } => format!("@{}", username),
+        Node::IssueReference {
+            repository: r,
+            number,
+        } => format!("{}#{}", repository(r), number),
+        Node::CommitReference {
+            repository: None,
+            sha,
+        } => sha.clone(),
+        Node::CommitReference {
+            repository: Some(r),
+            sha,
+        } => format!("{}@{}", r, sha),
+        Node::Hashtag { tag } => format!("#{}", tag),
+        _ => return None,
+    })
+}
+
+/// GitHub usernames: ASCII letters, digits and single inner hyphens, up to
+/// 39 characters.
+pub fn is_username(name: &str) -> bool {
+    (1..=39).contains(&name.len())
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        && !name.starts_with('-')
+        && !name.ends_with('-')
+        && !name.contains("--")
+}
+
+/// `owner/repo`, with a username as owner and a repository name of ASCII
+/// letters, digits, `.`, `_` and `-`.
+pub fn is_repository(repository: &str) -> bool {
+    repository.split_once('/').is_some_and(|(owner, name)| {
+        is_username(owner)
+            && !name.is_empty()
+            && name != "."
+            && name != ".."
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '_' || c == '-')
+    })
+}
+
+/// 7 to 40 lowercase hex digits, with at least one digit and one letter so
+/// plain numbers and words like `deadbeef` are not taken for commits.
+pub fn is_commit_sha(sha: &str) -> bool {
+    (7..=40).contains(&sha.len())
+        && sha.chars().all(|c| matches!(c, '0'..='9' | 'a'..='f'))
+        && sha.contains(|c: char| c.is_ascii_digit())
+        && sha.contains(|c: char| c.is_ascii_lowercase())
+}
+
+/// Hashtags start with a letter, followed by letters, digits, `_` and `-`.
+pub fn is_hashtag(tag: &str) -> bool {
+    tag.starts_with(char::is_alphabetic)
+        && tag
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_' || c == '-')
+}
+
+/// Callout kinds are lowercase letters, digits, `-` and `_`, so they can be
+/// used in class names.
+pub fn is_callout_kind(kind: &str) -> bool {
+    !kind.is_empty()
+        && kind
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_')
+}
+
+/// Inline and leaf directive names start with an ASCII letter, followed by
+/// letters, digits, `-` and `_`.
+pub fn is_directive_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// Error returned when a deserialized AST is structurally valid JSON but
+/// cannot be rendered faithfully.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationError {
+    pub path: String,
+    pub message: String,
+}
+
+impl std::fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.path, self.message)
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+/// Checks the invariants the parser guarantees but serde cannot express,
+/// e.g. heading levels and custom block names.
+pub fn validate(nodes: &[Node]) -> Result<(), ValidationError> {
+    validate_nodes(nodes, "nodes")
+}
+
+fn validate_nodes(nodes: &[Node], path: &str) -> Result<(), ValidationError> {
+    for (i, node) in nodes.iter().enumerate() {
+        let node_path = format!("{}[{}]", path, i);
+        if matches!(node, Node::FrontMatter { .. }) && node_path != "nodes[0]" {
+            return Err(ValidationError {
+                path: node_path,
+                message: "front matter must be the first node".to_string(),
+            });
+        }
+        validate_node(node, &node_path)?;
+    }
+    Ok(())
+}
+
+fn validate_node(node: &Node, path: &str) -> Result<(), ValidationError> {
+    let error = |message: String| ValidationError {
+        path: path.to_string(),
+        message,
+    };
+
+    match node {
+        Node::Heading {
+            level, children, ..
+        } => {
+            if !(1..=6).contains(level) {
+                return Err(error(format!(
+                    "heading level {} is out of range 1..=6",
+                    level
+                )));
+            }
+            validate_nodes(children, &format!("{}.children", path))
+        }
+        Node::Link { text, .. } => validate_nodes(text, &format!("{}.text", path)),
+        Node::Paragraph { children }
+        | Node::Bold { children }
+        | Node::Italic { children }
+        | Node::Strikethrough { children }
+        | Node::Underline { children }
+        | Node::Highlight { children }
+        | Node::Superscript { children }
+        | Node::Subscript { children }
+        | Node::Inserted { children }
+        | Node::BlockQuote { children }
+        | Node::DefinitionTerm { children }
+        | Node::DefinitionDescription { children } => {
+            validate_nodes(children, &format!("{}.children", path))
+        }
+        Node::List { items, .. } => {
+            for (i, item) in items.iter().enumerate() {
+                let item_path = format!("{}.items[{}]", path, i);
+                validate_nodes(&item.content, &format!("{}.content", item_path))?;
+                validate_nodes(&item.children, &format!("{}.children", item_path))?;
+            }
+            Ok(())
+        }
+        Node::Table { header, rows } => {
+            for (i, cell) in header.iter().enumerate() {
+                validate_nodes(&cell.children, &format!("{}.header[{}].children", path, i))?;
+            }
+            for (r, row) in rows.iter().enumerate() {
+                for (i, cell) in row.iter().enumerate() {
+                    let cell_path = format!("{}.rows[{}][{}].children", path, r, i);
+                    validate_nodes(&cell.children, &cell_path)?;
+                }
+            }
+            Ok(())
+        }
+        Node::CustomBlock {
+            name,
+            label,
+            children,
+            ..
+        } => {
+            if name.is_empty() || name.contains(|c: char| c.is_whitespace() || c == '[' || c == '{')
+            {
+                return Err(error(format!("invalid custom block name {:?}", name)));
+            }
+            validate_nodes(label, &format!("{}.label", path))?;
+            validate_nodes(children, &format!("{}.children", path))
+        }
+        Node::DefinitionList { children } => {
+            for (i, child) in children.iter().enumerate() {
+                if !matches!(
+                    child,
+                    Node::DefinitionTerm { .. } | Node::DefinitionDescription { .. }
+                ) {
+                    return Err(ValidationError {
+                        path: format!("{}.children[{}]", path, i),
+                        message: "definition lists hold only terms and descriptions".to_string(),
+                    });
+                }
+            }
+            validate_nodes(children, &format!("{}.children", path))
+        }
+        Node::InlineDirective { name, label, .. } | Node::LeafDirective { name, label, .. } => {
+            if !is_directive_name(name) {
+                return Err(error(format!("invalid directive name {:?}", name)));
+            }
+            validate_nodes(label, &format!("{}.label", path))
+        }
+        Node::WikiLink {
+            target,
+            heading,
+            alias,
+        } => {
+            let parts = [Some(target), heading.as_ref(), alias.as_ref()];
+            if let Some(part) = parts
+                .into_iter()
+                .flatten()
+                .find(|part| part.contains(['[', ']', '\n']))
+            {
+                return Err(error(format!("invalid wiki link part {:?}", part)));
+            }
+            if target.contains(['#', '|']) || heading.as_ref().is_some_and(|h| h.contains('|')) {
+                return Err(error(format!(
+                    "wiki link target {:?} contains `#` or `|`",
+                    target
+                )));
+            }
+            if target.is_empty() && heading.is_none() {
+                return Err(error("wiki link without target".to_string()));
+            }
+            Ok(())
+        }
+        Node::Mention { username } => {
+            if !is_username(username) {
+                return Err(error(format!("invalid username {:?}", username)));
+            }
+            Ok(())
+        }
+        Node::IssueReference { repository, .. } | Node::CommitReference { repository, .. }
+            if repository.as_ref().is_some_and(|r| !is_repository(r)) =>
+        {
+            Err(error(format!("invalid repository {:?}", repository)))
+        }
+        Node::IssueReference { .. } => Ok(()),
+        Node::CommitReference { sha, .. } => {
+            if !is_commit_sha(sha) {
+                return Err(error(format!("invalid commit SHA {:?}", sha)));
+            }
+            Ok(())
+        }
+        Node::Hashtag { tag } => {
+            if !is_hashtag(tag) {
+                return Err(error(format!("invalid hashtag {:?}", tag)));
+            }
+            Ok(())
+        }
+        Node::Emoji { shortcode, unicode } => {
+            if !crate::emoji::is_shortcode(shortcode) {
+                return Err(error(format!("invalid emoji shortcode {:?}", shortcode)));
+            }
+            if unicode.is_empty() {
+                return Err(error("emoji without unicode".to_string()));
+            }
+            Ok(())
+        }
+        Node::Callout {
+            kind,
+            title,
+            children,
+            ..
+        } => {
+            if !is_callout_kind(kind) {
+                return Err(error(format!("invalid callout kind {:?}", kind)));
+            }
+            validate_nodes(title, &format!("{}.title", path))?;
+            validate_nodes(children, &format!("{}.children", path))
+        }
+        Node::FrontMatter { format, raw, .. } => {
+            if raw
+                .lines()
+                .any(|line| line.trim_end() == format.delimiter())
+            {
+                return Err(error(format!(
+                    "front matter contains its delimiter `{}`",
+                    format.delimiter()
+                )));
+            }
+            Ok(())
+        }
+        Node::HorizontalRule
+        | Node::LineBreak
+        | Node::Image { .. }
+        | Node::Text { .. }
+        | Node::InlineMath { .. }
+        | Node::BlockMath { .. }
+        | Node::InlineCode { .. }
+        | Node::CodeBlock { .. } => Ok(()),
+    }
+}
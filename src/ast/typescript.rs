@@ -0,0 +1,396 @@
+//! TypeScript declarations for the serialized AST.
+//!
+//! Emitted into the generated `.d.ts` through `typescript_custom_section`
+//! in `lib.rs`; `tests/typescript_tests.rs` checks them against the Rust
+//! types so the two cannot drift apart.
+
+pub const DEFINITIONS: &str = r#"
+export type ListType = "Ordered" | "Unordered";
+
+export type TableAlignment = "Left" | "Center" | "Right" | "None";
+
+export interface ListItem {
+    content: Node[];
+    children: Node[];
+    checked?: boolean | null;
+}
+
+export interface TableCell {
+    children: Node[];
+    alignment: TableAlignment;
+}
+
+export type FrontMatterFormat = "yaml" | "toml";
+
+export interface FrontMatterNode {
+    type: "frontMatter";
+    format: FrontMatterFormat;
+    raw: string;
+    metadata: Record<string, unknown>;
+}
+
+export interface HeadingNode {
+    type: "heading";
+    level: number;
+    id: string;
+    children: Node[];
+}
+
+export interface HorizontalRuleNode {
+    type: "horizontalRule";
+}
+
+export interface ParagraphNode {
+    type: "paragraph";
+    children: Node[];
+}
+
+export interface LineBreakNode {
+    type: "lineBreak";
+}
+
+export interface LinkNode {
+    type: "link";
+    text: Node[];
+    url: string;
+    attributes?: Record<string, string>;
+}
+
+export interface WikiLinkNode {
+    type: "wikiLink";
+    target: string;
+    heading?: string | null;
+    alias?: string | null;
+}
+
+export interface ImageNode {
+    type: "image";
+    alt: string;
+    url: string;
+    attributes?: Record<string, string>;
+}
+
+export interface BoldNode {
+    type: "bold";
+    children: Node[];
+}
+
+export interface ItalicNode {
+    type: "italic";
+    children: Node[];
+}
+
+export interface StrikethroughNode {
+    type: "strikethrough";
+    children: Node[];
+}
+
+export interface UnderlineNode {
+    type: "underline";
+    children: Node[];
+}
+
+export interface HighlightNode {
+    type: "highlight";
+    children: Node[];
+}
+
+export interface SuperscriptNode {
+    type: "superscript";
+    children: Node[];
+}
+
+export interface SubscriptNode {
+    type: "subscript";
+    children: Node[];
+}
+
+export interface InsertedNode {
+    type: "inserted";
+    children: Node[];
+}
+
+export interface TextNode {
+    type: "text";
+    content: string;
+}
+
+export interface InlineDirectiveNode {
+    type: "inlineDirective";
+    name: string;
+    label: Node[];
+    attributes: Record<string, string>;
+}
+
+export interface MentionNode {
+    type: "mention";
+    username: string;
+}
+
+export interface IssueReferenceNode {
+    type: "issueReference";
+    repository?: string | null;
+    number: number;
+}
+
+export interface CommitReferenceNode {
+    type: "commitReference";
+    repository?: string | null;
+    sha: string;
+}
+
+export interface HashtagNode {
+    type: "hashtag";
+    tag: string;
+}
+
+export interface EmojiNode {
+    type: "emoji";
+    shortcode: string;
+    unicode: string;
+}
+
+export interface InlineMathNode {
+    type: "inlineMath";
+    content: string;
+}
+
+export interface BlockMathNode {
+    type: "blockMath";
+    content: string;
+}
+
+export interface InlineCodeNode {
+    type: "inlineCode";
+    content: string;
+}
+
+export interface CodeBlockNode {
+    type: "codeBlock";
+    lang: string;
+    filename?: string | null;
+    code: string;
+}
+
+export interface BlockQuoteNode {
+    type: "blockQuote";
+    children: Node[];
+}
+
+export type Fold = "open" | "closed";
+
+export interface CalloutNode {
+    type: "callout";
+    kind: string;
+    title: Node[];
+    foldable?: Fold | null;
+    children: Node[];
+}
+
+export interface ListNode {
+    type: "list";
+    kind: ListType;
+    items: ListItem[];
+}
+
+export interface DefinitionListNode {
+    type: "definitionList";
+    children: Node[];
+}
+
+export interface DefinitionTermNode {
+    type: "definitionTerm";
+    children: Node[];
+}
+
+export interface DefinitionDescriptionNode {
+    type: "definitionDescription";
+    children: Node[];
+}
+
+export interface TableNode {
+    type: "table";
+    header: TableCell[];
+    rows: TableCell[][];
+}
+
+export interface LeafDirectiveNode {
+    type: "leafDirective";
+    name: string;
+    label: Node[];
+    attributes: Record<string, string>;
+}
+
+export interface CustomBlockNode {
+    type: "customBlock";
+    name: string;
+    label?: Node[];
+    attributes: Record<string, string>;
+    children: Node[];
+}
+
+export type Node =
+    | FrontMatterNode
+    | HeadingNode
+    | HorizontalRuleNode
+    | ParagraphNode
+    | LineBreakNode
+    | LinkNode
+    | WikiLinkNode
+    | ImageNode
+    | BoldNode
+    | ItalicNode
+    | StrikethroughNode
+    | UnderlineNode
+    | HighlightNode
+    | SuperscriptNode
+    | SubscriptNode
+    | InsertedNode
+    | TextNode
+    | InlineDirectiveNode
+    | MentionNode
+    | IssueReferenceNode
+    | CommitReferenceNode
+    | HashtagNode
+    | EmojiNode
+    | InlineMathNode
+    | BlockMathNode
+    | InlineCodeNode
+    | CodeBlockNode
+    | BlockQuoteNode
+    | CalloutNode
+    | ListNode
+    | DefinitionListNode
+    | DefinitionTermNode
+    | DefinitionDescriptionNode
+    | TableNode
+    | LeafDirectiveNode
+    | CustomBlockNode;
+
+export type ReferenceNode = MentionNode | IssueReferenceNode | CommitReferenceNode | HashtagNode;
+
+export interface ReferenceTemplates {
+    repository?: string;
+    mention?: string;
+    issue?: string;
+    commit?: string;
+    hashtag?: string;
+}
+
+export type DirectiveNode = InlineDirectiveNode | LeafDirectiveNode | CustomBlockNode;
+
+export interface DirectiveContent {
+    label: string;
+    children: string;
+}
+
+export interface Document {
+    metadata: Record<string, unknown>;
+    children: Node[];
+}
+
+export interface QueryMatch {
+    path: number[];
+    node: Node | ListItem | TableCell;
+}
+
+export type TextEdit =
+    | { op: "equal"; text: string }
+    | { op: "insert"; text: string }
+    | { op: "delete"; text: string };
+
+export type Change =
+    | { op: "insert"; path: number[] }
+    | { op: "delete"; path: number[] }
+    | { op: "update"; oldPath: number[]; newPath: number[]; text?: TextEdit[] }
+    | { op: "move"; from: number[]; to: number[] };
+
+export interface StreamUpdate {
+    stable: string;
+    tail: string;
+}
+
+export interface BlockEdit {
+    start: number;
+    removed: number;
+    nodes: Node[];
+}
+
+export type Severity = "error" | "warning" | "info";
+
+export interface Fix {
+    span: { start: number; end: number };
+    replacement: string;
+}
+
+export interface Diagnostic {
+    severity: Severity;
+    code: string;
+    message: string;
+    span: { start: number; end: number };
+    fix?: Fix;
+}
+
+export interface ParseOptions {
+    maxDepth?: number;
+    maxInputLength?: number;
+    maxNodes?: number;
+    frontMatter?: boolean;
+    highlight?: boolean;
+    superscript?: boolean;
+    subscript?: boolean;
+    inserted?: boolean;
+    wikiLinks?: boolean;
+    mentions?: boolean;
+    issues?: boolean;
+    commits?: boolean;
+    hashtags?: boolean;
+}
+
+export interface ParseResult {
+    nodes: Node[];
+    diagnostics: Diagnostic[];
+}
+
+export type LintRule =
+    | "heading-increment"
+    | "duplicate-heading"
+    | "image-alt"
+    | "bare-url"
+    | "list-marker"
+    | "trailing-spaces"
+    | "empty-link"
+    | "unclosed-custom-block"
+    | "table-column-mismatch";
+
+export type LintConfig = Partial<Record<LintRule, "off" | Severity>>;
+
+export interface SearchEntry {
+    path: string;
+    id: string;
+    title: string;
+    headings: string[];
+    excerpt: string;
+}
+
+export interface SearchIndex {
+    entries: SearchEntry[];
+    terms: Record<string, [number, number][]>;
+}
+
+export interface SearchHit {
+    entry: SearchEntry;
+    score: number;
+}
+
+export interface PageLinks {
+    links: string[];
+    backlinks: string[];
+    missing: string[];
+}
+
+export interface LinkGraph {
+    pages: Record<string, PageLinks>;
+    orphans: string[];
+}
+"#;
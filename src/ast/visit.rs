@@ -0,0 +1,196 @@
+//! Depth-first traversal over the AST.
+//!
+//! Implement [`Visitor`] (or [`VisitorMut`]) and override only the hooks you
+//! care about; the default methods call the matching `walk_*` function, which
+//! descends into every child collection. Call `walk_*` from an overridden
+//! hook to keep descending.
+
+use crate::ast::{ListItem, Node, TableCell};
+
+pub trait Visitor {
+    fn visit_node(&mut self, node: &Node) {
+        walk_node(self, node);
+    }
+
+    fn visit_list_item(&mut self, item: &ListItem) {
+        walk_list_item(self, item);
+    }
+
+    fn visit_table_cell(&mut self, cell: &TableCell) {
+        walk_table_cell(self, cell);
+    }
+}
+
+pub trait VisitorMut {
+    fn visit_node_mut(&mut self, node: &mut Node) {
+        walk_node_mut(self, node);
+    }
+
+    fn visit_list_item_mut(&mut self, item: &mut ListItem) {
+        walk_list_item_mut(self, item);
+    }
+
+    fn visit_table_cell_mut(&mut self, cell: &mut TableCell) {
+        walk_table_cell_mut(self, cell);
+    }
+}
+
+pub fn walk_nodes<V: Visitor + ?Sized>(visitor: &mut V, nodes: &[Node]) {
+    for node in nodes {
+        visitor.visit_node(node);
+    }
+}
+
+pub fn walk_node<V: Visitor + ?Sized>(visitor: &mut V, node: &Node) {
+    match node {
+        Node::Heading { children, .. }
+        | Node::Paragraph { children }
+        | Node::Bold { children }
+        | Node::Italic { children }
+        | Node::Strikethrough { children }
+        | Node::Underline { children }
+        | Node::Highlight { children }
+        | Node::Superscript { children }
+        | Node::Subscript { children }
+        | Node::Inserted { children }
+        | Node::BlockQuote { children }
+        | Node::DefinitionList { children }
+        | Node::DefinitionTerm { children }
+        | Node::DefinitionDescription { children } => walk_nodes(visitor, children),
+        Node::Link { text, .. } => walk_nodes(visitor, text),
+        Node::InlineDirective { label, .. } | Node::LeafDirective { label, .. } => {
+            walk_nodes(visitor, label)
+        }
+        Node::Callout {
+            title, children, ..
+        } => {
+            walk_nodes(visitor, title);
+            walk_nodes(visitor, children);
+        }
+        Node::CustomBlock {
+            label, children, ..
+        } => {
+            walk_nodes(visitor, label);
+            walk_nodes(visitor, children);
+        }
+        Node::List { items, .. } => {
+            for item in items {
+                visitor.visit_list_item(item);
+            }
+        }
+        Node::Table { header, rows } => {
+            for cell in header {
+                visitor.visit_table_cell(cell);
+            }
+            for row in rows {
+                for cell in row {
+                    visitor.visit_table_cell(cell);
+                }
+            }
+        }
+        Node::FrontMatter { .. }
+        | Node::HorizontalRule
+        | Node::LineBreak
+        | Node::Image { .. }
+        | Node::Text { .. }
+        | Node::Emoji { .. }
+        | Node::WikiLink { .. }
+        | Node::Mention { .. }
+        | Node::IssueReference { .. }
+        | Node::CommitReference { .. }
+        | Node::Hashtag { .. }
+        | Node::InlineMath { .. }
+        | Node::BlockMath { .. }
+        | Node::InlineCode { .. }
+        | Node::CodeBlock { .. } => {}
+    }
+}
+
+pub fn walk_list_item<V: Visitor + ?Sized>(visitor: &mut V, item: &ListItem) {
+    walk_nodes(visitor, &item.content);
+    walk_nodes(visitor, &item.children);
+}
+
+pub fn walk_table_cell<V: Visitor + ?Sized>(visitor: &mut V, cell: &TableCell) {
+    walk_nodes(visitor, &cell.children);
+}
+
+pub fn walk_nodes_mut<V: VisitorMut + ?Sized>(visitor: &mut V, nodes: &mut [Node]) {
+    for node in nodes {
+        visitor.visit_node_mut(node);
+    }
+}
+
+pub fn walk_node_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut Node) {
+    match node {
+        Node::Heading { children, .. }
+        | Node::Paragraph { children }
+        | Node::Bold { children }
+        | Node::Italic { children }
+        | Node::Strikethrough { children }
+        | Node::Underline { children }
+        | Node::Highlight { children }
+        | Node::Superscript { children }
+        | Node::Subscript { children }
+        | Node::Inserted { children }
+        | Node::BlockQuote { children }
+        | Node::DefinitionList { children }
+        | Node::DefinitionTerm { children }
+        | Node::DefinitionDescription { children } => walk_nodes_mut(visitor, children),
+        Node::Link { text, .. } => walk_nodes_mut(visitor, text),
+        Node::InlineDirective { label, .. } | Node::LeafDirective { label, .. } => {
+            walk_nodes_mut(visitor, label)
+        }
+        Node::Callout {
+            title, children, ..
+        } => {
+            walk_nodes_mut(visitor, title);
+            walk_nodes_mut(visitor, children);
+        }
+        Node::CustomBlock {
+            label, children, ..
+        } => {
+            walk_nodes_mut(visitor, label);
+            walk_nodes_mut(visitor, children);
+        }
+        Node::List { items, .. } => {
+            for item in items {
+                visitor.visit_list_item_mut(item);
+            }
+        }
+        Node::Table { header, rows } => {
+            for cell in header {
+                visitor.visit_table_cell_mut(cell);
+            }
+            for row in rows {
+                for cell in row {
+                    visitor.visit_table_cell_mut(cell);
+                }
+            }
+        }
+        Node::FrontMatter { .. }
+        | Node::HorizontalRule
+        | Node::LineBreak
+        | Node::Image { .. }
+        | Node::Text { .. }
+        | Node::Emoji { .. }
+        | Node::WikiLink { .. }
+        | Node::Mention { .. }
+        | Node::IssueReference { .. }
+        | Node::CommitReference { .. }
+        | Node::Hashtag { .. }
+        | Node::InlineMath { .. }
+        | Node::BlockMath { .. }
+        | Node::InlineCode { .. }
+        | Node::CodeBlock { .. } => {}
+    }
+}
+
+pub fn walk_list_item_mut<V: VisitorMut + ?Sized>(visitor: &mut V, item: &mut ListItem) {
+    walk_nodes_mut(visitor, &mut item.content);
+    walk_nodes_mut(visitor, &mut item.children);
+}
+
+pub fn walk_table_cell_mut<V: VisitorMut + ?Sized>(visitor: &mut V, cell: &mut TableCell) {
+    walk_nodes_mut(visitor, &mut cell.children);
+}
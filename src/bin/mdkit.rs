@@ -0,0 +1,500 @@
+//! `mdkit` command-line tool, built with `--features cli`.
+//!
+//! Every subcommand reads files, glob patterns or stdin. Converters write to
+//! stdout, a single `--output` file, or one file per input in `--out-dir`,
+//! keeping the directory structure below each glob's fixed prefix.
+
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::{Component, Path, PathBuf};
+use std::process::ExitCode;
+
+use clap::{Args, Parser as Cli, Subcommand};
+use mdkit::ast::{ListItem, Node, plain_text};
+use mdkit::diagnostics::{Severity, apply_fixes, line_col};
+use mdkit::links::{LinkChecker, LinkElement, LinkKind};
+use mdkit::lint::Linter;
+use mdkit::parser::Parser;
+use mdkit::site::Site;
+use mdkit::{markdown, renderer, toc};
+
+#[derive(Cli)]
+#[command(name = "mdkit", version, about = "Convert, lint and format Markdown")]
+struct Options {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Render HTML
+    Html(Io),
+    /// Print the AST as JSON
+    Ast(Io),
+    /// Extract plain text
+    Text(Io),
+    /// Print a table of contents as a Markdown list
+    Toc(Io),
+    /// Reformat Markdown
+    Fmt {
+        #[command(flatten)]
+        io: Io,
+        /// Rewrite input files in place
+        #[arg(short, long, conflicts_with_all = ["output", "out_dir", "check"])]
+        write: bool,
+        /// List inputs that are not formatted and exit with status 1
+        #[arg(long)]
+        check: bool,
+    },
+    /// Build an HTML site from a folder of Markdown files
+    Site {
+        /// Folder with `.md` files and assets
+        source: PathBuf,
+        /// Folder to write the site into
+        output: PathBuf,
+        /// HTML template using `{{content}}`, `{{title}}`, `{{toc}}`, `{{nav}}` and `{{root}}`
+        #[arg(short, long)]
+        template: Option<PathBuf>,
+        /// Title of the generated index page
+        #[arg(long)]
+        title: Option<String>,
+        /// Also write a `search.json` index
+        #[arg(long)]
+        search: bool,
+    },
+    /// Check Markdown against the lint rules
+    Lint {
+        /// Files or glob patterns; stdin when omitted or `-`
+        inputs: Vec<String>,
+        /// JSON rule levels, e.g. `{ "bare-url": "off" }`
+        #[arg(short, long)]
+        config: Option<PathBuf>,
+        /// Apply autofixes, rewriting input files in place
+        #[arg(long)]
+        fix: bool,
+    },
+    /// Check links, images and anchors without fetching external URLs
+    Links {
+        /// Files or glob patterns; stdin when omitted or `-`
+        inputs: Vec<String>,
+        /// Folder that `/` links and files outside the inputs resolve against
+        #[arg(long, default_value = ".")]
+        root: PathBuf,
+        /// Also list external URLs
+        #[arg(long)]
+        external: bool,
+    },
+}
+
+#[derive(Args)]
+struct Io {
+    /// Files or glob patterns; stdin when omitted or `-`
+    inputs: Vec<String>,
+    /// Write all output to this file
+    #[arg(short, long, conflicts_with = "out_dir")]
+    output: Option<PathBuf>,
+    /// Write one file per input into this directory
+    #[arg(short = 'd', long)]
+    out_dir: Option<PathBuf>,
+}
+
+struct Input {
+    /// `None` for stdin.
+    path: Option<PathBuf>,
+    /// Path below the glob prefix, used under `--out-dir`.
+    relative: PathBuf,
+    text: String,
+}
+
+impl Input {
+    fn name(&self) -> String {
+        self.path
+            .as_ref()
+            .map_or("<stdin>".to_string(), |path| path.display().to_string())
+    }
+}
+
+fn main() -> ExitCode {
+    match run(Options::parse().command) {
+        Ok(code) => code,
+        Err(message) => {
+            eprintln!("mdkit: {}", message);
+            ExitCode::from(2)
+        }
+    }
+}
+
+fn run(command: Command) -> Result<ExitCode, String> {
+    match command {
+        Command::Html(io) => convert(&io, "html", |text| {
+            renderer::render(&Parser::new(text).parse_document())
+        }),
+        Command::Ast(io) => convert(&io, "json", |text| {
+            let nodes = Parser::new(text).parse_document();
+            serde_json::to_string_pretty(&nodes).unwrap_or_default() + "\n"
+        }),
+        Command::Text(io) => convert(&io, "txt", |text| {
+            plain_text_blocks(&Parser::new(text).parse_document())
+        }),
+        Command::Toc(io) => convert(&io, "md", |text| {
+            toc::render(&toc::toc(&Parser::new(text).parse_document()))
+        }),
+        Command::Fmt { io, write, check } => format(&io, write, check),
+        Command::Site {
+            source,
+            output,
+            template,
+            title,
+            search,
+        } => site(&source, &output, template.as_deref(), title, search),
+        Command::Lint {
+            inputs,
+            config,
+            fix,
+        } => lint(&inputs, config.as_deref(), fix),
+        Command::Links {
+            inputs,
+            root,
+            external,
+        } => links(&inputs, &root, external),
+    }
+}
+
+fn convert(io: &Io, extension: &str, render: impl Fn(&str) -> String) -> Result<ExitCode, String> {
+    let outputs = read_inputs(&io.inputs)?
+        .into_iter()
+        .map(|input| {
+            let output = render(&input.text);
+            (input, output)
+        })
+        .collect();
+    write_outputs(io, extension, outputs)?;
+    Ok(ExitCode::SUCCESS)
+}
+
+fn format(io: &Io, write: bool, check: bool) -> Result<ExitCode, String> {
+    let inputs = read_inputs(&io.inputs)?;
+    let format = |text: &str| markdown::render(&Parser::new(text).parse_document());
+
+    if check {
+        let mut unformatted = false;
+        for input in &inputs {
+            if format(&input.text) != input.text {
+                println!("{}", input.name());
+                unformatted = true;
+            }
+        }
+        return Ok(if unformatted {
+            ExitCode::FAILURE
+        } else {
+            ExitCode::SUCCESS
+        });
+    }
+
+    if write {
+        for input in &inputs {
+            let formatted = format(&input.text);
+            match &input.path {
+                Some(path) => write_file(path, &formatted)?,
+                None => print(&formatted)?,
+            }
+        }
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    let outputs = inputs
+        .into_iter()
+        .map(|input| {
+            let output = format(&input.text);
+            (input, output)
+        })
+        .collect();
+    write_outputs(io, "md", outputs)?;
+    Ok(ExitCode::SUCCESS)
+}
+
+fn site(
+    source: &Path,
+    output: &Path,
+    template: Option<&Path>,
+    title: Option<String>,
+    search: bool,
+) -> Result<ExitCode, String> {
+    let mut site = Site::new(source);
+    site.search(search);
+    if let Some(path) = template {
+        site.template(
+            fs::read_to_string(path).map_err(|err| format!("{}: {}", path.display(), err))?,
+        );
+    }
+    if let Some(title) = title {
+        site.title(title);
+    }
+
+    let report = site
+        .build(output)
+        .map_err(|err| format!("{}: {}", source.display(), err))?;
+    eprintln!(
+        "wrote {} pages and {} assets to {}",
+        report.pages.len(),
+        report.assets.len(),
+        output.display()
+    );
+    Ok(ExitCode::SUCCESS)
+}
+
+fn lint(patterns: &[String], config: Option<&Path>, fix: bool) -> Result<ExitCode, String> {
+    let linter = match config {
+        Some(path) => {
+            let text =
+                fs::read_to_string(path).map_err(|err| format!("{}: {}", path.display(), err))?;
+            serde_json::from_str::<Linter>(&text)
+                .map_err(|err| format!("{}: {}", path.display(), err))?
+        }
+        None => Linter::new(),
+    };
+
+    let mut failed = false;
+    for mut input in read_inputs(patterns)? {
+        let mut diagnostics = linter.lint(&input.text);
+
+        if fix && diagnostics.iter().any(|d| d.fix.is_some()) {
+            input.text = apply_fixes(&input.text, &diagnostics);
+            match &input.path {
+                Some(path) => write_file(path, &input.text)?,
+                None => print(&input.text)?,
+            }
+            diagnostics = linter.lint(&input.text);
+        }
+
+        for diagnostic in &diagnostics {
+            let (line, column) = line_col(&input.text, diagnostic.span.start);
+            let report = format!(
+                "{}:{}:{}: {} [{}] {}",
+                input.name(),
+                line,
+                column,
+                diagnostic.severity,
+                diagnostic.code,
+                diagnostic.message
+            );
+            // With `--fix` on stdin, stdout carries the fixed document.
+            if fix && input.path.is_none() {
+                eprintln!("{}", report);
+            } else {
+                println!("{}", report);
+            }
+            failed |= diagnostic.severity != Severity::Info;
+        }
+    }
+
+    Ok(if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    })
+}
+
+fn links(patterns: &[String], root: &Path, external: bool) -> Result<ExitCode, String> {
+    let inputs = read_inputs(patterns)?;
+    let mut checker = LinkChecker::new(root);
+    let mut documents = Vec::new();
+    for input in &inputs {
+        let path = match &input.path {
+            Some(path) => path.strip_prefix(root).unwrap_or(path).to_path_buf(),
+            None => PathBuf::from("<stdin>"),
+        };
+        checker.add(&path, &input.text);
+        documents.push((path, input));
+    }
+
+    let mut failed = false;
+    for link in checker.check() {
+        let report = match &link.error {
+            Some(error) => {
+                let element = match (link.kind, link.element) {
+                    (LinkKind::Reference, _) => "reference",
+                    (_, LinkElement::Link) => "link",
+                    (_, LinkElement::Image) => "image",
+                    (_, LinkElement::Definition) => "definition",
+                };
+                format!("broken {} `{}`: {}", element, link.url, error)
+            }
+            None if external && link.kind == LinkKind::External => {
+                format!("external `{}`", link.url)
+            }
+            None => continue,
+        };
+        let Some((_, input)) = documents.iter().find(|(path, _)| *path == link.path) else {
+            continue;
+        };
+        let (line, column) = line_col(&input.text, link.span.start);
+        println!("{}:{}:{}: {}", input.name(), line, column, report);
+        failed |= link.is_broken();
+    }
+
+    Ok(if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    })
+}
+
+fn read_inputs(patterns: &[String]) -> Result<Vec<Input>, String> {
+    if patterns.is_empty() {
+        return Ok(vec![read_stdin()?]);
+    }
+
+    let mut inputs = Vec::new();
+    for pattern in patterns {
+        if pattern == "-" {
+            inputs.push(read_stdin()?);
+        } else if pattern.contains(['*', '?', '[']) {
+            let base = glob_base(pattern);
+            let mut matched = false;
+            for entry in glob::glob(pattern).map_err(|err| format!("{}: {}", pattern, err))? {
+                let path = entry.map_err(|err| err.to_string())?;
+                if path.is_file() {
+                    let relative = path.strip_prefix(&base).unwrap_or(&path).to_path_buf();
+                    inputs.push(read_file(path, relative)?);
+                    matched = true;
+                }
+            }
+            if !matched {
+                return Err(format!("no files match {}", pattern));
+            }
+        } else {
+            let path = PathBuf::from(pattern);
+            let relative = PathBuf::from(path.file_name().unwrap_or(path.as_os_str()));
+            inputs.push(read_file(path, relative)?);
+        }
+    }
+    Ok(inputs)
+}
+
+/// Leading components of a glob pattern that contain no wildcards.
+fn glob_base(pattern: &str) -> PathBuf {
+    Path::new(pattern)
+        .components()
+        .take_while(|component| {
+            !matches!(component, Component::Normal(name)
+                if name.to_string_lossy().contains(['*', '?', '[']))
+        })
+        .collect()
+}
+
+fn read_file(path: PathBuf, relative: PathBuf) -> Result<Input, String> {
+    let text = fs::read_to_string(&path).map_err(|err| format!("{}: {}", path.display(), err))?;
+    Ok(Input {
+        path: Some(path),
+        relative,
+        text,
+    })
+}
+
+fn read_stdin() -> Result<Input, String> {
+    let mut text = String::new();
+    io::stdin()
+        .read_to_string(&mut text)
+        .map_err(|err| format!("<stdin>: {}", err))?;
+    Ok(Input {
+        path: None,
+        relative: PathBuf::from("stdin"),
+        text,
+    })
+}
+
+fn write_outputs(io: &Io, extension: &str, outputs: Vec<(Input, String)>) -> Result<(), String> {
+    if let Some(dir) = &io.out_dir {
+        for (input, output) in outputs {
+            if input.path.is_none() {
+                return Err("--out-dir needs file inputs, not stdin".to_string());
+            }
+            let path = dir.join(&input.relative).with_extension(extension);
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)
+                    .map_err(|err| format!("{}: {}", parent.display(), err))?;
+            }
+            write_file(&path, &output)?;
+        }
+        return Ok(());
+    }
+
+    let combined: String = outputs.into_iter().map(|(_, output)| output).collect();
+    match &io.output {
+        Some(path) => write_file(path, &combined),
+        None => print(&combined),
+    }
+}
+
+fn write_file(path: &Path, contents: &str) -> Result<(), String> {
+    fs::write(path, contents).map_err(|err| format!("{}: {}", path.display(), err))
+}
+
+fn print(contents: &str) -> Result<(), String> {
+    io::stdout()
+        .write_all(contents.as_bytes())
+        .map_err(|err| format!("<stdout>: {}", err))
+}
+
+/// Text of each block on its own, separated by blank lines; list items and
+/// table rows get a line each.
+fn plain_text_blocks(nodes: &[Node]) -> String {
+    fn collect(node: &Node, blocks: &mut Vec<String>) {
+        match node {
+            Node::BlockQuote { children }
+            | Node::Callout { children, .. }
+            | Node::DefinitionList { children }
+            | Node::DefinitionDescription { children }
+            | Node::CustomBlock { children, .. } => {
+                for child in children {
+                    collect(child, blocks);
+                }
+            }
+            Node::List { items, .. } => {
+                let mut lines = Vec::new();
+                list_lines(items, &mut lines);
+                blocks.push(lines.join("\n"));
+            }
+            Node::Table { header, rows } => {
+                let lines: Vec<String> = std::iter::once(header)
+                    .chain(rows)
+                    .map(|row| {
+                        row.iter()
+                            .map(|cell| plain_text(&cell.children))
+                            .collect::<Vec<_>>()
+                            .join("\t")
+                    })
+                    .collect();
+                blocks.push(lines.join("\n"));
+            }
+            Node::CodeBlock { code, .. } => blocks.push(code.clone()),
+            Node::BlockMath { content } => blocks.push(content.clone()),
+            Node::HorizontalRule => {}
+            _ => blocks.push(plain_text(std::slice::from_ref(node))),
+        }
+    }
+
+    fn list_lines(items: &[ListItem], lines: &mut Vec<String>) {
+        for item in items {
+            lines.push(plain_text(&item.content));
+            for child in &item.children {
+                if let Node::List { items, .. } = child {
+                    list_lines(items, lines);
+                }
+            }
+        }
+    }
+
+    let mut blocks = Vec::new();
+    for node in nodes {
+        collect(node, &mut blocks);
+    }
+    blocks.retain(|block| !block.trim().is_empty());
+
+    let mut text = blocks.join("\n\n");
+    if !text.is_empty() {
+        text.push('\n');
+    }
+    text
+}
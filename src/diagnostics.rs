@@ -0,0 +1,101 @@
+use std::fmt;
+use std::ops::Range;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Severity {
+    Error,
+    Warning,
+    Info,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Info => "info",
+        })
+    }
+}
+
+/// A problem found in the source. `span` is a character range into the
+/// parsed input; inside blockquotes it covers the enclosing quote.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub code: String,
+    pub message: String,
+    pub span: Range<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fix: Option<Fix>,
+}
+
+/// Replacement of a character range of the source that resolves a
+/// diagnostic.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Fix {
+    pub span: Range<usize>,
+    pub replacement: String,
+}
+
+impl Diagnostic {
+    pub fn new(
+        severity: Severity,
+        code: &str,
+        message: impl Into<String>,
+        span: Range<usize>,
+    ) -> Self {
+        Self {
+            severity,
+            code: code.to_string(),
+            message: message.into(),
+            span,
+            fix: None,
+        }
+    }
+
+    pub fn warning(code: &str, message: impl Into<String>, span: Range<usize>) -> Self {
+        Self::new(Severity::Warning, code, message, span)
+    }
+}
+
+/// Applies the fixes of `diagnostics` to `input`. Fixes overlapping one
+/// applied earlier in the source are skipped; run again to apply them.
+pub fn apply_fixes(input: &str, diagnostics: &[Diagnostic]) -> String {
+    let mut fixes: Vec<&Fix> = diagnostics.iter().filter_map(|d| d.fix.as_ref()).collect();
+    fixes.sort_by_key(|fix| (fix.span.start, fix.span.end));
+
+    let chars: Vec<char> = input.chars().collect();
+    let mut output = String::new();
+    let mut pos = 0;
+    for fix in fixes {
+        let start = fix.span.start.min(chars.len());
+        let end = fix.span.end.clamp(start, chars.len());
+        if start < pos {
+            continue;
+        }
+        output.extend(&chars[pos..start]);
+        output.push_str(&fix.replacement);
+        pos = end;
+    }
+    output.extend(&chars[pos..]);
+    output
+}
+
+/// One-based line and column of a character offset in `input`.
+pub fn line_col(input: &str, offset: usize) -> (usize, usize) {
+    let mut line = 1;
+    let mut col = 1;
+    for c in input.chars().take(offset) {
+        if c == '\n' {
+            line += 1;
+            col = 1;
+        } else {
+            col += 1;
+        }
+    }
+    (line, col)
+}
@@ -0,0 +1,453 @@
+//! Structural diff between two parsed documents.
+//!
+//! Siblings are aligned with a longest common subsequence over whole
+//! subtrees, after their common prefix and suffix; a very long differing
+//! middle is not aligned but replaced as a whole. Unmatched nodes that reappear unchanged elsewhere in the same
+//! sibling list become moves, unmatched nodes of the same type are diffed
+//! recursively as updates, and the rest are inserts and deletes. Paths use
+//! the same child model as [`crate::query`].
+
+use std::collections::VecDeque;
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::ast::Node;
+use crate::query::NodeRef;
+use crate::renderer;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "op", rename_all = "camelCase")]
+pub enum Change {
+    /// Node present only in the new document, at `path` in the new tree.
+    Insert { path: Vec<usize> },
+    /// Node present only in the old document, at `path` in the old tree.
+    Delete { path: Vec<usize> },
+    /// Node whose own fields changed; children are reported separately.
+    #[serde(rename_all = "camelCase")]
+    Update {
+        old_path: Vec<usize>,
+        new_path: Vec<usize>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        text: Option<Vec<TextEdit>>,
+    },
+    /// Unchanged subtree that moved within its sibling list.
+    Move { from: Vec<usize>, to: Vec<usize> },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "op", content = "text", rename_all = "camelCase")]
+pub enum TextEdit {
+    Equal(String),
+    Insert(String),
+    Delete(String),
+}
+
+/// Returns the changes turning `old` into `new`.
+pub fn diff(old: &[Node], new: &[Node]) -> Vec<Change> {
+    let old: Vec<NodeRef> = old.iter().map(NodeRef::Node).collect();
+    let new: Vec<NodeRef> = new.iter().map(NodeRef::Node).collect();
+
+    let mut changes = Vec::new();
+    diff_children(&old, &new, &[], &[], &mut changes);
+    changes
+}
+
+/// Word-level diff of two strings; whitespace runs are kept as tokens.
+pub fn diff_text(old: &str, new: &str) -> Vec<TextEdit> {
+    let old_tokens = tokenize(old);
+    let new_tokens = tokenize(new);
+
+    let mut edits: Vec<TextEdit> = Vec::new();
+    for step in align(&old_tokens, &new_tokens, |a, b| a == b) {
+        let edit = match step {
+            Step::Same(i, _) => TextEdit::Equal(old_tokens[i].to_string()),
+            Step::Old(i) => TextEdit::Delete(old_tokens[i].to_string()),
+            Step::New(j) => TextEdit::Insert(new_tokens[j].to_string()),
+        };
+
+        match (edits.last_mut(), edit) {
+            (Some(TextEdit::Equal(acc)), TextEdit::Equal(s))
+            | (Some(TextEdit::Insert(acc)), TextEdit::Insert(s))
+            | (Some(TextEdit::Delete(acc)), TextEdit::Delete(s)) => acc.push_str(&s),
+            (_, edit) => edits.push(edit),
+        }
+    }
+    edits
+}
+
+/// Renders `new` with `<ins>`/`<del>` markup showing what changed since `old`.
+pub fn render_redline(old: &[Node], new: &[Node]) -> String {
+    redline_siblings(old, new)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Step {
+    Same(usize, usize),
+    Old(usize),
+    New(usize),
+}
+
+/// Largest `old.len() * new.len()` between the common prefix and suffix
+/// that is aligned with a full LCS table; a larger middle is replaced as a
+/// whole, so that diffing big documents stays within bounded memory.
+const MAX_ALIGN_CELLS: usize = 1 << 22;
+
+/// Longest common subsequence alignment of two sequences.
+fn align<T>(old: &[T], new: &[T], eq: impl Fn(&T, &T) -> bool) -> Vec<Step> {
+    let prefix = old.iter().zip(new).take_while(|(a, b)| eq(a, b)).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| eq(a, b))
+        .count();
+    let (old_end, new_end) = (old.len() - suffix, new.len() - suffix);
+
+    let mut steps: Vec<Step> = (0..prefix).map(|i| Step::Same(i, i)).collect();
+    let (n, m) = (old_end - prefix, new_end - prefix);
+    if n.saturating_mul(m) > MAX_ALIGN_CELLS {
+        steps.extend((prefix..old_end).map(Step::Old));
+        steps.extend((prefix..new_end).map(Step::New));
+    } else {
+        let width = m + 1;
+        let mut table = vec![0u32; (n + 1) * width];
+        for i in (0..n).rev() {
+            for j in (0..m).rev() {
+                table[i * width + j] = if eq(&old[prefix + i], &new[prefix + j]) {
+                    table[(i + 1) * width + j + 1] + 1
+                } else {
+                    table[(i + 1) * width + j].max(table[i * width + j + 1])
+                };
+            }
+        }
+
+        let (mut i, mut j) = (0, 0);
+        while i < n && j < m {
+            if eq(&old[prefix + i], &new[prefix + j]) {
+                steps.push(Step::Same(prefix + i, prefix + j));
+                i += 1;
+                j += 1;
+            } else if table[(i + 1) * width + j] >= table[i * width + j + 1] {
+                steps.push(Step::Old(prefix + i));
+                i += 1;
+            } else {
+                steps.push(Step::New(prefix + j));
+                j += 1;
+            }
+        }
+        steps.extend((prefix + i..old_end).map(Step::Old));
+        steps.extend((prefix + j..new_end).map(Step::New));
+    }
+    steps.extend(
+        (old_end..old.len())
+            .zip(new_end..new.len())
+            .map(|(i, j)| Step::Same(i, j)),
+    );
+    steps
+}
+
+fn align_siblings(old: &[NodeRef], new: &[NodeRef]) -> Vec<Step> {
+    align(old, new, |a, b| a == b)
+}
+
+fn diff_children(
+    old: &[NodeRef],
+    new: &[NodeRef],
+    old_path: &[usize],
+    new_path: &[usize],
+    changes: &mut Vec<Change>,
+) {
+    let path = |base: &[usize], i: usize| {
+        let mut path = base.to_vec();
+        path.push(i);
+        path
+    };
+
+    let steps = align_siblings(old, new);
+    let mut deleted: Vec<usize> = steps
+        .iter()
+        .filter_map(|s| if let Step::Old(i) = s { Some(*i) } else { None })
+        .collect();
+    let mut inserted: Vec<usize> = steps
+        .iter()
+        .filter_map(|s| if let Step::New(j) = s { Some(*j) } else { None })
+        .collect();
+
+    // Unchanged subtrees that changed position.
+    deleted.retain(|&i| match inserted.iter().position(|&j| old[i] == new[j]) {
+        Some(k) => {
+            let j = inserted.remove(k);
+            changes.push(Change::Move {
+                from: path(old_path, i),
+                to: path(new_path, j),
+            });
+            false
+        }
+        None => true,
+    });
+
+    // Same-typed leftovers are treated as edits of one another, in order.
+    deleted.retain(|&i| {
+        match inserted
+            .iter()
+            .position(|&j| old[i].type_name() == new[j].type_name())
+        {
+            Some(k) => {
+                let j = inserted.remove(k);
+                diff_node(
+                    old[i],
+                    new[j],
+                    &path(old_path, i),
+                    &path(new_path, j),
+                    changes,
+                );
+                false
+            }
+            None => true,
+        }
+    });
+
+    for i in deleted {
+        changes.push(Change::Delete {
+            path: path(old_path, i),
+        });
+    }
+    for j in inserted {
+        changes.push(Change::Insert {
+            path: path(new_path, j),
+        });
+    }
+}
+
+fn diff_node(
+    old: NodeRef,
+    new: NodeRef,
+    old_path: &[usize],
+    new_path: &[usize],
+    changes: &mut Vec<Change>,
+) {
+    if own_fields(old) != own_fields(new) {
+        let text = match (old, new) {
+            (
+                NodeRef::Node(Node::Text { content: a }),
+                NodeRef::Node(Node::Text { content: b }),
+            ) => Some(diff_text(a, b)),
+            _ => None,
+        };
+        changes.push(Change::Update {
+            old_path: old_path.to_vec(),
+            new_path: new_path.to_vec(),
+            text,
+        });
+    }
+
+    diff_children(
+        &old.children(),
+        &new.children(),
+        old_path,
+        new_path,
+        changes,
+    );
+}
+
+/// Serialized node without its child collections.
+fn own_fields(node: NodeRef) -> Value {
+    let mut value = serde_json::to_value(node).unwrap_or(Value::Null);
+    if let Value::Object(map) = &mut value {
+        for key in ["children", "items", "header", "rows"] {
+            map.remove(key);
+        }
+        match node {
+            NodeRef::Node(Node::Link { .. }) => {
+                map.remove("text");
+            }
+            NodeRef::Node(
+                Node::InlineDirective { .. }
+                | Node::LeafDirective { .. }
+                | Node::CustomBlock { .. },
+            ) => {
+                map.remove("label");
+            }
+            NodeRef::Node(Node::Callout { .. }) => {
+                map.remove("title");
+            }
+            NodeRef::ListItem(_) => {
+                map.remove("content");
+            }
+            _ => {}
+        }
+    }
+    value
+}
+
+fn tokenize(text: &str) -> Vec<&str> {
+    let mut tokens = Vec::new();
+    let mut start = 0;
+    let mut prev_space = None;
+
+    for (i, c) in text.char_indices() {
+        let space = c.is_whitespace();
+        if prev_space.is_some_and(|p| p != space) {
+            tokens.push(&text[start..i]);
+            start = i;
+        }
+        prev_space = Some(space);
+    }
+    if start < text.len() {
+        tokens.push(&text[start..]);
+    }
+    tokens
+}
+
+fn render_trimmed(node: &Node) -> String {
+    renderer::render(std::slice::from_ref(node))
+        .trim_end()
+        .to_string()
+}
+
+const CHILDREN_MARKER: &str = "\u{0}mdkit-redline-children\u{0}";
+
+/// Renders aligned siblings. Within each run of unmatched nodes, a deleted
+/// node is paired with the next inserted node of the same type and rendered
+/// once with redlined children or text.
+fn redline_siblings(old: &[Node], new: &[Node]) -> String {
+    let old_refs: Vec<NodeRef> = old.iter().map(NodeRef::Node).collect();
+    let new_refs: Vec<NodeRef> = new.iter().map(NodeRef::Node).collect();
+
+    let mut html = String::new();
+    let mut deleted = Vec::new();
+    let mut inserted = Vec::new();
+
+    for step in align_siblings(&old_refs, &new_refs) {
+        match step {
+            Step::Old(i) => deleted.push(i),
+            Step::New(j) => inserted.push(j),
+            Step::Same(_, j) => {
+                redline_run(old, new, &mut deleted, &mut inserted, &mut html);
+                html.push_str(&renderer::render(&new[j..=j]));
+            }
+        }
+    }
+    redline_run(old, new, &mut deleted, &mut inserted, &mut html);
+    html
+}
+
+fn redline_run(
+    old: &[Node],
+    new: &[Node],
+    deleted: &mut Vec<usize>,
+    inserted: &mut Vec<usize>,
+    html: &mut String,
+) {
+    let mut pending: VecDeque<usize> = deleted.drain(..).collect();
+
+    for j in inserted.drain(..) {
+        let partner = pending.iter().position(|&i| {
+            NodeRef::Node(&old[i]).type_name() == NodeRef::Node(&new[j]).type_name()
+        });
+
+        match partner {
+            Some(k) => {
+                for i in pending.drain(..k) {
+                    html.push_str(&format!("<del>{}</del>", render_trimmed(&old[i])));
+                }
+                if let Some(i) = pending.pop_front() {
+                    html.push_str(&redline_node(&old[i], &new[j]));
+                }
+            }
+            None => html.push_str(&format!("<ins>{}</ins>", render_trimmed(&new[j]))),
+        }
+    }
+
+    for i in pending {
+        html.push_str(&format!("<del>{}</del>", render_trimmed(&old[i])));
+    }
+}
+
+fn redline_node(old: &Node, new: &Node) -> String {
+    if let (Node::Text { content: a }, Node::Text { content: b }) = (old, new) {
+        return diff_text(a, b)
+            .into_iter()
+            .map(|edit| match edit {
+                TextEdit::Equal(s) => s,
+                TextEdit::Insert(s) => format!("<ins>{}</ins>", s),
+                TextEdit::Delete(s) => format!("<del>{}</del>", s),
+            })
+            .collect();
+    }
+
+    let (old_children, new_children) = match (inline_children(old), inline_children(new)) {
+        (Some(a), Some(b)) if own_fields(NodeRef::Node(old)) == own_fields(NodeRef::Node(new)) => {
+            (a, b)
+        }
+        _ => {
+            return format!(
+                "<del>{}</del><ins>{}</ins>",
+                render_trimmed(old),
+                render_trimmed(new)
+            );
+        }
+    };
+
+    let shell = with_children(
+        new,
+        vec![Node::Text {
+            content: CHILDREN_MARKER.to_string(),
+        }],
+    );
+    let inner = redline_siblings(old_children, new_children);
+    renderer::render(std::slice::from_ref(&shell)).replacen(CHILDREN_MARKER, &inner, 1)
+}
+
+fn inline_children(node: &Node) -> Option<&[Node]> {
+    match node {
+        Node::Heading { children, .. }
+        | Node::Paragraph { children }
+        | Node::Bold { children }
+        | Node::Italic { children }
+        | Node::Strikethrough { children }
+        | Node::Underline { children }
+        | Node::Highlight { children }
+        | Node::Superscript { children }
+        | Node::Subscript { children }
+        | Node::Inserted { children }
+        | Node::BlockQuote { children }
+        | Node::Callout { children, .. }
+        | Node::DefinitionList { children }
+        | Node::DefinitionTerm { children }
+        | Node::DefinitionDescription { children }
+        | Node::CustomBlock { children, .. } => Some(children),
+        Node::Link { text, .. } => Some(text),
+        Node::InlineDirective { label, .. } | Node::LeafDirective { label, .. } => Some(label),
+        _ => None,
+    }
+}
+
+fn with_children(node: &Node, new_children: Vec<Node>) -> Node {
+    let mut node = node.clone();
+    match &mut node {
+        Node::Heading { children, .. }
+        | Node::Paragraph { children }
+        | Node::Bold { children }
+        | Node::Italic { children }
+        | Node::Strikethrough { children }
+        | Node::Underline { children }
+        | Node::Highlight { children }
+        | Node::Superscript { children }
+        | Node::Subscript { children }
+        | Node::Inserted { children }
+        | Node::BlockQuote { children }
+        | Node::Callout { children, .. }
+        | Node::DefinitionList { children }
+        | Node::DefinitionTerm { children }
+        | Node::DefinitionDescription { children }
+        | Node::CustomBlock { children, .. } => *children = new_children,
+        Node::Link { text, .. } => *text = new_children,
+        Node::InlineDirective { label, .. } | Node::LeafDirective { label, .. } => {
+            *label = new_children
+        }
+        _ => {}
+    }
+    node
+}
@@ -0,0 +1,64 @@
+//! Emoji shortcodes such as `:rocket:`, using GitHub's names.
+//!
+//! The table is compiled in with the `emoji` cargo feature. Without it no
+//! shortcode is known, so `:rocket:` stays text and the wasm build keeps
+//! its size; `Emoji` nodes built by hand still render.
+//!
+//! ```
+//! # #[cfg(feature = "emoji")]
+//! # {
+//! use mdkit::emoji;
+//!
+//! assert_eq!(emoji::lookup("rocket"), Some("🚀"));
+//! assert_eq!(emoji::codepoints("#\u{fe0f}\u{20e3}"), "0023-20e3");
+//! # }
+//! ```
+
+#[cfg(feature = "emoji")]
+mod table;
+
+/// Image URL template for GitHub's emoji images, for
+/// [`Renderer::emoji_images`](crate::renderer::Renderer::emoji_images).
+pub const GITHUB_IMAGES: &str =
+    "https://github.githubassets.com/images/icons/emoji/unicode/{codepoints}.png";
+
+/// Every known shortcode with its emoji, sorted by shortcode. Empty
+/// without the `emoji` feature.
+pub fn shortcodes() -> &'static [(&'static str, &'static str)] {
+    #[cfg(feature = "emoji")]
+    {
+        table::EMOJI
+    }
+    #[cfg(not(feature = "emoji"))]
+    {
+        &[]
+    }
+}
+
+/// The emoji for a shortcode given without colons.
+pub fn lookup(shortcode: &str) -> Option<&'static str> {
+    let table = shortcodes();
+    table
+        .binary_search_by_key(&shortcode, |&(name, _)| name)
+        .ok()
+        .map(|i| table[i].1)
+}
+
+/// Shortcodes are lowercase ASCII letters, digits, `_`, `+` and `-`.
+pub fn is_shortcode(name: &str) -> bool {
+    !name.is_empty()
+        && name.chars().all(|c| {
+            c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '+' || c == '-'
+        })
+}
+
+/// Lowercase hex code points of at least four digits joined by `-`,
+/// without variation selectors, as in the file names of GitHub's images.
+pub fn codepoints(unicode: &str) -> String {
+    unicode
+        .chars()
+        .filter(|&c| c != '\u{fe0f}')
+        .map(|c| format!("{:04x}", c as u32))
+        .collect::<Vec<_>>()
+        .join("-")
+}
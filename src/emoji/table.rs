@@ -0,0 +1,829 @@
+//! Shortcode table, sorted by shortcode for binary search. Names follow
+//! GitHub's; emoji that default to text presentation carry U+FE0F.
+
+pub(super) static EMOJI: &[(&str, &str)] = &[
+    ("+1", "👍"),
+    ("-1", "👎"),
+    ("100", "💯"),
+    ("1234", "🔢"),
+    ("1st_place_medal", "🥇"),
+    ("2nd_place_medal", "🥈"),
+    ("3rd_place_medal", "🥉"),
+    ("a", "🅰\u{fe0f}"),
+    ("ab", "🆎"),
+    ("abacus", "🧮"),
+    ("abc", "🔤"),
+    ("abcd", "🔡"),
+    ("adhesive_bandage", "🩹"),
+    ("airplane", "✈\u{fe0f}"),
+    ("alarm_clock", "⏰"),
+    ("alembic", "⚗\u{fe0f}"),
+    ("alien", "👽"),
+    ("ambulance", "🚑"),
+    ("anchor", "⚓"),
+    ("anger", "💢"),
+    ("angry", "😠"),
+    ("anguished", "😧"),
+    ("ant", "🐜"),
+    ("apple", "🍎"),
+    ("arrow_backward", "◀\u{fe0f}"),
+    ("arrow_down", "⬇\u{fe0f}"),
+    ("arrow_forward", "▶\u{fe0f}"),
+    ("arrow_heading_down", "⤵\u{fe0f}"),
+    ("arrow_heading_up", "⤴\u{fe0f}"),
+    ("arrow_left", "⬅\u{fe0f}"),
+    ("arrow_lower_left", "↙\u{fe0f}"),
+    ("arrow_lower_right", "↘\u{fe0f}"),
+    ("arrow_right", "➡\u{fe0f}"),
+    ("arrow_right_hook", "↪\u{fe0f}"),
+    ("arrow_up", "⬆\u{fe0f}"),
+    ("arrow_up_down", "↕\u{fe0f}"),
+    ("arrow_upper_left", "↖\u{fe0f}"),
+    ("arrow_upper_right", "↗\u{fe0f}"),
+    ("arrows_clockwise", "🔃"),
+    ("arrows_counterclockwise", "🔄"),
+    ("art", "🎨"),
+    ("asterisk", "*\u{fe0f}\u{20e3}"),
+    ("astonished", "😲"),
+    ("atm", "🏧"),
+    ("atom_symbol", "⚛\u{fe0f}"),
+    ("avocado", "🥑"),
+    ("b", "🅱\u{fe0f}"),
+    ("baby", "👶"),
+    ("baby_chick", "🐤"),
+    ("baby_symbol", "🚼"),
+    ("back", "🔙"),
+    ("bacon", "🥓"),
+    ("balloon", "🎈"),
+    ("ballot_box_with_check", "☑\u{fe0f}"),
+    ("banana", "🍌"),
+    ("bangbang", "‼\u{fe0f}"),
+    ("bank", "🏦"),
+    ("bar_chart", "📊"),
+    ("baseball", "⚾"),
+    ("basket", "🧺"),
+    ("basketball", "🏀"),
+    ("bat", "🦇"),
+    ("battery", "🔋"),
+    ("beach_umbrella", "🏖\u{fe0f}"),
+    ("bear", "🐻"),
+    ("bed", "🛏\u{fe0f}"),
+    ("bee", "🐝"),
+    ("beer", "🍺"),
+    ("beers", "🍻"),
+    ("beetle", "🐞"),
+    ("beginner", "🔰"),
+    ("bell", "🔔"),
+    ("bento", "🍱"),
+    ("bike", "🚲"),
+    ("billed_cap", "🧢"),
+    ("biohazard", "☣\u{fe0f}"),
+    ("bird", "🐦"),
+    ("birthday", "🎂"),
+    ("black_circle", "⚫"),
+    ("black_flag", "🏴"),
+    ("black_heart", "🖤"),
+    ("black_joker", "🃏"),
+    ("black_large_square", "⬛"),
+    ("black_nib", "✒\u{fe0f}"),
+    ("blossom", "🌼"),
+    ("blowfish", "🐡"),
+    ("blue_book", "📘"),
+    ("blue_heart", "💙"),
+    ("blue_square", "🟦"),
+    ("blush", "😊"),
+    ("boar", "🐗"),
+    ("boat", "⛵"),
+    ("bomb", "💣"),
+    ("book", "📖"),
+    ("bookmark", "🔖"),
+    ("bookmark_tabs", "📑"),
+    ("books", "📚"),
+    ("boom", "💥"),
+    ("bouquet", "💐"),
+    ("bow", "🙇"),
+    ("bow_and_arrow", "🏹"),
+    ("bowling", "🎳"),
+    ("boy", "👦"),
+    ("brain", "🧠"),
+    ("brazil", "🇧🇷"),
+    ("bread", "🍞"),
+    ("bricks", "🧱"),
+    ("briefcase", "💼"),
+    ("broccoli", "🥦"),
+    ("broken_heart", "💔"),
+    ("broom", "🧹"),
+    ("brown_circle", "🟤"),
+    ("brown_heart", "🤎"),
+    ("brown_square", "🟫"),
+    ("bug", "🐛"),
+    ("building_construction", "🏗\u{fe0f}"),
+    ("bulb", "💡"),
+    ("bullettrain_side", "🚄"),
+    ("burrito", "🌯"),
+    ("bus", "🚌"),
+    ("bust_in_silhouette", "👤"),
+    ("busts_in_silhouette", "👥"),
+    ("butterfly", "🦋"),
+    ("cactus", "🌵"),
+    ("cake", "🍰"),
+    ("calendar", "📆"),
+    ("call_me_hand", "🤙"),
+    ("camel", "🐫"),
+    ("camera", "📷"),
+    ("camera_flash", "📸"),
+    ("camping", "🏕\u{fe0f}"),
+    ("canada", "🇨🇦"),
+    ("candle", "🕯\u{fe0f}"),
+    ("candy", "🍬"),
+    ("capital_abcd", "🔠"),
+    ("car", "🚗"),
+    ("card_file_box", "🗃\u{fe0f}"),
+    ("card_index_dividers", "🗂\u{fe0f}"),
+    ("carrot", "🥕"),
+    ("cat", "🐱"),
+    ("cat2", "🐈"),
+    ("cd", "💿"),
+    ("chains", "⛓\u{fe0f}"),
+    ("champagne", "🍾"),
+    ("chart_with_downwards_trend", "📉"),
+    ("chart_with_upwards_trend", "📈"),
+    ("checkered_flag", "🏁"),
+    ("cheese", "🧀"),
+    ("cherries", "🍒"),
+    ("cherry_blossom", "🌸"),
+    ("chess_pawn", "♟\u{fe0f}"),
+    ("chicken", "🐔"),
+    ("children_crossing", "🚸"),
+    ("chocolate_bar", "🍫"),
+    ("christmas_tree", "🎄"),
+    ("cl", "🆑"),
+    ("clap", "👏"),
+    ("clapper", "🎬"),
+    ("clinking_glasses", "🥂"),
+    ("clipboard", "📋"),
+    ("closed_book", "📕"),
+    ("closed_lock_with_key", "🔐"),
+    ("cloud", "☁\u{fe0f}"),
+    ("cloud_with_rain", "🌧\u{fe0f}"),
+    ("clown_face", "🤡"),
+    ("clubs", "♣\u{fe0f}"),
+    ("cn", "🇨🇳"),
+    ("cocktail", "🍸"),
+    ("coconut", "🥥"),
+    ("coffee", "☕"),
+    ("coffin", "⚰\u{fe0f}"),
+    ("cold_face", "🥶"),
+    ("cold_sweat", "😰"),
+    ("collision", "💥"),
+    ("comet", "☄\u{fe0f}"),
+    ("compass", "🧭"),
+    ("computer", "💻"),
+    ("computer_mouse", "🖱\u{fe0f}"),
+    ("confetti_ball", "🎊"),
+    ("confounded", "😖"),
+    ("confused", "😕"),
+    ("construction", "🚧"),
+    ("cookie", "🍪"),
+    ("cool", "🆒"),
+    ("copyright", "©\u{fe0f}"),
+    ("corn", "🌽"),
+    ("cow", "🐮"),
+    ("cowboy_hat_face", "🤠"),
+    ("crab", "🦀"),
+    ("crayon", "🖍\u{fe0f}"),
+    ("credit_card", "💳"),
+    ("crescent_moon", "🌙"),
+    ("crocodile", "🐊"),
+    ("croissant", "🥐"),
+    ("crossed_fingers", "🤞"),
+    ("crossed_flags", "🎌"),
+    ("crossed_swords", "⚔\u{fe0f}"),
+    ("crown", "👑"),
+    ("cry", "😢"),
+    ("crying_cat_face", "😿"),
+    ("crystal_ball", "🔮"),
+    ("cup_with_straw", "🥤"),
+    ("cupcake", "🧁"),
+    ("cupid", "💘"),
+    ("currency_exchange", "💱"),
+    ("curry", "🍛"),
+    ("cursing_face", "🤬"),
+    ("cyclone", "🌀"),
+    ("dagger", "🗡\u{fe0f}"),
+    ("dancer", "💃"),
+    ("dark_sunglasses", "🕶\u{fe0f}"),
+    ("dart", "🎯"),
+    ("dash", "💨"),
+    ("date", "📅"),
+    ("de", "🇩🇪"),
+    ("deciduous_tree", "🌳"),
+    ("desert", "🏜\u{fe0f}"),
+    ("desktop_computer", "🖥\u{fe0f}"),
+    ("detective", "🕵\u{fe0f}"),
+    ("diamond_shape_with_a_dot_inside", "💠"),
+    ("diamonds", "♦\u{fe0f}"),
+    ("disappointed", "😞"),
+    ("disappointed_relieved", "😥"),
+    ("dizzy", "💫"),
+    ("dizzy_face", "😵"),
+    ("dna", "🧬"),
+    ("dog", "🐶"),
+    ("dog2", "🐕"),
+    ("dollar", "💵"),
+    ("dolphin", "🐬"),
+    ("door", "🚪"),
+    ("doughnut", "🍩"),
+    ("dragon", "🐉"),
+    ("dragon_face", "🐲"),
+    ("dress", "👗"),
+    ("drooling_face", "🤤"),
+    ("droplet", "💧"),
+    ("drum", "🥁"),
+    ("duck", "🦆"),
+    ("dvd", "📀"),
+    ("e-mail", "📧"),
+    ("eagle", "🦅"),
+    ("earth_africa", "🌍"),
+    ("earth_americas", "🌎"),
+    ("earth_asia", "🌏"),
+    ("egg", "🥚"),
+    ("eggplant", "🍆"),
+    ("eight", "8\u{fe0f}\u{20e3}"),
+    ("eight_spoked_asterisk", "✳\u{fe0f}"),
+    ("eject_button", "⏏\u{fe0f}"),
+    ("electric_plug", "🔌"),
+    ("elephant", "🐘"),
+    ("email", "📧"),
+    ("end", "🔚"),
+    ("envelope", "✉\u{fe0f}"),
+    ("es", "🇪🇸"),
+    ("eu", "🇪🇺"),
+    ("euro", "💶"),
+    ("european_union", "🇪🇺"),
+    ("evergreen_tree", "🌲"),
+    ("exclamation", "❗"),
+    ("exploding_head", "🤯"),
+    ("expressionless", "😑"),
+    ("eye", "👁\u{fe0f}"),
+    ("eyeglasses", "👓"),
+    ("eyes", "👀"),
+    ("face_with_head_bandage", "🤕"),
+    ("face_with_thermometer", "🤒"),
+    ("facepalm", "🤦"),
+    ("facepunch", "👊"),
+    ("factory", "🏭"),
+    ("fallen_leaf", "🍂"),
+    ("fast_forward", "⏩"),
+    ("fearful", "😨"),
+    ("feet", "🐾"),
+    ("female_sign", "♀\u{fe0f}"),
+    ("file_folder", "📁"),
+    ("fire", "🔥"),
+    ("fire_engine", "🚒"),
+    ("fire_extinguisher", "🧯"),
+    ("fireworks", "🎆"),
+    ("fish", "🐟"),
+    ("fist", "✊"),
+    ("fist_left", "🤛"),
+    ("fist_oncoming", "👊"),
+    ("fist_raised", "✊"),
+    ("fist_right", "🤜"),
+    ("five", "5\u{fe0f}\u{20e3}"),
+    ("flashlight", "🔦"),
+    ("fleur_de_lis", "⚜\u{fe0f}"),
+    ("flipper", "🐬"),
+    ("floppy_disk", "💾"),
+    ("flushed", "😳"),
+    ("flying_saucer", "🛸"),
+    ("fog", "🌫\u{fe0f}"),
+    ("football", "🏈"),
+    ("four", "4\u{fe0f}\u{20e3}"),
+    ("four_leaf_clover", "🍀"),
+    ("fox_face", "🦊"),
+    ("fr", "🇫🇷"),
+    ("free", "🆓"),
+    ("fries", "🍟"),
+    ("frog", "🐸"),
+    ("frowning", "😦"),
+    ("frowning_face", "☹\u{fe0f}"),
+    ("fu", "🖕"),
+    ("fuelpump", "⛽"),
+    ("full_moon", "🌕"),
+    ("game_die", "🎲"),
+    ("gb", "🇬🇧"),
+    ("gear", "⚙\u{fe0f}"),
+    ("gem", "💎"),
+    ("ghost", "👻"),
+    ("gift", "🎁"),
+    ("gift_heart", "💝"),
+    ("giraffe", "🦒"),
+    ("girl", "👧"),
+    ("globe_with_meridians", "🌐"),
+    ("goal_net", "🥅"),
+    ("golf", "⛳"),
+    ("grapes", "🍇"),
+    ("green_apple", "🍏"),
+    ("green_book", "📗"),
+    ("green_circle", "🟢"),
+    ("green_heart", "💚"),
+    ("green_square", "🟩"),
+    ("grey_exclamation", "❕"),
+    ("grey_question", "❔"),
+    ("grimacing", "😬"),
+    ("grin", "😁"),
+    ("grinning", "😀"),
+    ("guitar", "🎸"),
+    ("gun", "🔫"),
+    ("hamburger", "🍔"),
+    ("hammer", "🔨"),
+    ("hammer_and_wrench", "🛠\u{fe0f}"),
+    ("hamster", "🐹"),
+    ("hand", "✋"),
+    ("hand_over_mouth", "🤭"),
+    ("handbag", "👜"),
+    ("handshake", "🤝"),
+    ("hankey", "💩"),
+    ("hash", "#\u{fe0f}\u{20e3}"),
+    ("headphones", "🎧"),
+    ("hear_no_evil", "🙉"),
+    ("heart", "❤\u{fe0f}"),
+    ("heart_decoration", "💟"),
+    ("heart_eyes", "😍"),
+    ("heart_eyes_cat", "😻"),
+    ("heartbeat", "💓"),
+    ("heartpulse", "💗"),
+    ("hearts", "♥\u{fe0f}"),
+    ("heavy_check_mark", "✔\u{fe0f}"),
+    ("heavy_division_sign", "➗"),
+    ("heavy_dollar_sign", "💲"),
+    ("heavy_exclamation_mark", "❗"),
+    ("heavy_heart_exclamation", "❣\u{fe0f}"),
+    ("heavy_minus_sign", "➖"),
+    ("heavy_multiplication_x", "✖\u{fe0f}"),
+    ("heavy_plus_sign", "➕"),
+    ("hedgehog", "🦔"),
+    ("helicopter", "🚁"),
+    ("herb", "🌿"),
+    ("honey_pot", "🍯"),
+    ("honeybee", "🐝"),
+    ("horse", "🐴"),
+    ("hospital", "🏥"),
+    ("hot_face", "🥵"),
+    ("hot_pepper", "🌶\u{fe0f}"),
+    ("hotdog", "🌭"),
+    ("hourglass", "⌛"),
+    ("hourglass_flowing_sand", "⏳"),
+    ("house", "🏠"),
+    ("hugs", "🤗"),
+    ("hushed", "😯"),
+    ("icecream", "🍦"),
+    ("id", "🆔"),
+    ("imp", "👿"),
+    ("inbox_tray", "📥"),
+    ("incoming_envelope", "📨"),
+    ("india", "🇮🇳"),
+    ("infinity", "♾\u{fe0f}"),
+    ("information_source", "ℹ\u{fe0f}"),
+    ("innocent", "😇"),
+    ("interrobang", "⁉\u{fe0f}"),
+    ("iphone", "📱"),
+    ("it", "🇮🇹"),
+    ("jack_o_lantern", "🎃"),
+    ("japanese_goblin", "👺"),
+    ("japanese_ogre", "👹"),
+    ("jeans", "👖"),
+    ("jigsaw", "🧩"),
+    ("joy", "😂"),
+    ("joy_cat", "😹"),
+    ("joystick", "🕹\u{fe0f}"),
+    ("jp", "🇯🇵"),
+    ("key", "🔑"),
+    ("keyboard", "⌨\u{fe0f}"),
+    ("keycap_ten", "🔟"),
+    ("kiss", "💋"),
+    ("kissing", "😗"),
+    ("kissing_cat", "😽"),
+    ("kissing_closed_eyes", "😚"),
+    ("kissing_heart", "😘"),
+    ("kissing_smiling_eyes", "😙"),
+    ("kiwi_fruit", "🥝"),
+    ("koala", "🐨"),
+    ("kr", "🇰🇷"),
+    ("label", "🏷\u{fe0f}"),
+    ("lady_beetle", "🐞"),
+    ("large_blue_circle", "🔵"),
+    ("large_blue_diamond", "🔷"),
+    ("large_orange_diamond", "🔶"),
+    ("laughing", "😆"),
+    ("leaves", "🍃"),
+    ("ledger", "📒"),
+    ("left_right_arrow", "↔\u{fe0f}"),
+    ("leftwards_arrow_with_hook", "↩\u{fe0f}"),
+    ("lemon", "🍋"),
+    ("link", "🔗"),
+    ("lion", "🦁"),
+    ("lips", "👄"),
+    ("lipstick", "💄"),
+    ("lizard", "🦎"),
+    ("lock", "🔒"),
+    ("lock_with_ink_pen", "🔏"),
+    ("lollipop", "🍭"),
+    ("loud_sound", "🔊"),
+    ("loudspeaker", "📢"),
+    ("love_letter", "💌"),
+    ("love_you_gesture", "🤟"),
+    ("lying_face", "🤥"),
+    ("mag", "🔍"),
+    ("mag_right", "🔎"),
+    ("magnet", "🧲"),
+    ("mailbox", "📫"),
+    ("male_sign", "♂\u{fe0f}"),
+    ("man", "👨"),
+    ("mandarin", "🍊"),
+    ("mango", "🥭"),
+    ("maple_leaf", "🍁"),
+    ("mask", "😷"),
+    ("medal_sports", "🏅"),
+    ("medical_symbol", "⚕\u{fe0f}"),
+    ("mega", "📣"),
+    ("melon", "🍈"),
+    ("memo", "📝"),
+    ("mens", "🚹"),
+    ("metal", "🤘"),
+    ("microphone", "🎤"),
+    ("microscope", "🔬"),
+    ("middle_finger", "🖕"),
+    ("milk_glass", "🥛"),
+    ("milky_way", "🌌"),
+    ("minidisc", "💽"),
+    ("mobile_phone_off", "📴"),
+    ("money_mouth_face", "🤑"),
+    ("money_with_wings", "💸"),
+    ("moneybag", "💰"),
+    ("monkey", "🐒"),
+    ("monkey_face", "🐵"),
+    ("monocle_face", "🧐"),
+    ("mortar_board", "🎓"),
+    ("motorcycle", "🏍\u{fe0f}"),
+    ("mountain", "⛰\u{fe0f}"),
+    ("mouse", "🐭"),
+    ("movie_camera", "🎥"),
+    ("moyai", "🗿"),
+    ("muscle", "💪"),
+    ("mushroom", "🍄"),
+    ("musical_keyboard", "🎹"),
+    ("musical_note", "🎵"),
+    ("mute", "🔇"),
+    ("nail_care", "💅"),
+    ("name_badge", "📛"),
+    ("nauseated_face", "🤢"),
+    ("necktie", "👔"),
+    ("negative_squared_cross_mark", "❎"),
+    ("nerd_face", "🤓"),
+    ("neutral_face", "😐"),
+    ("new", "🆕"),
+    ("new_moon", "🌑"),
+    ("new_moon_with_face", "🌚"),
+    ("newspaper", "📰"),
+    ("nine", "9\u{fe0f}\u{20e3}"),
+    ("ninja", "🥷"),
+    ("no_bell", "🔕"),
+    ("no_entry", "⛔"),
+    ("no_entry_sign", "🚫"),
+    ("no_mouth", "😶"),
+    ("no_smoking", "🚭"),
+    ("notebook", "📓"),
+    ("notes", "🎶"),
+    ("nut_and_bolt", "🔩"),
+    ("o", "⭕"),
+    ("o2", "🅾\u{fe0f}"),
+    ("ocean", "🌊"),
+    ("octopus", "🐙"),
+    ("office", "🏢"),
+    ("ok", "🆗"),
+    ("ok_hand", "👌"),
+    ("old_key", "🗝\u{fe0f}"),
+    ("older_man", "👴"),
+    ("older_woman", "👵"),
+    ("on", "🔛"),
+    ("one", "1\u{fe0f}\u{20e3}"),
+    ("open_book", "📖"),
+    ("open_file_folder", "📂"),
+    ("open_hands", "👐"),
+    ("open_mouth", "😮"),
+    ("orange", "🍊"),
+    ("orange_book", "📙"),
+    ("orange_circle", "🟠"),
+    ("orange_heart", "🧡"),
+    ("orange_square", "🟧"),
+    ("otter", "🦦"),
+    ("outbox_tray", "📤"),
+    ("owl", "🦉"),
+    ("package", "📦"),
+    ("page_facing_up", "📄"),
+    ("page_with_curl", "📃"),
+    ("paintbrush", "🖌\u{fe0f}"),
+    ("palm_tree", "🌴"),
+    ("palms_up_together", "🤲"),
+    ("pancakes", "🥞"),
+    ("panda_face", "🐼"),
+    ("paperclip", "📎"),
+    ("parking", "🅿\u{fe0f}"),
+    ("part_alternation_mark", "〽\u{fe0f}"),
+    ("partly_sunny", "⛅"),
+    ("partying_face", "🥳"),
+    ("passport_control", "🛂"),
+    ("pause_button", "⏸\u{fe0f}"),
+    ("paw_prints", "🐾"),
+    ("peace_symbol", "☮\u{fe0f}"),
+    ("peach", "🍑"),
+    ("pear", "🍐"),
+    ("pen", "🖊\u{fe0f}"),
+    ("pencil", "📝"),
+    ("pencil2", "✏\u{fe0f}"),
+    ("penguin", "🐧"),
+    ("pensive", "😔"),
+    ("performing_arts", "🎭"),
+    ("persevere", "😣"),
+    ("phone", "☎\u{fe0f}"),
+    ("pie", "🥧"),
+    ("pig", "🐷"),
+    ("pill", "💊"),
+    ("pinching_hand", "🤏"),
+    ("pineapple", "🍍"),
+    ("pirate_flag", "🏴\u{200d}☠\u{fe0f}"),
+    ("pizza", "🍕"),
+    ("pleading_face", "🥺"),
+    ("point_down", "👇"),
+    ("point_left", "👈"),
+    ("point_right", "👉"),
+    ("point_up", "☝\u{fe0f}"),
+    ("point_up_2", "👆"),
+    ("police_car", "🚓"),
+    ("poop", "💩"),
+    ("popcorn", "🍿"),
+    ("potable_water", "🚰"),
+    ("potato", "🥔"),
+    ("pound", "💷"),
+    ("pout", "😡"),
+    ("pouting_cat", "😾"),
+    ("pray", "🙏"),
+    ("printer", "🖨\u{fe0f}"),
+    ("punch", "👊"),
+    ("purple_circle", "🟣"),
+    ("purple_heart", "💜"),
+    ("purple_square", "🟪"),
+    ("pushpin", "📌"),
+    ("put_litter_in_its_place", "🚮"),
+    ("question", "❓"),
+    ("rabbit", "🐰"),
+    ("radio", "📻"),
+    ("radio_button", "🔘"),
+    ("radioactive", "☢\u{fe0f}"),
+    ("rage", "😡"),
+    ("rainbow", "🌈"),
+    ("rainbow_flag", "🏳\u{fe0f}\u{200d}🌈"),
+    ("raised_back_of_hand", "🤚"),
+    ("raised_eyebrow", "🤨"),
+    ("raised_hand", "✋"),
+    ("raised_hands", "🙌"),
+    ("ramen", "🍜"),
+    ("receipt", "🧾"),
+    ("record_button", "⏺\u{fe0f}"),
+    ("recycle", "♻\u{fe0f}"),
+    ("red_car", "🚗"),
+    ("red_circle", "🔴"),
+    ("red_square", "🟥"),
+    ("registered", "®\u{fe0f}"),
+    ("relaxed", "☺\u{fe0f}"),
+    ("relieved", "😌"),
+    ("repeat", "🔁"),
+    ("rescue_worker_helmet", "⛑\u{fe0f}"),
+    ("restroom", "🚻"),
+    ("revolving_hearts", "💞"),
+    ("rewind", "⏪"),
+    ("ribbon", "🎀"),
+    ("rice", "🍚"),
+    ("ring", "💍"),
+    ("robot", "🤖"),
+    ("rocket", "🚀"),
+    ("rofl", "🤣"),
+    ("roll_eyes", "🙄"),
+    ("rooster", "🐓"),
+    ("rose", "🌹"),
+    ("rotating_light", "🚨"),
+    ("round_pushpin", "📍"),
+    ("ru", "🇷🇺"),
+    ("runner", "🏃"),
+    ("running", "🏃"),
+    ("safety_vest", "🦺"),
+    ("sailboat", "⛵"),
+    ("sandwich", "🥪"),
+    ("satellite", "📡"),
+    ("satisfied", "😆"),
+    ("sauropod", "🦕"),
+    ("saxophone", "🎷"),
+    ("school", "🏫"),
+    ("school_satchel", "🎒"),
+    ("scissors", "✂\u{fe0f}"),
+    ("scorpion", "🦂"),
+    ("scream", "😱"),
+    ("scream_cat", "🙀"),
+    ("see_no_evil", "🙈"),
+    ("seedling", "🌱"),
+    ("selfie", "🤳"),
+    ("seven", "7\u{fe0f}\u{20e3}"),
+    ("shark", "🦈"),
+    ("shield", "🛡\u{fe0f}"),
+    ("ship", "🚢"),
+    ("shirt", "👕"),
+    ("shit", "💩"),
+    ("shopping_cart", "🛒"),
+    ("shower", "🚿"),
+    ("shrimp", "🦐"),
+    ("shrug", "🤷"),
+    ("shushing_face", "🤫"),
+    ("six", "6\u{fe0f}\u{20e3}"),
+    ("skull", "💀"),
+    ("skull_and_crossbones", "☠\u{fe0f}"),
+    ("sleeping", "😴"),
+    ("sleepy", "😪"),
+    ("slightly_frowning_face", "🙁"),
+    ("slightly_smiling_face", "🙂"),
+    ("sloth", "🦥"),
+    ("small_blue_diamond", "🔹"),
+    ("small_orange_diamond", "🔸"),
+    ("small_red_triangle", "🔺"),
+    ("small_red_triangle_down", "🔻"),
+    ("smile", "😄"),
+    ("smile_cat", "😸"),
+    ("smiley", "😃"),
+    ("smiley_cat", "😺"),
+    ("smiling_face_with_three_hearts", "🥰"),
+    ("smiling_imp", "😈"),
+    ("smirk", "😏"),
+    ("smirk_cat", "😼"),
+    ("snail", "🐌"),
+    ("snake", "🐍"),
+    ("sneezing_face", "🤧"),
+    ("snowflake", "❄\u{fe0f}"),
+    ("snowman", "⛄"),
+    ("snowman_with_snow", "☃\u{fe0f}"),
+    ("soap", "🧼"),
+    ("sob", "😭"),
+    ("soccer", "⚽"),
+    ("soon", "🔜"),
+    ("sos", "🆘"),
+    ("sound", "🔉"),
+    ("space_invader", "👾"),
+    ("spades", "♠\u{fe0f}"),
+    ("spaghetti", "🍝"),
+    ("sparkle", "❇\u{fe0f}"),
+    ("sparkler", "🎇"),
+    ("sparkles", "✨"),
+    ("sparkling_heart", "💖"),
+    ("speak_no_evil", "🙊"),
+    ("speaker", "🔈"),
+    ("speech_balloon", "💬"),
+    ("spider", "🕷\u{fe0f}"),
+    ("sponge", "🧽"),
+    ("squid", "🦑"),
+    ("star", "⭐"),
+    ("star2", "🌟"),
+    ("star_struck", "🤩"),
+    ("stars", "🌠"),
+    ("statue_of_liberty", "🗽"),
+    ("steam_locomotive", "🚂"),
+    ("stethoscope", "🩺"),
+    ("stop_button", "⏹\u{fe0f}"),
+    ("stop_sign", "🛑"),
+    ("stopwatch", "⏱\u{fe0f}"),
+    ("straight_ruler", "📏"),
+    ("strawberry", "🍓"),
+    ("stuck_out_tongue", "😛"),
+    ("stuck_out_tongue_closed_eyes", "😝"),
+    ("stuck_out_tongue_winking_eye", "😜"),
+    ("sun_with_face", "🌞"),
+    ("sunflower", "🌻"),
+    ("sunglasses", "😎"),
+    ("sunny", "☀\u{fe0f}"),
+    ("superhero", "🦸"),
+    ("sushi", "🍣"),
+    ("sweat", "😓"),
+    ("sweat_drops", "💦"),
+    ("sweat_smile", "😅"),
+    ("symbols", "🔣"),
+    ("syringe", "💉"),
+    ("t-rex", "🦖"),
+    ("taco", "🌮"),
+    ("tada", "🎉"),
+    ("tangerine", "🍊"),
+    ("taxi", "🚕"),
+    ("tea", "🍵"),
+    ("technologist", "🧑\u{200d}💻"),
+    ("telephone", "☎\u{fe0f}"),
+    ("telephone_receiver", "📞"),
+    ("telescope", "🔭"),
+    ("tennis", "🎾"),
+    ("test_tube", "🧪"),
+    ("thermometer", "🌡\u{fe0f}"),
+    ("thinking", "🤔"),
+    ("thought_balloon", "💭"),
+    ("thread", "🧵"),
+    ("three", "3\u{fe0f}\u{20e3}"),
+    ("thumbsdown", "👎"),
+    ("thumbsup", "👍"),
+    ("ticket", "🎫"),
+    ("tickets", "🎟\u{fe0f}"),
+    ("tiger", "🐯"),
+    ("timer_clock", "⏲\u{fe0f}"),
+    ("tired_face", "😫"),
+    ("tm", "™\u{fe0f}"),
+    ("toilet", "🚽"),
+    ("tokyo_tower", "🗼"),
+    ("tomato", "🍅"),
+    ("tongue", "👅"),
+    ("toolbox", "🧰"),
+    ("top", "🔝"),
+    ("tophat", "🎩"),
+    ("tornado", "🌪\u{fe0f}"),
+    ("traffic_light", "🚥"),
+    ("train", "🚋"),
+    ("triangular_flag_on_post", "🚩"),
+    ("triangular_ruler", "📐"),
+    ("trident", "🔱"),
+    ("triumph", "😤"),
+    ("trophy", "🏆"),
+    ("tropical_drink", "🍹"),
+    ("tropical_fish", "🐠"),
+    ("truck", "🚚"),
+    ("trumpet", "🎺"),
+    ("tshirt", "👕"),
+    ("tulip", "🌷"),
+    ("turtle", "🐢"),
+    ("tv", "📺"),
+    ("twisted_rightwards_arrows", "🔀"),
+    ("two", "2\u{fe0f}\u{20e3}"),
+    ("two_hearts", "💕"),
+    ("uk", "🇬🇧"),
+    ("umbrella", "☔"),
+    ("unamused", "😒"),
+    ("underage", "🔞"),
+    ("unicorn", "🦄"),
+    ("unlock", "🔓"),
+    ("up", "🆙"),
+    ("upside_down_face", "🙃"),
+    ("us", "🇺🇸"),
+    ("v", "✌\u{fe0f}"),
+    ("vertical_traffic_light", "🚦"),
+    ("vibration_mode", "📳"),
+    ("video_camera", "📹"),
+    ("video_game", "🎮"),
+    ("violin", "🎻"),
+    ("volcano", "🌋"),
+    ("volleyball", "🏐"),
+    ("vomiting_face", "🤮"),
+    ("vs", "🆚"),
+    ("vulcan_salute", "🖖"),
+    ("walking", "🚶"),
+    ("warning", "⚠\u{fe0f}"),
+    ("wastebasket", "🗑\u{fe0f}"),
+    ("watch", "⌚"),
+    ("watermelon", "🍉"),
+    ("wave", "👋"),
+    ("wc", "🚾"),
+    ("weary", "😩"),
+    ("whale", "🐳"),
+    ("whale2", "🐋"),
+    ("wheelchair", "♿"),
+    ("white_check_mark", "✅"),
+    ("white_circle", "⚪"),
+    ("white_flag", "🏳\u{fe0f}"),
+    ("white_heart", "🤍"),
+    ("white_large_square", "⬜"),
+    ("wine_glass", "🍷"),
+    ("wink", "😉"),
+    ("wolf", "🐺"),
+    ("woman", "👩"),
+    ("womens", "🚺"),
+    ("woozy_face", "🥴"),
+    ("world_map", "🗺\u{fe0f}"),
+    ("worried", "😟"),
+    ("wrench", "🔧"),
+    ("writing_hand", "✍\u{fe0f}"),
+    ("x", "❌"),
+    ("yawning_face", "🥱"),
+    ("yellow_circle", "🟡"),
+    ("yellow_heart", "💛"),
+    ("yellow_square", "🟨"),
+    ("yen", "💴"),
+    ("yin_yang", "☯\u{fe0f}"),
+    ("yum", "😋"),
+    ("zany_face", "🤪"),
+    ("zap", "⚡"),
+    ("zero", "0\u{fe0f}\u{20e3}"),
+    ("zipper_mouth_face", "🤐"),
+    ("zombie", "🧟"),
+    ("zzz", "💤"),
+];
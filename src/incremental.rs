@@ -0,0 +1,242 @@
+//! Re-parsing of edited documents for editors.
+//!
+//! The document keeps its top-level blocks with their spans. An edit
+//! re-parses from the block before the edited region and stops as soon as
+//! a freshly parsed block ends where an old block, shifted by the edit,
+//! began again; every block after that point is reused. Because blocks are
+//! re-parsed until the boundaries line up, edits that open or close a code
+//! fence, custom block or list automatically extend to the enclosing block.
+//!
+//! Offsets are in characters (Unicode scalar values), matching
+//! [`Parser::position`].
+//!
+//! ```
+//! use mdkit::incremental::IncrementalDocument;
+//!
+//! let mut doc = IncrementalDocument::new("# Title\n\nFirst\n\nSecond\n");
+//! let change = doc.edit(9..14, "Changed");
+//!
+//! assert_eq!((change.start, change.removed, change.inserted), (1, 1, 1));
+//! assert_eq!(doc.text(), "# Title\n\nChanged\n\nSecond\n");
+//! ```
+
+use std::ops::Range;
+
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+use crate::ast::Node;
+use crate::parser::{Block, Parser};
+use crate::renderer;
+use crate::to_js;
+
+/// Which top-level blocks an edit replaced: `removed` blocks starting at
+/// `start` were replaced by `inserted` new ones, like `Array.splice`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct BlockChange {
+    pub start: usize,
+    pub removed: usize,
+    pub inserted: usize,
+}
+
+pub struct IncrementalDocument {
+    text: Vec<char>,
+    blocks: Vec<Block>,
+}
+
+impl IncrementalDocument {
+    pub fn new(input: &str) -> Self {
+        Self {
+            text: input.chars().collect(),
+            blocks: Parser::new(input).parse_blocks(),
+        }
+    }
+
+    pub fn text(&self) -> String {
+        self.text.iter().collect()
+    }
+
+    pub fn blocks(&self) -> &[Block] {
+        &self.blocks
+    }
+
+    pub fn nodes(&self) -> Vec<Node> {
+        self.blocks.iter().map(|block| block.node.clone()).collect()
+    }
+
+    pub fn render(&self) -> String {
+        renderer::render(&self.nodes())
+    }
+
+    /// Replaces the characters in `range` with `new_text` and re-parses the
+    /// affected blocks.
+    pub fn edit(&mut self, range: Range<usize>, new_text: &str) -> BlockChange {
+        let start = range.start.min(self.text.len());
+        let end = range.end.clamp(start, self.text.len());
+        let inserted: Vec<char> = new_text.chars().collect();
+        let delta = inserted.len() as isize - (end - start) as isize;
+        let edit_end = start + inserted.len();
+
+        self.text.splice(start..end, inserted);
+
+        // First block reaching the edit. Blocks before it are re-parsed too
+        // when the lines they peek at overlap the edit.
+        let mut first = self
+            .blocks
+            .iter()
+            .position(|block| block.span.end >= start)
+            .unwrap_or(self.blocks.len());
+        while first > 0 && self.lookahead_end(&self.blocks[first - 1]) >= start {
+            first -= 1;
+        }
+        // An unclosed `---` or `+++` first line becomes front matter as soon
+        // as a closing line is typed anywhere below it.
+        let leading: String = self.text.iter().take_while(|&&c| c != '\n').collect();
+        let front_matter = matches!(
+            self.blocks.first(),
+            Some(Block {
+                node: Node::FrontMatter { .. },
+                ..
+            })
+        );
+        if !front_matter && matches!(leading.trim_end(), "---" | "+++") {
+            first = 0;
+        }
+        let reparse_from = self
+            .blocks
+            .get(first)
+            .map_or(start, |block| block.span.start.min(start));
+
+        // Old blocks that start after the edited range; their source is
+        // unchanged, so they can be reused once parsing lines up with them.
+        let reusable = self
+            .blocks
+            .iter()
+            .position(|block| block.span.start >= end)
+            .unwrap_or(self.blocks.len());
+
+        let source: String = self.text.iter().collect();
+        let mut parser = Parser::new(&source);
+        parser.seek(reparse_from);
+
+        let mut fresh = Vec::new();
+        let mut resume = self.blocks.len();
+        loop {
+            if parser.position() >= edit_end {
+                let next = next_start(&self.text, parser.position());
+                let found = self.blocks[reusable..]
+                    .binary_search_by_key(&next, |block| shift(block.span.start, delta));
+                if let Ok(offset) = found {
+                    resume = reusable + offset;
+                    break;
+                }
+            }
+
+            match parser.next_block() {
+                Some(block) => fresh.push(block),
+                None => break,
+            }
+        }
+
+        let mut tail: Vec<Block> = self.blocks.drain(resume..).collect();
+        for block in &mut tail {
+            block.span = shift(block.span.start, delta)..shift(block.span.end, delta);
+        }
+
+        let change = BlockChange {
+            start: first,
+            removed: self.blocks.len() - first,
+            inserted: fresh.len(),
+        };
+
+        self.blocks.truncate(first);
+        self.blocks.extend(fresh);
+        self.blocks.extend(tail);
+        change
+    }
+}
+
+impl IncrementalDocument {
+    /// End of the text a block's parser looked at past its span. Lists,
+    /// tables and terms peek at the next line; a definition list looks past
+    /// blank lines for a term and the `: ` line after it.
+    fn lookahead_end(&self, block: &Block) -> usize {
+        let line_end = |from: usize| {
+            self.text[from.min(self.text.len())..]
+                .iter()
+                .position(|&c| c == '\n')
+                .map_or(self.text.len(), |i| from + i)
+        };
+        match block.node {
+            Node::DefinitionList { .. } => {
+                let term_end = line_end(next_start(&self.text, block.span.end));
+                line_end(term_end + 1)
+            }
+            _ => line_end(block.span.end),
+        }
+    }
+}
+
+fn shift(offset: usize, delta: isize) -> usize {
+    (offset as isize + delta) as usize
+}
+
+/// Offset of the first non-whitespace character at or after `pos`, which is
+/// where the parser would start its next block.
+fn next_start(text: &[char], pos: usize) -> usize {
+    text[pos.min(text.len())..]
+        .iter()
+        .position(|c| !c.is_whitespace())
+        .map_or(text.len(), |i| pos + i)
+}
+
+/// JavaScript handle over [`IncrementalDocument`]. `edit` returns
+/// `{ start, removed, nodes }`, so `ast.splice(start, removed, ...nodes)`
+/// keeps a client-side copy of the AST in sync.
+#[wasm_bindgen(js_name = IncrementalDocument)]
+pub struct JsIncrementalDocument {
+    inner: IncrementalDocument,
+}
+
+#[derive(Serialize)]
+struct JsBlockChange<'a> {
+    start: usize,
+    removed: usize,
+    nodes: Vec<&'a Node>,
+}
+
+#[wasm_bindgen(js_class = IncrementalDocument)]
+impl JsIncrementalDocument {
+    #[wasm_bindgen(constructor)]
+    pub fn new(input: &str) -> Self {
+        Self {
+            inner: IncrementalDocument::new(input),
+        }
+    }
+
+    #[wasm_bindgen(unchecked_return_type = "BlockEdit")]
+    pub fn edit(&mut self, start: usize, end: usize, text: &str) -> Result<JsValue, JsError> {
+        let change = self.inner.edit(start..end, text);
+        let blocks = &self.inner.blocks()[change.start..change.start + change.inserted];
+
+        Ok(to_js(&JsBlockChange {
+            start: change.start,
+            removed: change.removed,
+            nodes: blocks.iter().map(|block| &block.node).collect(),
+        })?)
+    }
+
+    #[wasm_bindgen(unchecked_return_type = "Node[]")]
+    pub fn nodes(&self) -> Result<JsValue, JsError> {
+        Ok(to_js(&self.inner.nodes())?)
+    }
+
+    #[wasm_bindgen(js_name = toHtml)]
+    pub fn to_html(&self) -> String {
+        self.inner.render()
+    }
+
+    pub fn text(&self) -> String {
+        self.inner.text()
+    }
+}
@@ -1,9 +1,27 @@
-use serde_wasm_bindgen::to_value;
+use serde::Serialize;
+use serde_wasm_bindgen::{Serializer, from_value};
 use wasm_bindgen::prelude::*;
 
 pub mod ast;
+pub mod diagnostics;
+pub mod diff;
+pub mod emoji;
+pub mod incremental;
+pub mod links;
+pub mod lint;
+pub mod markdown;
 pub mod parser;
+pub mod query;
 pub mod renderer;
+pub mod search;
+pub mod site;
+pub mod streaming;
+pub mod toc;
+pub mod transform;
+pub mod wiki;
+
+#[wasm_bindgen(typescript_custom_section)]
+const AST_TYPES: &'static str = ast::typescript::DEFINITIONS;
 
 #[wasm_bindgen]
 pub fn markdown_to_html(input: &str) -> String {
@@ -12,10 +30,165 @@ pub fn markdown_to_html(input: &str) -> String {
     renderer::render(&ast)
 }
 
-#[wasm_bindgen]
+#[wasm_bindgen(unchecked_return_type = "Node[]")]
 pub fn markdown_to_ast(input: &str) -> JsValue {
     let mut parser = crate::parser::Parser::new(input);
     let nodes = parser.parse_document();
 
-    to_value(&nodes).unwrap_or(JsValue::NULL)
+    to_js(&nodes).unwrap_or(JsValue::NULL)
+}
+
+/// The AST with front matter decoded into `metadata`.
+#[wasm_bindgen(unchecked_return_type = "Document")]
+pub fn markdown_to_document(input: &str) -> Result<JsValue, JsError> {
+    let nodes = parser::Parser::new(input).parse_document();
+    Ok(to_js(&ast::Document::new(nodes))?)
+}
+
+/// Like `markdown_to_html`, with limits for untrusted input.
+#[wasm_bindgen]
+pub fn markdown_to_html_with_options(
+    input: &str,
+    #[wasm_bindgen(unchecked_param_type = "ParseOptions")] options: JsValue,
+) -> Result<String, JsError> {
+    let options = options_from_js(options)?;
+    let ast = parser::Parser::with_options(input, options).parse_document();
+    Ok(renderer::render(&ast))
+}
+
+#[wasm_bindgen(unchecked_return_type = "ParseResult")]
+pub fn parse_with_diagnostics(
+    input: &str,
+    #[wasm_bindgen(unchecked_param_type = "ParseOptions | undefined")] options: JsValue,
+) -> Result<JsValue, JsError> {
+    let options = options_from_js(options)?;
+    let result = parser::Parser::with_options(input, options).parse_with_diagnostics();
+    Ok(to_js(&result)?)
+}
+
+#[wasm_bindgen(unchecked_return_type = "Diagnostic[]")]
+pub fn lint_markdown(
+    input: &str,
+    #[wasm_bindgen(unchecked_param_type = "LintConfig | undefined")] config: JsValue,
+) -> Result<JsValue, JsError> {
+    let linter: lint::Linter = if config.is_undefined() || config.is_null() {
+        lint::Linter::new()
+    } else {
+        from_value(config).map_err(|err| JsError::new(&format!("invalid lint config: {}", err)))?
+    };
+    Ok(to_js(&linter.lint(input))?)
+}
+
+#[wasm_bindgen]
+pub fn apply_fixes(
+    input: &str,
+    #[wasm_bindgen(unchecked_param_type = "Diagnostic[]")] diagnostics: JsValue,
+) -> Result<String, JsError> {
+    let diagnostics: Vec<diagnostics::Diagnostic> = from_value(diagnostics)
+        .map_err(|err| JsError::new(&format!("invalid diagnostics: {}", err)))?;
+    Ok(diagnostics::apply_fixes(input, &diagnostics))
+}
+
+/// Builds a search index from Markdown pages keyed by their URL.
+#[wasm_bindgen(unchecked_return_type = "SearchIndex")]
+pub fn search_index(
+    #[wasm_bindgen(unchecked_param_type = "Record<string, string>")] pages: JsValue,
+) -> Result<JsValue, JsError> {
+    let pages: std::collections::BTreeMap<String, String> =
+        from_value(pages).map_err(|err| JsError::new(&format!("invalid pages: {}", err)))?;
+    let mut index = search::SearchIndex::new();
+    for (path, text) in &pages {
+        index.add(path, &parser::Parser::new(text).parse_document());
+    }
+    Ok(to_js(&index)?)
+}
+
+/// Builds the wiki link graph of Markdown pages keyed by page name.
+#[wasm_bindgen(unchecked_return_type = "LinkGraph")]
+pub fn link_graph(
+    #[wasm_bindgen(unchecked_param_type = "Record<string, string>")] pages: JsValue,
+) -> Result<JsValue, JsError> {
+    let pages: std::collections::BTreeMap<String, String> =
+        from_value(pages).map_err(|err| JsError::new(&format!("invalid pages: {}", err)))?;
+    let mut graph = wiki::LinkGraph::new();
+    for (name, text) in &pages {
+        graph.add(name, text);
+    }
+    Ok(to_js(&graph)?)
+}
+
+/// Searches an index from `search_index` or a parsed `search.json`.
+#[wasm_bindgen(unchecked_return_type = "SearchHit[]")]
+pub fn search(
+    #[wasm_bindgen(unchecked_param_type = "SearchIndex")] index: JsValue,
+    query: &str,
+) -> Result<JsValue, JsError> {
+    let index: search::SearchIndex =
+        from_value(index).map_err(|err| JsError::new(&format!("invalid search index: {}", err)))?;
+    Ok(to_js(&index.search(query))?)
+}
+
+#[wasm_bindgen]
+pub fn ast_to_html(
+    #[wasm_bindgen(unchecked_param_type = "Node[]")] ast: JsValue,
+) -> Result<String, JsError> {
+    let nodes = nodes_from_js(ast)?;
+    Ok(renderer::render(&nodes))
+}
+
+#[wasm_bindgen]
+pub fn ast_to_markdown(
+    #[wasm_bindgen(unchecked_param_type = "Node[]")] ast: JsValue,
+) -> Result<String, JsError> {
+    let nodes = nodes_from_js(ast)?;
+    Ok(markdown::render(&nodes))
+}
+
+#[wasm_bindgen(unchecked_return_type = "QueryMatch[]")]
+pub fn query_ast(
+    #[wasm_bindgen(unchecked_param_type = "Node[]")] ast: JsValue,
+    selector: &str,
+) -> Result<JsValue, JsError> {
+    let nodes = nodes_from_js(ast)?;
+    let matches = query::query(&nodes, selector)?;
+    Ok(to_js(&matches)?)
+}
+
+#[wasm_bindgen(unchecked_return_type = "Change[]")]
+pub fn diff_ast(
+    #[wasm_bindgen(unchecked_param_type = "Node[]")] old: JsValue,
+    #[wasm_bindgen(unchecked_param_type = "Node[]")] new: JsValue,
+) -> Result<JsValue, JsError> {
+    let old = nodes_from_js(old)?;
+    let new = nodes_from_js(new)?;
+    Ok(to_js(&diff::diff(&old, &new))?)
+}
+
+#[wasm_bindgen]
+pub fn markdown_redline(old: &str, new: &str) -> String {
+    let old = parser::Parser::new(old).parse_document();
+    let new = parser::Parser::new(new).parse_document();
+    diff::render_redline(&old, &new)
+}
+
+/// Serializes with maps as plain objects so values match the declared types.
+pub(crate) fn to_js<T: Serialize + ?Sized>(
+    value: &T,
+) -> Result<JsValue, serde_wasm_bindgen::Error> {
+    value.serialize(&Serializer::new().serialize_maps_as_objects(true))
+}
+
+fn nodes_from_js(value: JsValue) -> Result<Vec<ast::Node>, JsError> {
+    let nodes: Vec<ast::Node> =
+        from_value(value).map_err(|err| JsError::new(&format!("invalid AST: {}", err)))?;
+    ast::validate(&nodes)?;
+    Ok(nodes)
+}
+
+/// Reads parse options; missing fields, `undefined` and `null` use defaults.
+pub(crate) fn options_from_js(value: JsValue) -> Result<parser::ParseOptions, JsError> {
+    if value.is_undefined() || value.is_null() {
+        return Ok(parser::ParseOptions::default());
+    }
+    from_value(value).map_err(|err| JsError::new(&format!("invalid options: {}", err)))
 }
@@ -0,0 +1,461 @@
+//! Offline link and anchor checker for a set of documents.
+//!
+//! Every link, image and reference definition is classified by
+//! [`LinkKind`]. Relative paths are resolved against the linking document's
+//! folder, or the checker's root when they start with `/`, and must name a
+//! document in the set or an existing file. `#fragments` must match a
+//! heading id of the target document. External URLs are never fetched.
+//!
+//! The parser leaves reference-style links (`[text][label]`) as text, so
+//! they and their `[label]: url` definitions are found in the source.
+//!
+//! ```
+//! use mdkit::links::LinkChecker;
+//!
+//! let mut checker = LinkChecker::new(".");
+//! checker.add("a.md", "# Intro\n\nSee [usage](b.md#usage) and [c](c.md).");
+//! checker.add("b.md", "# Usage");
+//!
+//! let links = checker.check();
+//! assert_eq!(links.len(), 2);
+//! assert!(!links[0].is_broken());
+//! assert_eq!(links[1].error.as_deref(), Some("`c.md` does not exist"));
+//! ```
+
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::ops::Range;
+use std::path::{Component, Path, PathBuf};
+
+use serde::Serialize;
+
+use crate::ast::Node;
+use crate::ast::visit::{Visitor, walk_node};
+use crate::lint::{Inline, Inlines, find_image, find_link, open_bracket};
+use crate::parser::{Block, Parser};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum LinkKind {
+    /// A URL with a scheme such as `https:` or `mailto:`, or starting with `//`.
+    External,
+    /// A fragment of the same document, e.g. `#usage`.
+    Anchor,
+    /// A path to a file, optionally with a fragment, e.g. `guide.md#usage`.
+    File,
+    /// A `[text][label]` reference to a definition.
+    Reference,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum LinkElement {
+    Link,
+    Image,
+    /// A `[label]: url` reference definition.
+    Definition,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CheckedLink {
+    /// Document the link appears in, as passed to [`LinkChecker::add`].
+    pub path: PathBuf,
+    pub element: LinkElement,
+    pub kind: LinkKind,
+    /// Destination, or the label of a reference.
+    pub url: String,
+    /// Character range of the link in its document.
+    pub span: Range<usize>,
+    /// Why the link is broken; `None` when it resolves or is external.
+    pub error: Option<String>,
+}
+
+impl CheckedLink {
+    pub fn is_broken(&self) -> bool {
+        self.error.is_some()
+    }
+}
+
+pub struct LinkChecker {
+    root: PathBuf,
+    documents: BTreeMap<PathBuf, Document>,
+}
+
+struct Document {
+    path: PathBuf,
+    text: String,
+    blocks: Vec<Block>,
+    anchors: HashSet<String>,
+}
+
+impl LinkChecker {
+    /// Files outside the set are looked up below `root`.
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            documents: BTreeMap::new(),
+        }
+    }
+
+    /// Adds a document at `path`, relative to the root.
+    pub fn add(&mut self, path: impl AsRef<Path>, text: &str) -> &mut Self {
+        let path = path.as_ref();
+        let blocks = Parser::new(text).parse_blocks();
+        self.documents.insert(
+            normalize(path),
+            Document {
+                path: path.to_path_buf(),
+                text: text.to_string(),
+                anchors: anchors(&blocks),
+                blocks,
+            },
+        );
+        self
+    }
+
+    /// Every link of every document, by path and then source position.
+    pub fn check(&self) -> Vec<CheckedLink> {
+        let mut checked = Vec::new();
+        for (path, document) in &self.documents {
+            let mut links = Vec::new();
+            let source: Vec<char> = document.text.chars().collect();
+            let mut definitions = HashSet::new();
+
+            for block in &document.blocks {
+                let text: String = source[block.span.clone()].iter().collect();
+                let to_span = |bytes: Range<usize>| {
+                    let start = block.span.start + text[..bytes.start].chars().count();
+                    start..start + text[bytes].chars().count()
+                };
+
+                for (element, url, bytes) in destinations(&block.node, &text) {
+                    let span = bytes.map_or_else(|| trim_end(&source, &block.span), to_span);
+                    links.push(self.link(path, document, element, url, span));
+                }
+
+                if matches!(block.node, Node::CodeBlock { .. } | Node::BlockMath { .. }) {
+                    continue;
+                }
+                let references = References::scan(&text);
+                for (label, url, bytes) in references.definitions {
+                    definitions.insert(label);
+                    let span = to_span(bytes);
+                    links.push(self.link(path, document, LinkElement::Definition, url, span));
+                }
+                for (element, label, bytes) in references.uses {
+                    links.push(CheckedLink {
+                        path: document.path.clone(),
+                        element,
+                        kind: LinkKind::Reference,
+                        url: label,
+                        span: to_span(bytes),
+                        error: None,
+                    });
+                }
+            }
+
+            // Definitions may follow the references that use them.
+            for link in &mut links {
+                if link.kind == LinkKind::Reference
+                    && !definitions.contains(&normalize_label(&link.url))
+                {
+                    link.error = Some(format!("no definition for `[{}]`", link.url));
+                }
+            }
+            links.sort_by_key(|link| (link.span.start, link.span.end));
+            checked.extend(links);
+        }
+        checked
+    }
+
+    fn link(
+        &self,
+        path: &Path,
+        document: &Document,
+        element: LinkElement,
+        url: String,
+        span: Range<usize>,
+    ) -> CheckedLink {
+        let kind = classify(&url);
+        let error = match kind {
+            LinkKind::External | LinkKind::Reference => None,
+            LinkKind::Anchor | LinkKind::File => self.resolve(path, document, &url).err(),
+        };
+        CheckedLink {
+            path: document.path.clone(),
+            element,
+            kind,
+            url,
+            span,
+            error,
+        }
+    }
+
+    fn resolve(&self, from: &Path, document: &Document, url: &str) -> Result<(), String> {
+        if url.trim().is_empty() {
+            return Err("link has no destination".to_string());
+        }
+        let (path, fragment) = url.split_once('#').unwrap_or((url, ""));
+        let path = decode(path.split('?').next().unwrap_or_default());
+        let fragment = decode(fragment);
+
+        if path.is_empty() {
+            return find_anchor(&document.anchors, &fragment, from);
+        }
+        let target = match path.strip_prefix('/') {
+            Some(path) => normalize(Path::new(path)),
+            None => normalize(&from.parent().unwrap_or(Path::new("")).join(path)),
+        };
+        if let Some(document) = self.documents.get(&target) {
+            return find_anchor(&document.anchors, &fragment, &target);
+        }
+
+        let file = self.root.join(&target);
+        if !file.exists() {
+            return Err(format!("`{}` does not exist", target.display()));
+        }
+        if fragment.is_empty() || file.is_dir() || target.extension().is_none_or(|ext| ext != "md")
+        {
+            return Ok(());
+        }
+        let text =
+            fs::read_to_string(&file).map_err(|err| format!("`{}`: {}", target.display(), err))?;
+        find_anchor(
+            &anchors(&Parser::new(&text).parse_blocks()),
+            &fragment,
+            &target,
+        )
+    }
+}
+
+fn classify(url: &str) -> LinkKind {
+    let has_scheme = url.split_once(':').is_some_and(|(scheme, _)| {
+        // A single letter is a Windows drive, not a scheme.
+        scheme.len() > 1
+            && scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+            && scheme
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+    });
+    if has_scheme || url.starts_with("//") {
+        LinkKind::External
+    } else if url.starts_with('#') {
+        LinkKind::Anchor
+    } else {
+        LinkKind::File
+    }
+}
+
+fn find_anchor(anchors: &HashSet<String>, fragment: &str, target: &Path) -> Result<(), String> {
+    if fragment.is_empty() || anchors.contains(fragment) {
+        Ok(())
+    } else {
+        Err(format!(
+            "no heading `#{}` in `{}`",
+            fragment,
+            target.display()
+        ))
+    }
+}
+
+/// Heading ids anywhere in the document, including quotes and lists.
+fn anchors(blocks: &[Block]) -> HashSet<String> {
+    struct Anchors(HashSet<String>);
+
+    impl Visitor for Anchors {
+        fn visit_node(&mut self, node: &Node) {
+            if let Node::Heading { id, .. } = node {
+                self.0.insert(id.clone());
+            }
+            walk_node(self, node);
+        }
+    }
+
+    let mut anchors = Anchors(HashSet::new());
+    for block in blocks {
+        anchors.visit_node(&block.node);
+    }
+    anchors.0
+}
+
+/// Links and images of `node` with their byte ranges in its source `text`,
+/// located the same way as the linter's inline rules.
+fn destinations(node: &Node, text: &str) -> Vec<(LinkElement, String, Option<Range<usize>>)> {
+    let mut inlines = Inlines::default();
+    inlines.visit_node(node);
+
+    let mut found = Vec::new();
+    let mut cursor = 0;
+    for inline in inlines.0 {
+        let (element, url, bytes) = match inline {
+            Inline::Image { alt, url } => {
+                let bytes = find_image(text, cursor, &alt, &url);
+                (LinkElement::Image, url, bytes)
+            }
+            Inline::Link { url, .. } => {
+                let bytes = find_link(text, cursor, &url);
+                (LinkElement::Link, url, bytes)
+            }
+            Inline::Text(_) => continue,
+        };
+        if let Some(bytes) = &bytes {
+            cursor = bytes.end;
+        }
+        found.push((element, url, bytes));
+    }
+    found
+}
+
+/// Reference definitions and uses in a block's source, by byte range.
+#[derive(Default)]
+struct References {
+    /// Normalized label, destination and the destination's range.
+    definitions: Vec<(String, String, Range<usize>)>,
+    /// Label as written and the range of the whole reference.
+    uses: Vec<(LinkElement, String, Range<usize>)>,
+}
+
+impl References {
+    fn scan(text: &str) -> Self {
+        let text = mask_code_spans(text);
+        let mut references = Self::default();
+
+        let mut offset = 0;
+        for line in text.split_inclusive('\n') {
+            let start = offset;
+            offset += line.len();
+
+            let trimmed = line.trim_start();
+            let indent = line.len() - trimmed.len();
+            if indent > 3 || !trimmed.starts_with('[') {
+                continue;
+            }
+            let Some(close) = trimmed.find("]:") else {
+                continue;
+            };
+            let label = &trimmed[1..close];
+            let rest = &trimmed[close + 2..];
+            let url = rest.split_whitespace().next().unwrap_or_default();
+            if label.trim().is_empty() || label.contains('[') || url.is_empty() {
+                continue;
+            }
+            let url_start = start + indent + close + 2 + rest.find(url).unwrap_or_default();
+            references.definitions.push((
+                normalize_label(label),
+                url.trim_start_matches('<')
+                    .trim_end_matches('>')
+                    .to_string(),
+                url_start..url_start + url.len(),
+            ));
+        }
+
+        let mut from = 0;
+        while let Some(i) = text[from..].find("][") {
+            let i = from + i;
+            from = i + 2;
+            let Some(close) = text[i + 2..].find(']') else {
+                break;
+            };
+            let label = &text[i + 2..i + 2 + close];
+            if label.contains(['[', '\n']) {
+                continue;
+            }
+            let Some(open) = open_bracket(&text[..i]) else {
+                continue;
+            };
+            // `[label][]` takes its label from the text.
+            let label = if label.trim().is_empty() {
+                &text[open + 1..i]
+            } else {
+                label
+            };
+            let (element, start) = match text[..open].strip_suffix('!') {
+                Some(before) => (LinkElement::Image, before.len()),
+                None => (LinkElement::Link, open),
+            };
+            from = i + 2 + close + 1;
+            references
+                .uses
+                .push((element, label.to_string(), start..from));
+        }
+        references
+    }
+}
+
+/// `text` with the contents of inline code spans blanked out, keeping
+/// every character's position.
+fn mask_code_spans(text: &str) -> String {
+    let mut in_code = false;
+    text.chars()
+        .map(|c| {
+            if c == '`' {
+                in_code = !in_code;
+                c
+            } else if in_code && !c.is_whitespace() {
+                ' '
+            } else {
+                c
+            }
+        })
+        .collect()
+}
+
+/// Labels match case-insensitively, with runs of whitespace collapsed.
+fn normalize_label(label: &str) -> String {
+    label
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// Resolves `.` and `..` without touching the file system; `..` above the
+/// start is kept.
+fn normalize(path: &Path) -> PathBuf {
+    let mut components: Vec<Component> = Vec::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir if matches!(components.last(), Some(Component::Normal(_))) => {
+                components.pop();
+            }
+            _ => components.push(component),
+        }
+    }
+    components.iter().collect()
+}
+
+/// Decodes `%XX` escapes, e.g. `%20` in file names.
+fn decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .filter(|hex| bytes[i] == b'%' && hex.iter().all(u8::is_ascii_hexdigit));
+        match hex {
+            Some(hex) => {
+                let hex = std::str::from_utf8(hex).unwrap_or_default();
+                decoded.push(u8::from_str_radix(hex, 16).unwrap_or_default());
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// `span` without trailing whitespace.
+fn trim_end(source: &[char], span: &Range<usize>) -> Range<usize> {
+    let trailing = source[span.clone()]
+        .iter()
+        .rev()
+        .take_while(|c| c.is_whitespace())
+        .count();
+    span.start..span.end - trailing
+}
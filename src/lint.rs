@@ -0,0 +1,511 @@
+//! Markdownlint-style checks over a document's AST and source.
+//!
+//! Every [`Rule`] can be switched off or given its own severity. Findings
+//! are [`Diagnostic`]s with character spans into the input; many carry a
+//! [`Fix`] that [`apply_fixes`](crate::diagnostics::apply_fixes) applies.
+//!
+//! ```
+//! use mdkit::diagnostics::apply_fixes;
+//! use mdkit::lint::{Level, Linter, Rule};
+//!
+//! let input = "# Title\n\n### Skipped  \n";
+//! let mut linter = Linter::new();
+//! linter.set(Rule::TrailingSpaces, Level::Off);
+//!
+//! let diagnostics = linter.lint(input);
+//! assert_eq!(diagnostics.len(), 1);
+//! assert_eq!(diagnostics[0].code, "heading-increment");
+//! assert_eq!(apply_fixes(input, &diagnostics), "# Title\n\n## Skipped  \n");
+//! ```
+
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+
+use serde::{Deserialize, Serialize};
+
+use crate::ast::visit::{Visitor, walk_node};
+use crate::ast::{Node, plain_text};
+use crate::diagnostics::{Diagnostic, Fix, Severity};
+use crate::parser::{Block, Parser};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Rule {
+    /// Top-level heading levels go up one at a time.
+    HeadingIncrement,
+    /// No two headings share an id.
+    DuplicateHeading,
+    /// Images have alt text.
+    ImageAlt,
+    /// URLs in text are written as links.
+    BareUrl,
+    /// Unordered list items all use the document's first marker.
+    ListMarker,
+    /// Lines outside code and math blocks have no trailing whitespace.
+    TrailingSpaces,
+    /// Links have text and a destination.
+    EmptyLink,
+    /// Every `:::` block is closed.
+    UnclosedCustomBlock,
+    /// Table rows have as many cells as the header.
+    TableColumnMismatch,
+}
+
+impl Rule {
+    pub const ALL: [Rule; 9] = [
+        Rule::HeadingIncrement,
+        Rule::DuplicateHeading,
+        Rule::ImageAlt,
+        Rule::BareUrl,
+        Rule::ListMarker,
+        Rule::TrailingSpaces,
+        Rule::EmptyLink,
+        Rule::UnclosedCustomBlock,
+        Rule::TableColumnMismatch,
+    ];
+
+    /// Code used in diagnostics and configuration, e.g. `heading-increment`.
+    pub fn code(self) -> &'static str {
+        match self {
+            Rule::HeadingIncrement => "heading-increment",
+            Rule::DuplicateHeading => "duplicate-heading",
+            Rule::ImageAlt => "image-alt",
+            Rule::BareUrl => "bare-url",
+            Rule::ListMarker => "list-marker",
+            Rule::TrailingSpaces => "trailing-spaces",
+            Rule::EmptyLink => "empty-link",
+            Rule::UnclosedCustomBlock => "unclosed-custom-block",
+            Rule::TableColumnMismatch => "table-column-mismatch",
+        }
+    }
+
+    pub fn default_level(self) -> Level {
+        match self {
+            Rule::UnclosedCustomBlock => Level::Error,
+            Rule::TrailingSpaces => Level::Info,
+            _ => Level::Warning,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Level {
+    Off,
+    Error,
+    Warning,
+    Info,
+}
+
+impl Level {
+    pub fn severity(self) -> Option<Severity> {
+        match self {
+            Level::Off => None,
+            Level::Error => Some(Severity::Error),
+            Level::Warning => Some(Severity::Warning),
+            Level::Info => Some(Severity::Info),
+        }
+    }
+}
+
+/// Rule configuration; rules not set explicitly use their default level.
+/// Deserializes from a map such as `{ "bare-url": "off" }`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Linter {
+    levels: HashMap<Rule, Level>,
+}
+
+impl Linter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set(&mut self, rule: Rule, level: Level) -> &mut Self {
+        self.levels.insert(rule, level);
+        self
+    }
+
+    pub fn level(&self, rule: Rule) -> Level {
+        self.levels
+            .get(&rule)
+            .copied()
+            .unwrap_or(rule.default_level())
+    }
+
+    /// Diagnostics of every enabled rule, in source order.
+    pub fn lint(&self, input: &str) -> Vec<Diagnostic> {
+        let mut parser = Parser::new(input);
+        let blocks = parser.parse_blocks();
+        let mut context = Context {
+            linter: self,
+            source: input.chars().collect(),
+            diagnostics: Vec::new(),
+        };
+
+        parser_rules(&mut context, parser.take_diagnostics());
+        heading_increment(&mut context, &blocks);
+        duplicate_heading(&mut context, &blocks);
+        inline_rules(&mut context, &blocks);
+        list_marker(&mut context, &blocks);
+        trailing_spaces(&mut context, &blocks);
+
+        context
+            .diagnostics
+            .sort_by_key(|diagnostic| (diagnostic.span.start, diagnostic.span.end));
+        context.diagnostics
+    }
+}
+
+/// Lints `input` with every rule at its default level.
+pub fn lint(input: &str) -> Vec<Diagnostic> {
+    Linter::new().lint(input)
+}
+
+struct Context<'a> {
+    linter: &'a Linter,
+    source: Vec<char>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Context<'_> {
+    fn report(
+        &mut self,
+        rule: Rule,
+        message: impl Into<String>,
+        span: Range<usize>,
+        fix: Option<Fix>,
+    ) {
+        if let Some(severity) = self.linter.level(rule).severity() {
+            let mut diagnostic = Diagnostic::new(severity, rule.code(), message, span);
+            diagnostic.fix = fix;
+            self.diagnostics.push(diagnostic);
+        }
+    }
+
+    /// Source text of a block.
+    fn text(&self, span: &Range<usize>) -> String {
+        self.source[span.clone()].iter().collect()
+    }
+
+    /// `span` without trailing whitespace.
+    fn trim_end(&self, span: &Range<usize>) -> Range<usize> {
+        let trailing = self.source[span.clone()]
+            .iter()
+            .rev()
+            .take_while(|c| c.is_whitespace())
+            .count();
+        span.start..span.end - trailing
+    }
+}
+
+/// Rules the parser already detects while recovering.
+fn parser_rules(context: &mut Context, diagnostics: Vec<Diagnostic>) {
+    for diagnostic in diagnostics {
+        match diagnostic.code.as_str() {
+            "unclosed-custom-block" => {
+                let end = diagnostic.span.end;
+                let replacement = if end > 0 && context.source[end - 1] != '\n' {
+                    "\n:::"
+                } else {
+                    ":::\n"
+                };
+                context.report(
+                    Rule::UnclosedCustomBlock,
+                    diagnostic.message,
+                    diagnostic.span,
+                    Some(Fix {
+                        span: end..end,
+                        replacement: replacement.to_string(),
+                    }),
+                );
+            }
+            "table-column-mismatch" => context.report(
+                Rule::TableColumnMismatch,
+                diagnostic.message,
+                diagnostic.span,
+                None,
+            ),
+            _ => {}
+        }
+    }
+}
+
+fn heading_increment(context: &mut Context, blocks: &[Block]) {
+    let mut previous = None;
+    for block in blocks {
+        let Node::Heading { level, .. } = block.node else {
+            continue;
+        };
+
+        if let Some(previous) = previous
+            && level > previous + 1
+        {
+            let hashes = context.source[block.span.clone()]
+                .iter()
+                .take_while(|&&c| c == '#')
+                .count();
+            let span = block.span.start..block.span.start + hashes;
+            context.report(
+                Rule::HeadingIncrement,
+                format!("heading level jumps from {} to {}", previous, level),
+                span.clone(),
+                Some(Fix {
+                    span,
+                    replacement: "#".repeat(previous + 1),
+                }),
+            );
+        }
+        previous = Some(level);
+    }
+}
+
+fn duplicate_heading(context: &mut Context, blocks: &[Block]) {
+    #[derive(Default)]
+    struct HeadingIds(Vec<String>);
+
+    impl Visitor for HeadingIds {
+        fn visit_node(&mut self, node: &Node) {
+            if let Node::Heading { id, .. } = node {
+                self.0.push(id.clone());
+            }
+            walk_node(self, node);
+        }
+    }
+
+    let mut seen = HashSet::new();
+    for block in blocks {
+        let mut ids = HeadingIds::default();
+        ids.visit_node(&block.node);
+
+        for id in ids.0 {
+            if !id.is_empty() && !seen.insert(id.clone()) {
+                let span = context.trim_end(&block.span);
+                context.report(
+                    Rule::DuplicateHeading,
+                    format!("heading id `{}` is already used", id),
+                    span,
+                    None,
+                );
+            }
+        }
+    }
+}
+
+pub(crate) enum Inline {
+    Image { alt: String, url: String },
+    Link { empty: bool, url: String },
+    Text(String),
+}
+
+/// Image, link and text nodes in document order; link text is skipped.
+#[derive(Default)]
+pub(crate) struct Inlines(pub(crate) Vec<Inline>);
+
+impl Visitor for Inlines {
+    fn visit_node(&mut self, node: &Node) {
+        match node {
+            Node::Image { alt, url, .. } => self.0.push(Inline::Image {
+                alt: alt.clone(),
+                url: url.clone(),
+            }),
+            Node::Link { text, url, .. } => self.0.push(Inline::Link {
+                empty: plain_text(text).trim().is_empty()
+                    && !text.iter().any(|node| matches!(node, Node::Image { .. })),
+                url: url.clone(),
+            }),
+            Node::Text { content } => self.0.push(Inline::Text(content.clone())),
+            _ => walk_node(self, node),
+        }
+    }
+}
+
+/// Image alt text, links and bare URLs. The AST has no inline positions,
+/// so each node is located in its block's source after the previous one;
+/// nodes that cannot be found are reported against the whole block.
+fn inline_rules(context: &mut Context, blocks: &[Block]) {
+    for block in blocks {
+        let mut inlines = Inlines::default();
+        inlines.visit_node(&block.node);
+        if inlines.0.is_empty() {
+            continue;
+        }
+
+        let text = context.text(&block.span);
+        let fallback = context.trim_end(&block.span);
+        let to_span = |bytes: Range<usize>| {
+            let start = block.span.start + text[..bytes.start].chars().count();
+            start..start + text[bytes].chars().count()
+        };
+        let mut cursor = 0;
+
+        for inline in inlines.0 {
+            match inline {
+                Inline::Image { alt, url } => {
+                    let found = find_image(&text, cursor, &alt, &url);
+                    if let Some(bytes) = &found {
+                        cursor = bytes.end;
+                    }
+                    if alt.trim().is_empty() {
+                        let span = found.map_or(fallback.clone(), to_span);
+                        context.report(Rule::ImageAlt, "image has no alt text", span, None);
+                    }
+                }
+                Inline::Link { empty, url } => {
+                    let found = find_link(&text, cursor, &url);
+                    if let Some(bytes) = &found {
+                        cursor = bytes.end;
+                    }
+
+                    let message = if empty {
+                        "link has no text"
+                    } else if url.trim().is_empty() {
+                        "link has no destination"
+                    } else {
+                        continue;
+                    };
+                    let span = found.map_or(fallback.clone(), to_span);
+                    context.report(Rule::EmptyLink, message, span, None);
+                }
+                Inline::Text(content) => {
+                    for url in bare_urls(&content) {
+                        let Some(bytes) = find_from(&text, cursor, url) else {
+                            continue;
+                        };
+                        cursor = bytes.end;
+                        let span = to_span(bytes);
+                        context.report(
+                            Rule::BareUrl,
+                            format!("bare URL `{}`", url),
+                            span.clone(),
+                            Some(Fix {
+                                span,
+                                replacement: format!("[{}]({})", url, url),
+                            }),
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Byte range of `![alt](url)` in `text` at or after `from`.
+pub(crate) fn find_image(text: &str, from: usize, alt: &str, url: &str) -> Option<Range<usize>> {
+    find_from(text, from, &format!("![{}]({})", alt, url))
+}
+
+/// Byte range of `[...](url)` in `text` at or after `from`.
+pub(crate) fn find_link(text: &str, from: usize, url: &str) -> Option<Range<usize>> {
+    let end = find_from(text, from, &format!("]({})", url))?;
+    open_bracket(&text[from..end.start]).map(|start| from + start..end.end)
+}
+
+/// Byte offset of the `[` matching a `]` just past the end of `text`,
+/// skipping nested bracket pairs such as images in link text.
+pub(crate) fn open_bracket(text: &str) -> Option<usize> {
+    let mut depth = 0;
+    for (i, c) in text.char_indices().rev() {
+        match c {
+            ']' => depth += 1,
+            '[' if depth == 0 => return Some(i),
+            '[' => depth -= 1,
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Byte range of the first `pattern` in `text` at or after `from`.
+fn find_from(text: &str, from: usize, pattern: &str) -> Option<Range<usize>> {
+    text[from..]
+        .find(pattern)
+        .map(|i| from + i..from + i + pattern.len())
+}
+
+fn bare_urls(text: &str) -> Vec<&str> {
+    text.split_whitespace()
+        .filter_map(|word| {
+            let start = word.find("https://").or_else(|| word.find("http://"))?;
+            let url =
+                word[start..].trim_end_matches(['.', ',', ';', ':', '!', '?', ')', '"', '\'']);
+            (!url.ends_with("//")).then_some(url)
+        })
+        .collect()
+}
+
+fn list_marker(context: &mut Context, blocks: &[Block]) {
+    let mut expected = None;
+    for block in blocks {
+        if !matches!(block.node, Node::List { .. }) {
+            continue;
+        }
+
+        let text = context.text(&block.span);
+        let mut line_start = block.span.start;
+        for line in text.split('\n') {
+            let indent = line.chars().take_while(|c| c.is_whitespace()).count();
+            let rest = line.trim_start();
+            let marker = rest
+                .chars()
+                .next()
+                .filter(|c| matches!(c, '-' | '*' | '+') && rest[1..].starts_with(' '));
+
+            if let Some(marker) = marker {
+                let at = line_start + indent;
+                match expected {
+                    None => expected = Some(marker),
+                    Some(expected) if expected != marker => context.report(
+                        Rule::ListMarker,
+                        format!("expected list marker `{}`, found `{}`", expected, marker),
+                        at..at + 1,
+                        Some(Fix {
+                            span: at..at + 1,
+                            replacement: expected.to_string(),
+                        }),
+                    ),
+                    Some(_) => {}
+                }
+            }
+            line_start += line.chars().count() + 1;
+        }
+    }
+}
+
+fn trailing_spaces(context: &mut Context, blocks: &[Block]) {
+    let verbatim: Vec<&Range<usize>> = blocks
+        .iter()
+        .filter(|block| matches!(block.node, Node::CodeBlock { .. } | Node::BlockMath { .. }))
+        .map(|block| &block.span)
+        .collect();
+
+    let mut found = Vec::new();
+    let mut line_start = 0;
+    for line in context.source.split(|&c| c == '\n') {
+        let content = line.strip_suffix(&['\r']).unwrap_or(line);
+        let trailing = content
+            .iter()
+            .rev()
+            .take_while(|&&c| c == ' ' || c == '\t')
+            .count();
+        let in_code = verbatim.iter().any(|span| span.contains(&line_start));
+
+        if trailing > 0 && !in_code {
+            let end = line_start + content.len();
+            found.push(end - trailing..end);
+        }
+        line_start += line.len() + 1;
+    }
+
+    for span in found {
+        context.report(
+            Rule::TrailingSpaces,
+            "trailing whitespace",
+            span.clone(),
+            Some(Fix {
+                span,
+                replacement: String::new(),
+            }),
+        );
+    }
+}
@@ -0,0 +1,403 @@
+use std::collections::HashMap;
+
+use crate::ast::Fold;
+use crate::ast::ListType;
+use crate::ast::Node;
+use crate::ast::TableAlignment;
+use crate::ast::TableCell;
+use crate::ast::is_directive_name;
+use crate::ast::reference_text;
+use crate::emoji;
+
+fn render_alignment(align: TableAlignment) -> &'static str {
+    match align {
+        TableAlignment::Left => ":---",
+        TableAlignment::Center => ":---:",
+        TableAlignment::Right => "---:",
+        TableAlignment::None => "---",
+    }
+}
+
+// Pipes in cells are escaped, code included, as the row is split first.
+fn render_row(cells: &[TableCell]) -> String {
+    let cells: Vec<String> = cells
+        .iter()
+        .map(|cell| render_inline(&cell.children).replace('|', "\\|"))
+        .collect();
+    format!("| {} |\n", cells.join(" | "))
+}
+
+fn indent_lines(text: &str, prefix: &str) -> String {
+    let mut out = String::new();
+    for line in text.lines() {
+        if line.is_empty() {
+            out.push_str(prefix.trim_end());
+        } else {
+            out.push_str(prefix);
+            out.push_str(line);
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// Characters escaped wherever they appear in text.
+const ESCAPED: &str = "\\`*[]~^$";
+
+/// Escapes `text` so it parses back as the same text: markers always, and
+/// `__`, `==`, `++`, emoji shortcodes and `:name{` directives only where
+/// they would be read as such. Directly inside an underline, any `_` would
+/// end it.
+fn escape(text: &str, underlined: bool) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut out = String::with_capacity(text.len());
+    for (i, &c) in chars.iter().enumerate() {
+        let doubled =
+            |c: char| i == 0 || i + 1 == chars.len() || chars[i - 1] == c || chars[i + 1] == c;
+        let escaped = match c {
+            '_' => underlined || doubled(c),
+            '=' | '+' => doubled(c),
+            ':' => {
+                let name: String = chars[i + 1..]
+                    .iter()
+                    .take_while(|&&c| c.is_ascii_alphanumeric() || "_+-".contains(c))
+                    .collect();
+                match chars.get(i + 1 + name.len()) {
+                    Some(':') => emoji::lookup(&name).is_some(),
+                    Some('{') => {
+                        (i == 0 || !chars[i - 1].is_alphanumeric()) && is_directive_name(&name)
+                    }
+                    _ => false,
+                }
+            }
+            _ => ESCAPED.contains(c),
+        };
+        if escaped {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// Escapes what would start a block at the beginning of a line of text:
+/// `#`, `>`, `-`, `+`, `|`, `:` and the `.` of a number like `1.`.
+fn escape_line_start(line: String) -> String {
+    let indent = line.len() - line.trim_start().len();
+    let rest = &line[indent..];
+    let digits = rest.chars().take_while(char::is_ascii_digit).count();
+    let at = if digits > 0 && rest[digits..].starts_with('.') {
+        indent + digits
+    } else if rest.starts_with(['#', '>', '-', '+', '|', ':']) {
+        indent
+    } else {
+        return line;
+    };
+    format!("{}\\{}", &line[..at], &line[at..])
+}
+
+/// Serializes an AST back into markdown. Text is escaped where it would
+/// read as syntax, so `Parser` reads a tree it produced, with the default
+/// options, back as the same tree. The exception is a line break inside
+/// emphasis, a link, a label, a table cell or a definition term: it is
+/// written as a `\` on the same line, which escapes punctuation after it.
+pub fn render(nodes: &[Node]) -> String {
+    let blocks: Vec<String> = nodes
+        .iter()
+        .map(|node| render_block(node).trim_end_matches('\n').to_string())
+        .filter(|block| !block.trim().is_empty())
+        .collect();
+
+    let mut markdown = blocks.join("\n\n");
+    if !markdown.is_empty() {
+        markdown.push('\n');
+    }
+    markdown
+}
+
+fn render_block(node: &Node) -> String {
+    match node {
+        // # Heading
+        Node::Heading {
+            level, children, ..
+        } => format!("{} {}", "#".repeat(*level), render_text_line(children)),
+
+        Node::FrontMatter { format, raw, .. } => {
+            let delimiter = format.delimiter();
+            if raw.is_empty() {
+                format!("{}\n{}", delimiter, delimiter)
+            } else {
+                format!("{}\n{}\n{}", delimiter, raw, delimiter)
+            }
+        }
+
+        Node::HorizontalRule => "---".to_string(),
+
+        Node::Paragraph { children } => render_text_line(children),
+
+        // $$ math $$
+        Node::BlockMath { content } => format!("$$\n{}\n$$", content),
+
+        // ```lang filename
+        Node::CodeBlock {
+            lang,
+            filename,
+            code,
+        } => {
+            let mut header = lang.clone();
+            if let Some(filename) = filename {
+                header.push(' ');
+                header.push_str(filename);
+            }
+            format!("```{}\n{}\n```", header, code)
+        }
+
+        // > quote
+        Node::BlockQuote { children } => indent_lines(&render(children), "> "),
+
+        // > [!NOTE]- Title
+        Node::Callout {
+            kind,
+            title,
+            foldable,
+            children,
+        } => {
+            let marker = match kind.as_str() {
+                "note" | "tip" | "important" | "warning" | "caution" => kind.to_uppercase(),
+                _ => kind.clone(),
+            };
+            let fold = match foldable {
+                Some(Fold::Open) => "+",
+                Some(Fold::Closed) => "-",
+                None => "",
+            };
+            let mut header = format!("[!{}]{}", marker, fold);
+            if !title.is_empty() {
+                header.push(' ');
+                header.push_str(&render_inline(title));
+            }
+            indent_lines(&format!("{}\n{}", header, render(children)), "> ")
+        }
+
+        // - item / 1. item
+        Node::List { kind, items } => {
+            let mut out = String::new();
+            for (i, item) in items.iter().enumerate() {
+                let marker = match kind {
+                    ListType::Ordered => format!("{}. ", i + 1),
+                    ListType::Unordered => "- ".to_string(),
+                };
+                out.push_str(&marker);
+
+                match item.checked {
+                    Some(true) => out.push_str("[x] "),
+                    Some(false) => out.push_str("[ ] "),
+                    None => {}
+                }
+
+                // Lines after a break are indented under the marker
+                let indent = " ".repeat(marker.len());
+                let content = render_text_line(&item.content);
+                let content = content.strip_suffix('\n').unwrap_or(&content);
+                out.push_str(&content.replace("\\\n", &format!("\n{}", indent)));
+                out.push('\n');
+
+                if !item.children.is_empty() {
+                    let nested = render(&item.children);
+                    out.push_str(&indent_lines(nested.trim_end_matches('\n'), &indent));
+                }
+            }
+            out
+        }
+
+        // | a | b |
+        Node::Table { header, rows } => {
+            let mut out = render_row(header);
+            let separators: Vec<&str> = header
+                .iter()
+                .map(|cell| render_alignment(cell.alignment))
+                .collect();
+            out.push_str(&format!("|{}|\n", separators.join("|")));
+            for row in rows {
+                out.push_str(&render_row(row));
+            }
+            out
+        }
+
+        // :::name key=value
+        Node::CustomBlock {
+            name,
+            label,
+            attributes,
+            children,
+        } => {
+            let mut keys: Vec<&String> = attributes.keys().collect();
+            keys.sort();
+
+            let mut header = name.clone();
+            if !label.is_empty() {
+                header.push_str(&format!("[{}]", render_inline(label)));
+            }
+            for key in keys {
+                header.push_str(&format!(" {}={}", key, quote(&attributes[key])));
+            }
+
+            let inner = render(children);
+            format!(":::{}\n{}:::", header, inner)
+        }
+
+        // Term
+        // : Definition
+        Node::DefinitionList { children } => {
+            let mut out = String::new();
+            for (i, child) in children.iter().enumerate() {
+                // A blank line between one term's definitions and the next term
+                if i > 0 && matches!(child, Node::DefinitionTerm { .. }) {
+                    out.push('\n');
+                }
+                out.push_str(&render_block(child));
+                out.push('\n');
+            }
+            out
+        }
+        Node::DefinitionTerm { children } => escape_line_start(render_inline(children)),
+        // Continuation lines are indented by four spaces
+        Node::DefinitionDescription { children } => {
+            let body = indent_lines(&render(children), "    ");
+            format!(": {}", body.trim_start().trim_end_matches('\n'))
+        }
+
+        // ::name[label]{attributes}
+        Node::LeafDirective {
+            name,
+            label,
+            attributes,
+        } => format!("::{}", directive(name, label, attributes)),
+
+        // Inline nodes at block level
+        _ => render_text_line(std::slice::from_ref(node)),
+    }
+}
+
+/// Inline nodes that make up lines of their own: a line break between
+/// them ends the line, and text at the start of a line is escaped where it
+/// would start a block.
+fn render_text_line(nodes: &[Node]) -> String {
+    let mut md = String::new();
+    let mut line_start = true;
+    for node in nodes {
+        match node {
+            Node::LineBreak => md.push_str("\\\n"),
+            Node::Text { content } if line_start => {
+                md.push_str(&escape_line_start(escape(content, false)))
+            }
+            _ => md.push_str(&render_inline(std::slice::from_ref(node))),
+        }
+        line_start = matches!(node, Node::LineBreak);
+    }
+    md
+}
+
+fn render_inline(nodes: &[Node]) -> String {
+    let mut md = String::new();
+    for node in nodes {
+        match node {
+            Node::Text { content } => md.push_str(&escape(content, false)),
+            Node::LineBreak => md.push('\\'),
+            Node::Bold { children } => md.push_str(&format!("**{}**", render_inline(children))),
+            Node::Italic { children } => md.push_str(&format!("*{}*", render_inline(children))),
+            Node::Strikethrough { children } => {
+                md.push_str(&format!("~~{}~~", render_inline(children)))
+            }
+            Node::Underline { children } => {
+                let inner: String = children
+                    .iter()
+                    .map(|child| match child {
+                        Node::Text { content } => escape(content, true),
+                        _ => render_inline(std::slice::from_ref(child)),
+                    })
+                    .collect();
+                md.push_str(&format!("__{}__", inner))
+            }
+            Node::Highlight { children } => {
+                md.push_str(&format!("=={}==", render_inline(children)))
+            }
+            Node::Superscript { children } => {
+                md.push_str(&format!("^{}^", render_inline(children)))
+            }
+            Node::Subscript { children } => md.push_str(&format!("~{}~", render_inline(children))),
+            Node::Inserted { children } => md.push_str(&format!("++{}++", render_inline(children))),
+            Node::Link { text, url, .. } => {
+                md.push_str(&format!("[{}]({})", render_inline(text), url))
+            }
+            Node::Image { alt, url, .. } => md.push_str(&format!("![{}]({})", alt, url)),
+            Node::InlineDirective {
+                name,
+                label,
+                attributes,
+            } => md.push_str(&format!(":{}", directive(name, label, attributes))),
+            Node::WikiLink {
+                target,
+                heading,
+                alias,
+            } => {
+                md.push_str("[[");
+                md.push_str(target);
+                if let Some(heading) = heading {
+                    md.push_str(&format!("#{}", heading));
+                }
+                if let Some(alias) = alias {
+                    md.push_str(&format!("|{}", alias));
+                }
+                md.push_str("]]");
+            }
+            Node::Mention { .. }
+            | Node::IssueReference { .. }
+            | Node::CommitReference { .. }
+            | Node::Hashtag { .. } => md.push_str(&reference_text(node).unwrap_or_default()),
+            Node::Emoji { shortcode, .. } => md.push_str(&format!(":{}:", shortcode)),
+            Node::InlineMath { content } => md.push_str(&format!("${}$", content)),
+            Node::InlineCode { content } => md.push_str(&format!("`{}`", content)),
+
+            // Block nodes nested in inline content
+            _ => md.push_str(&render_block(node)),
+        }
+    }
+    md
+}
+
+/// `name[label]{#id .class key="value"}`, leaving out empty attributes.
+fn directive(name: &str, label: &[Node], attributes: &HashMap<String, String>) -> String {
+    let mut parts = Vec::new();
+    if let Some(id) = attributes.get("id") {
+        parts.push(format!("#{}", id));
+    }
+    if let Some(class) = attributes.get("class") {
+        parts.extend(class.split_whitespace().map(|class| format!(".{}", class)));
+    }
+    let mut keys: Vec<&String> = attributes
+        .keys()
+        .filter(|key| *key != "id" && *key != "class")
+        .collect();
+    keys.sort();
+    parts.extend(
+        keys.into_iter()
+            .map(|key| format!("{}={}", key, quote(&attributes[key]))),
+    );
+
+    let mut md = format!("{}[{}]", name, render_inline(label));
+    if !parts.is_empty() {
+        md.push_str(&format!("{{{}}}", parts.join(" ")));
+    }
+    md
+}
+
+/// Attribute value in double quotes, or single quotes when it contains a
+/// double quote.
+fn quote(value: &str) -> String {
+    if value.contains('"') {
+        format!("'{}'", value)
+    } else {
+        format!("\"{}\"", value)
+    }
+}
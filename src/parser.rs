@@ -1,91 +1,373 @@
 use std::collections::HashMap;
+use std::ops::Range;
 
-use crate::ast::{ListItem, ListType, Node, TableAlignment, TableCell};
+use serde::{Deserialize, Serialize};
+
+use serde_json::{Map, Value};
+
+use crate::ast::visit::{Visitor, walk_node, walk_nodes};
+use crate::ast::{
+    Fold, FrontMatterFormat, ListItem, ListType, Node, TableAlignment, TableCell, is_commit_sha,
+    is_hashtag, is_repository, is_username, plain_text,
+};
+use crate::diagnostics::Diagnostic;
+
+/// A top-level node and the character range of the input it was parsed from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Block {
+    pub span: Range<usize>,
+    pub node: Node,
+}
+
+/// Nodes of a document together with the problems found while parsing it.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ParseResult {
+    pub nodes: Vec<Node>,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+/// Limits that keep parsing of untrusted input bounded. Past a limit the
+/// parser degrades to plain text and reports a diagnostic instead of
+/// recursing further or allocating more nodes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ParseOptions {
+    /// Deepest nesting of blockquotes, custom blocks, lists, links and
+    /// emphasis.
+    pub max_depth: usize,
+    /// Input length in characters; the rest is kept as a single text block.
+    #[serde(rename = "maxInputLength")]
+    pub max_input_len: usize,
+    /// Total nodes, list items and table cells in the tree.
+    pub max_nodes: usize,
+    /// Whether a leading `---` or `+++` block is front matter.
+    pub front_matter: bool,
+    /// `==text==` as [`Node::Highlight`].
+    pub highlight: bool,
+    /// `^text^` as [`Node::Superscript`]; the text cannot contain spaces.
+    pub superscript: bool,
+    /// `~text~` as [`Node::Subscript`]; the text cannot contain spaces.
+    pub subscript: bool,
+    /// `++text++` as [`Node::Inserted`].
+    pub inserted: bool,
+    /// `[[Page]]`, `[[Page#Heading]]` and `[[Page|alias]]` as
+    /// [`Node::WikiLink`].
+    pub wiki_links: bool,
+    /// `@username` in text as [`Node::Mention`].
+    pub mentions: bool,
+    /// `#123` and `owner/repo#123` in text as [`Node::IssueReference`]. A
+    /// heading then needs a space after its `#`s.
+    pub issues: bool,
+    /// Commit SHAs and `owner/repo@sha` in text as [`Node::CommitReference`].
+    pub commits: bool,
+    /// `#tag` in text as [`Node::Hashtag`]. A heading then needs a space
+    /// after its `#`s.
+    pub hashtags: bool,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self {
+            max_depth: 64,
+            max_input_len: 16 * 1024 * 1024,
+            max_nodes: 1_000_000,
+            front_matter: true,
+            highlight: false,
+            superscript: false,
+            subscript: false,
+            inserted: false,
+            wiki_links: false,
+            mentions: false,
+            issues: false,
+            commits: false,
+            hashtags: false,
+        }
+    }
+}
+
+impl ParseOptions {
+    fn references(&self) -> bool {
+        self.mentions || self.issues || self.commits || self.hashtags
+    }
+
+    /// Options for link text, which is not linked again.
+    fn without_references(self) -> Self {
+        Self {
+            mentions: false,
+            issues: false,
+            commits: false,
+            hashtags: false,
+            ..self
+        }
+    }
+}
+
+/// Wraps the children of an inline extension in its node.
+type Wrap = fn(Vec<Node>) -> Node;
+
+/// The `]` matching each `[` on one line, found in a single pass so that
+/// directive labels left open cannot make every later `:` rescan the line.
+#[derive(Debug, Clone, Default)]
+struct Brackets {
+    line: Range<usize>,
+    closers: HashMap<usize, usize>,
+}
+
+impl Brackets {
+    /// Position of the `]` matching the `[` at `open`.
+    fn closing(&mut self, input: &[char], open: usize) -> Option<usize> {
+        if !self.line.contains(&open) {
+            let start = input[..open]
+                .iter()
+                .rposition(|&c| c == '\n')
+                .map_or(0, |i| i + 1);
+            let end = input[open..]
+                .iter()
+                .position(|&c| c == '\n')
+                .map_or(input.len(), |i| open + i);
+            self.line = start..end;
+            self.closers.clear();
+
+            let mut opens = Vec::new();
+            for (i, &c) in input.iter().enumerate().take(end).skip(start) {
+                match c {
+                    '[' if !is_escaped(input, i) => opens.push(i),
+                    ']' if !is_escaped(input, i) => {
+                        if let Some(open) = opens.pop() {
+                            self.closers.insert(open, i);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        self.closers.get(&open).copied()
+    }
+}
+
+/// Usage shared by a parser and the nested parsers it spawns.
+#[derive(Debug, Clone, Copy, Default)]
+struct Budget {
+    nodes: usize,
+    depth_reported: bool,
+    nodes_reported: bool,
+}
 
 pub struct Parser {
     input: Vec<char>,
     pos: usize,
+    // Offset of `input` within the outermost document, for nested parsers.
+    offset: usize,
+    diagnostics: Vec<Diagnostic>,
+    options: ParseOptions,
+    depth: usize,
+    budget: Budget,
+    // Input past `max_input_len`, emitted unparsed after everything else.
+    overflow: Vec<char>,
+    brackets: Brackets,
+    // An inline extension marker with no closing occurrence before the
+    // position, so later openers on the line need not search again.
+    unclosed: Option<(&'static str, usize)>,
 }
 
 impl Parser {
     pub fn new(input: &str) -> Self {
+        Self::with_options(input, ParseOptions::default())
+    }
+
+    pub fn with_options(input: &str, options: ParseOptions) -> Self {
+        let mut input: Vec<char> = input.chars().collect();
+        let overflow = if input.len() > options.max_input_len {
+            input.split_off(options.max_input_len)
+        } else {
+            Vec::new()
+        };
+
         Self {
-            input: input.chars().collect(),
+            input,
             pos: 0,
+            offset: 0,
+            diagnostics: Vec::new(),
+            options,
+            depth: 0,
+            budget: Budget::default(),
+            overflow,
+            brackets: Brackets::default(),
+            unclosed: None,
         }
     }
 
+    pub fn parse_with_diagnostics(&mut self) -> ParseResult {
+        let nodes = self.parse_document();
+        ParseResult {
+            nodes,
+            diagnostics: self.take_diagnostics(),
+        }
+    }
+
+    /// Diagnostics collected so far.
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
+    pub fn take_diagnostics(&mut self) -> Vec<Diagnostic> {
+        std::mem::take(&mut self.diagnostics)
+    }
+
     pub fn parse_document(&mut self) -> Vec<Node> {
-        let mut nodes = Vec::new();
-        while !self.is_eof() {
-            let start_pos = self.pos;
+        self.parse_blocks()
+            .into_iter()
+            .map(|block| block.node)
+            .collect()
+    }
 
-            self.skip_empty_lines();
-            if self.is_eof() {
-                break;
-            }
+    /// Parses the remaining input into top-level blocks with their spans.
+    pub fn parse_blocks(&mut self) -> Vec<Block> {
+        let mut blocks = Vec::new();
+        while let Some(block) = self.next_block() {
+            blocks.push(block);
+        }
+        blocks
+    }
 
-            // Heading parsing
-            if self.starts_with("#") {
-                nodes.push(self.parse_heading());
-            }
-            // HR parsing
-            else if self.starts_with("---") {
-                self.read_line();
-                nodes.push(Node::HorizontalRule);
-            }
-            // Code block parsing
-            else if self.starts_with("```") {
-                nodes.push(self.parse_code_block());
-            }
-            // Blockquote parsing
-            else if self.starts_with(">") {
-                nodes.push(self.parse_blockquote());
-            }
-            // Math block parsing
-            else if self.starts_with("$$") {
-                nodes.push(self.parse_block_math());
-            }
-            // Custom block parsing
-            else if self.starts_with(":::") {
-                nodes.push(self.parse_custom_block());
-            }
-            // Table parsing
-            else if self.is_table_start() {
-                if let Some(table) = self.parse_table() {
-                    nodes.push(table);
-                } else {
-                    let line = self.read_line();
-                    nodes.push(Node::Paragraph {
-                        children: vec![Node::Text { content: line }],
-                    });
+    /// Parses the next top-level block, skipping blank lines before it.
+    pub fn next_block(&mut self) -> Option<Block> {
+        self.skip_empty_lines();
+        if self.is_eof() {
+            return self.overflow_block();
+        }
+        let block_start = self.pos;
+
+        if self.nodes_exhausted() {
+            let rest: String = self.rest().iter().collect();
+            self.pos = self.input.len();
+            self.warn_nodes(block_start..self.pos);
+            return Some(Block {
+                span: block_start..self.pos,
+                node: Node::Paragraph {
+                    children: vec![Node::Text { content: rest }],
+                },
+            });
+        }
+        self.budget.nodes += 1;
+
+        // Front matter, only at the very start of the document
+        let front_matter = if block_start == 0 && self.depth == 0 && self.options.front_matter {
+            self.parse_front_matter()
+        } else {
+            None
+        };
+        let mut node = if let Some(front_matter) = front_matter {
+            front_matter
+        }
+        // Heading parsing
+        else if self.starts_with("#") && self.is_heading_start() {
+            self.parse_heading()
+        }
+        // HR parsing
+        else if self.starts_with("---") {
+            self.read_line();
+            Node::HorizontalRule
+        }
+        // Code block parsing
+        else if self.starts_with("```") {
+            self.parse_code_block()
+        }
+        // Blockquote parsing
+        else if self.starts_with(">") {
+            self.parse_blockquote()
+        }
+        // Math block parsing
+        else if self.starts_with("$$") {
+            self.parse_block_math()
+        }
+        // Custom block parsing
+        else if self.starts_with(":::") && self.is_custom_block_start() {
+            self.parse_custom_block()
+        }
+        // Leaf directive parsing
+        else if self.starts_with("::")
+            && let Some(directive) = self.parse_leaf_directive()
+        {
+            directive
+        }
+        // Table parsing
+        else if self.is_table_start() {
+            if let Some(table) = self.parse_table() {
+                table
+            } else {
+                let line = self.read_line();
+                Node::Paragraph {
+                    children: vec![Node::Text { content: line }],
                 }
             }
-            // List parsing
-            else if self.is_list_start() {
-                let line = self.peek_line();
-                let (indent, _) = self.get_line_indentation(&line);
+        }
+        // List parsing
+        else if self.is_list_start() {
+            let line = self.peek_line();
+            let (indent, _) = self.get_line_indentation(&line);
 
-                nodes.push(self.parse_list(indent));
-            }
-            // inline elements
-            else {
-                let inline = self.parse_inline_elements('\n');
-                self.consume_if('\n');
-                nodes.push(Node::Paragraph { children: inline });
+            self.parse_list(indent)
+        }
+        // Definition list parsing
+        else if self.is_definition_list_start() {
+            self.parse_definition_list()
+        }
+        // inline elements
+        else {
+            let inline = self.parse_inline_elements('\n');
+            self.consume_if('\n');
+            Node::Paragraph { children: inline }
+        };
+
+        // A block parser that consumed nothing must not stall the loop.
+        if self.pos <= block_start {
+            let fallback = self.read_line();
+            if !fallback.trim().is_empty() {
+                node = Node::Text { content: fallback };
+            } else {
+                return None;
             }
+        }
 
-            if self.pos <= start_pos {
-                let fallback = self.read_line();
-                if !fallback.trim().is_empty() {
-                    nodes.push(Node::Text { content: fallback });
-                } else {
-                    println!("This is else block");
-                    break;
-                }
-            }
+        Some(Block {
+            span: block_start..self.pos,
+            node,
+        })
+    }
+
+    /// Text past `max_input_len`, as one unparsed paragraph.
+    fn overflow_block(&mut self) -> Option<Block> {
+        if self.overflow.is_empty() {
+            return None;
         }
-        nodes
+        let content: String = std::mem::take(&mut self.overflow).into_iter().collect();
+        let span = self.input.len()..self.input.len() + content.chars().count();
+        self.warn(
+            "input-too-long",
+            format!(
+                "input is longer than {} characters; the rest is not parsed",
+                self.options.max_input_len
+            ),
+            span.clone(),
+        );
+        Some(Block {
+            span,
+            node: Node::Paragraph {
+                children: vec![Node::Text { content }],
+            },
+        })
+    }
+
+    /// Current position, in characters from the start of the input.
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    /// Moves to a character offset, which must be the start of a line for
+    /// block parsing to be meaningful.
+    pub fn seek(&mut self, pos: usize) {
+        self.pos = pos.min(self.input.len());
     }
 
     /// Inline elements parser
@@ -103,10 +385,38 @@ impl Parser {
                 break;
             }
 
+            // Past a limit, markers are kept as literal text.
+            let nests = ch == '*' || ch == '[' || self.starts_with("__") || self.starts_with("~~");
+            if self.budget.nodes + nodes.len() >= self.options.max_nodes {
+                let at = self.pos;
+                self.warn_nodes(at..at + 1);
+                text_acc.push(self.next_char());
+                continue;
+            }
+            if nests && !self.can_nest() {
+                text_acc.push(self.next_char());
+                continue;
+            }
+
             if ch == '\\' {
-                self.flush_text(&mut text_acc, &mut nodes);
-                self.consume(1);
-                nodes.push(Node::LineBreak);
+                // Escaped punctuation is text; a lone backslash breaks the line
+                match self.rest().get(1).copied() {
+                    Some(escaped) if escaped.is_ascii_punctuation() => {
+                        self.consume(2);
+                        text_acc.push(escaped);
+                    }
+                    // At the end of a line, the text goes on on the next one
+                    Some('\n') if delimiter == '\n' && self.continues_after_break() => {
+                        self.flush_text(&mut text_acc, &mut nodes);
+                        self.consume(2);
+                        nodes.push(Node::LineBreak);
+                    }
+                    _ => {
+                        self.flush_text(&mut text_acc, &mut nodes);
+                        self.consume(1);
+                        nodes.push(Node::LineBreak);
+                    }
+                }
             }
             // Inline math
             else if ch == '$' && !self.starts_with("$$") {
@@ -117,7 +427,7 @@ impl Parser {
             else if self.starts_with("***") {
                 self.flush_text(&mut text_acc, &mut nodes);
                 self.consume(3);
-                let inner = self.parse_inline_elements('*');
+                let inner = self.parse_nested_inline('*');
                 self.consume_repeated('*', 3);
                 nodes.push(Node::Bold {
                     children: vec![Node::Italic { children: inner }],
@@ -127,27 +437,45 @@ impl Parser {
             else if self.starts_with("**") {
                 self.flush_text(&mut text_acc, &mut nodes);
                 self.consume(2);
-                let inner = self.parse_inline_elements('*');
+                let inner = self.parse_nested_inline('*');
                 self.consume_repeated('*', 2);
                 nodes.push(Node::Bold { children: inner });
             } else if self.starts_with("__") {
                 self.flush_text(&mut text_acc, &mut nodes);
                 self.consume(2);
-                let inner = self.parse_inline_elements('_');
+                let inner = self.parse_nested_inline('_');
                 self.consume_repeated('_', 2);
                 nodes.push(Node::Underline { children: inner });
             } else if self.starts_with("~~") {
                 self.flush_text(&mut text_acc, &mut nodes);
                 self.consume(2);
-                let inner = self.parse_inline_elements('~');
+                let inner = self.parse_nested_inline('~');
                 self.consume_repeated('~', 2);
                 nodes.push(Node::Strikethrough { children: inner });
             }
+            // Highlight, superscript, subscript and inserted text, when enabled
+            else if let Some((marker, wrap)) = self.extension_marker()
+                && let Some(len) = self.enclosed(marker)
+            {
+                if !self.can_nest() {
+                    text_acc.push(self.next_char());
+                    continue;
+                }
+                self.flush_text(&mut text_acc, &mut nodes);
+                let start = self.pos + marker.len();
+                let end = start + len;
+                let content: String = self.input[start..end].iter().collect();
+                let mut sub_parser = self.nested(&content, start);
+                let children = sub_parser.parse_inline_elements('\0');
+                self.absorb(sub_parser, Some(start..end));
+                self.seek(end + marker.len());
+                nodes.push(wrap(children));
+            }
             // Single italic *
             else if ch == '*' {
                 self.flush_text(&mut text_acc, &mut nodes);
                 self.consume(1);
-                let inner = self.parse_inline_elements('*');
+                let inner = self.parse_nested_inline('*');
                 self.consume_if('*');
                 nodes.push(Node::Italic { children: inner });
             } else if ch == '`' {
@@ -163,6 +491,13 @@ impl Parser {
                 nodes.push(Node::InlineCode {
                     content: code_content,
                 });
+            } else if self.options.wiki_links
+                && self.starts_with("[[")
+                && let Some((link, len)) = self.wiki_link()
+            {
+                self.flush_text(&mut text_acc, &mut nodes);
+                self.consume(len);
+                nodes.push(link);
             } else if self.starts_with("![") {
                 self.flush_text(&mut text_acc, &mut nodes);
                 self.consume(2);
@@ -172,7 +507,11 @@ impl Parser {
                 if self.peek() == '(' {
                     self.consume(1);
                     let url = self.read_until(')');
-                    nodes.push(Node::Image { alt, url });
+                    nodes.push(Node::Image {
+                        alt,
+                        url,
+                        attributes: HashMap::new(),
+                    });
                 } else {
                     text_acc.push_str(&format!("![{}", alt));
                 }
@@ -180,32 +519,279 @@ impl Parser {
                 self.flush_text(&mut text_acc, &mut nodes);
                 self.consume(1);
 
+                let text_start = self.pos;
                 let link_text_raw = self.read_until(']');
 
                 if self.peek() == '(' {
                     self.consume(1);
                     let url = self.read_until(')');
 
-                    let mut sub_parser = Parser::new(&link_text_raw);
+                    let mut sub_parser = self.nested(&link_text_raw, text_start);
+                    sub_parser.options = self.options.without_references();
+                    let text = sub_parser.parse_inline_elements('\0');
+                    self.absorb(sub_parser, Some(text_start..self.pos));
                     nodes.push(Node::Link {
-                        text: sub_parser.parse_inline_elements('\0'),
+                        text,
                         url,
+                        attributes: HashMap::new(),
                     });
                 } else {
                     text_acc.push_str(&format!("[{}", link_text_raw));
                 }
             }
+            // Emoji shortcode, with the `emoji` feature
+            else if ch == ':'
+                && let Some((shortcode, unicode)) = self.emoji_shortcode()
+            {
+                self.flush_text(&mut text_acc, &mut nodes);
+                self.consume(shortcode.len() + 2);
+                nodes.push(Node::Emoji {
+                    shortcode,
+                    unicode: unicode.to_string(),
+                });
+            }
+            // Inline directive, unless the colon ends a word like `note:`
+            else if ch == ':'
+                && !text_acc.ends_with(char::is_alphanumeric)
+                && let Some(directive) = {
+                    let (input, brackets, at) = (&self.input, &mut self.brackets, self.pos + 1);
+                    read_directive(&input[at..], |i| {
+                        brackets.closing(input, at + i).map(|close| close - at - i)
+                    })
+                }
+            {
+                if !self.can_nest() {
+                    text_acc.push(self.next_char());
+                    continue;
+                }
+                self.flush_text(&mut text_acc, &mut nodes);
+                let start = self.pos;
+                let label = self.parse_directive_label(&directive, start + 1);
+                self.consume(1 + directive.len);
+                nodes.push(Node::InlineDirective {
+                    name: directive.name,
+                    label,
+                    attributes: directive.attributes,
+                });
+            }
             // Normal text
             else {
                 text_acc.push(self.next_char());
             }
         }
         self.flush_text(&mut text_acc, &mut nodes);
+        self.budget.nodes += nodes.len();
+        nodes
+    }
+
+    /// The enabled extension marker at the current position and the node it
+    /// wraps its content in. `~~` stays strikethrough.
+    fn extension_marker(&self) -> Option<(&'static str, Wrap)> {
+        let options = &self.options;
+        if options.highlight && self.starts_with("==") {
+            Some(("==", |children| Node::Highlight { children }))
+        } else if options.inserted && self.starts_with("++") {
+            Some(("++", |children| Node::Inserted { children }))
+        } else if options.superscript && self.starts_with("^") {
+            Some(("^", |children| Node::Superscript { children }))
+        } else if options.subscript && self.starts_with("~") && !self.starts_with("~~") {
+            Some(("~", |children| Node::Subscript { children }))
+        } else {
+            None
+        }
+    }
+
+    /// The `[[...]]` wiki link at the current position and its length. The
+    /// link ends on the same line and has a page or a heading.
+    fn wiki_link(&self) -> Option<(Node, usize)> {
+        let inner: String = self.rest()[2..]
+            .iter()
+            .take_while(|&&c| c != '\n' && c != '[' && c != ']')
+            .collect();
+        let len = inner.chars().count() + 4;
+        if self.rest().get(len - 2..len) != Some(&[']', ']']) {
+            return None;
+        }
+
+        let (link, alias) = match inner.split_once('|') {
+            Some((link, alias)) => (link, Some(alias.trim())),
+            None => (inner.as_str(), None),
+        };
+        let (target, heading) = match link.split_once('#') {
+            Some((target, heading)) => (target.trim(), Some(heading.trim())),
+            None => (link.trim(), None),
+        };
+        let heading = heading.filter(|heading| !heading.is_empty());
+        if target.is_empty() && heading.is_none() {
+            return None;
+        }
+        let node = Node::WikiLink {
+            target: target.to_string(),
+            heading: heading.map(str::to_string),
+            alias: alias.filter(|alias| !alias.is_empty()).map(str::to_string),
+        };
+        Some((node, len))
+    }
+
+    /// The known `:shortcode:` at the current position and its emoji.
+    fn emoji_shortcode(&self) -> Option<(String, &'static str)> {
+        if crate::emoji::shortcodes().is_empty() {
+            return None;
+        }
+        let shortcode: String = self.rest()[1..]
+            .iter()
+            .take_while(|&&c| crate::emoji::is_shortcode(c.encode_utf8(&mut [0; 4])))
+            .collect();
+        if self.rest().get(shortcode.len() + 1) != Some(&':') {
+            return None;
+        }
+        let unicode = crate::emoji::lookup(&shortcode)?;
+        Some((shortcode, unicode))
+    }
+
+    /// Length of the text between `marker` here and its next occurrence on
+    /// the line. The text must not start or end with whitespace, and
+    /// single-character markers (`^`, `~`) allow none at all.
+    fn enclosed(&mut self, marker: &'static str) -> Option<usize> {
+        if let Some((unclosed, before)) = self.unclosed
+            && unclosed == marker
+            && self.pos < before
+        {
+            return None;
+        }
+        let start = self.pos + marker.len();
+        let rest = &self.input[start.min(self.input.len())..];
+        if rest.first().is_none_or(|c| c.is_whitespace()) {
+            return None;
+        }
+
+        for (i, &c) in rest.iter().enumerate() {
+            if c == '\n' {
+                self.unclosed = Some((marker, start + i));
+                return None;
+            }
+            if marker.len() == 1 && c.is_whitespace() {
+                return None;
+            }
+            if marker
+                .chars()
+                .eq(rest[i..].iter().take(marker.len()).copied())
+                && !is_escaped(rest, i)
+            {
+                return (i > 0 && !rest[i - 1].is_whitespace()).then_some(i);
+            }
+        }
+        self.unclosed = Some((marker, self.input.len()));
+        None
+    }
+
+    fn parse_nested_inline(&mut self, delimiter: char) -> Vec<Node> {
+        self.depth += 1;
+        let nodes = self.parse_inline_elements(delimiter);
+        self.depth -= 1;
         nodes
     }
 }
 
 impl Parser {
+    /// A `---` (YAML) or `+++` (TOML) line, the metadata and a matching
+    /// closing line. Without a closing line, or when the lines between do
+    /// not decode to a table of keys, nothing is consumed.
+    fn parse_front_matter(&mut self) -> Option<Node> {
+        let format = match self.peek_line().trim_end() {
+            "---" => FrontMatterFormat::Yaml,
+            "+++" => FrontMatterFormat::Toml,
+            _ => return None,
+        };
+        let start = self.pos;
+        self.read_line();
+
+        let mut lines = Vec::new();
+        loop {
+            if self.is_eof() {
+                self.pos = start;
+                return None;
+            }
+            let line = self.read_line();
+            if line.trim_end() == format.delimiter() {
+                break;
+            }
+            lines.push(line);
+        }
+
+        let raw = lines.join("\n");
+        // Only `---\n---` is empty front matter; a blank or comment-only
+        // block between rules stays content.
+        let decoded = if lines.is_empty() {
+            Ok(Some(Map::new()))
+        } else {
+            decode_front_matter(format, &raw)
+        };
+        let metadata = match decoded {
+            Ok(Some(metadata)) => metadata,
+            // Text between two rules, e.g. `---\nIntro\n---`
+            Ok(None) => {
+                self.pos = start;
+                return None;
+            }
+            Err(message) => {
+                let end = self.pos;
+                self.warn("invalid-front-matter", message, start..end);
+                self.pos = start;
+                return None;
+            }
+        };
+        Some(Node::FrontMatter {
+            format,
+            raw,
+            metadata,
+        })
+    }
+
+    /// Whether the line after the `\` and newline here continues the text:
+    /// it is not blank and does not start a block of its own.
+    fn continues_after_break(&mut self) -> bool {
+        let start = self.pos;
+        self.pos += 2;
+        while matches!(self.peek(), ' ' | '\t') {
+            self.pos += 1;
+        }
+        let line = self.peek_line();
+        let starts_block = (self.starts_with("#") && self.is_heading_start())
+            || ["---", "```", ">", "$$", "::"]
+                .iter()
+                .any(|marker| self.starts_with(marker))
+            || definition_content(&line).is_some()
+            || self.is_table_start()
+            || self.is_list_start()
+            || self.is_definition_list_start();
+        self.pos = start;
+        !line.trim().is_empty() && !starts_block
+    }
+
+    /// Whether the `#` here starts a heading, with at most six `#`s. With
+    /// issue references or hashtags, `#123` and `#tag` at the start of a
+    /// line are text.
+    fn is_heading_start(&self) -> bool {
+        let hashes = self.rest().iter().take_while(|&&c| c == '#').count();
+        if hashes > 6 {
+            return false;
+        }
+        if !self.options.issues && !self.options.hashtags {
+            return true;
+        }
+        self.rest().get(hashes).is_none_or(|c| c.is_whitespace())
+    }
+
+    /// Whether the `:::` here is followed by a block name; a bare `:::`
+    /// outside of a block is text.
+    fn is_custom_block_start(&self) -> bool {
+        self.rest()[3..]
+            .iter()
+            .find(|c| !matches!(c, ' ' | '\t'))
+            .is_some_and(|c| !c.is_whitespace() && *c != '[' && *c != '{')
+    }
+
     /// Heading parser
     /// # Heading 1
     fn parse_heading(&mut self) -> Node {
@@ -218,12 +804,11 @@ impl Parser {
         let content = self.parse_inline_elements('\n');
         self.consume_if('\n');
 
-        let raw_text = self.extract_plain_text(&content);
-        let id = self.slugify(&raw_text);
+        let id = slugify(&heading_text(&content));
 
         Node::Heading {
-            level: level,
-            id: id,
+            level,
+            id,
             children: content,
         }
     }
@@ -248,29 +833,19 @@ impl Parser {
 
             if indent < base_indent {
                 break;
-            } else if indent > base_indent {
-                println!("indent > base_indent");
-                if let Some(last_item) = items.last_mut() {
-                    if self.is_list_line(&trimmed_line) {
-                        last_item.children.push(self.parse_list(indent));
-                    } else {
-                        self.read_line();
-                        let mut p = Parser::new(trimmed_line.trim());
-                        let mut extra_content = p.parse_inline_elements('\0');
-                        last_item.content.push(Node::LineBreak);
-                        last_item.content.append(&mut extra_content);
-                    }
-                    continue;
+            } else if indent > base_indent
+                && let Some(last_item) = items.last_mut()
+            {
+                if self.is_list_line(&trimmed_line) && self.can_nest() {
+                    self.depth += 1;
+                    last_item.children.push(self.parse_list(indent));
+                    self.depth -= 1;
                 } else {
-                    if let Some(last_item) = items.last_mut() {
-                        self.read_line();
-                        let mut p = Parser::new(trimmed_line.trim());
-                        let mut extra_content = p.parse_inline_elements('\0');
-                        last_item.content.push(Node::LineBreak);
-                        last_item.content.append(&mut extra_content);
-                        continue;
-                    }
+                    let mut extra_content = self.parse_list_line(trimmed_line.trim());
+                    last_item.content.push(Node::LineBreak);
+                    last_item.content.append(&mut extra_content);
                 }
+                continue;
             }
 
             if !self.is_list_line(&trimmed_line) {
@@ -282,14 +857,12 @@ impl Parser {
                 break;
             }
 
-            self.read_line();
-
             let clean_content = self.clean_marker(&trimmed_line, initial_kind.clone());
             let (checked, final_text) = self.extract_checkbox(&clean_content);
 
-            let mut p = Parser::new(&final_text);
+            self.budget.nodes += 1;
             items.push(ListItem {
-                content: p.parse_inline_elements('\0'),
+                content: self.parse_list_line(&final_text),
                 checked,
                 children: Vec::new(),
             });
@@ -301,9 +874,20 @@ impl Parser {
         }
     }
 
+    /// Consumes the current line and parses `text`, taken from it, inline.
+    fn parse_list_line(&mut self, text: &str) -> Vec<Node> {
+        let line_start = self.pos;
+        self.read_line();
+        let mut sub_parser = self.nested(text, line_start);
+        let nodes = sub_parser.parse_inline_elements('\0');
+        self.absorb(sub_parser, Some(line_start..self.pos));
+        nodes
+    }
+
     /// Blockquote parser
     /// > This is blockquote
     fn parse_blockquote(&mut self) -> Node {
+        let start = self.pos;
         let mut inner_content = String::new();
         while self.starts_with(">") {
             self.consume(1);
@@ -312,25 +896,54 @@ impl Parser {
             inner_content.push('\n');
             self.skip_whitespace_inline();
         }
-        let mut sub_parser = Parser::new(&inner_content);
-        Node::BlockQuote {
-            children: sub_parser.parse_document(),
+        // Quote markers are stripped, so nested spans cannot be mapped back
+        // exactly; they are reported against the whole quote instead.
+        if !self.can_nest() {
+            return Node::BlockQuote {
+                children: vec![Node::Text {
+                    content: inner_content,
+                }],
+            };
+        }
+        let callout = callout_marker(&inner_content);
+        let body = callout
+            .as_ref()
+            .map_or(inner_content.as_str(), |marker| marker.body);
+
+        let mut sub_parser = self.nested(body, start);
+        let children = sub_parser.parse_document();
+        self.absorb(sub_parser, Some(start..self.pos));
+
+        let Some(marker) = callout else {
+            return Node::BlockQuote { children };
+        };
+        let mut title_parser = self.nested(marker.title, start);
+        let title = title_parser.parse_inline_elements('\n');
+        self.absorb(title_parser, Some(start..self.pos));
+        Node::Callout {
+            kind: marker.kind,
+            title,
+            foldable: marker.foldable,
+            children,
         }
     }
 
     /// Code block parser
+    /// ````markdown
     /// ```rust
     /// fn main() {
     ///     println!("Hello from Rust");
     /// }
     /// ```
+    /// ````
     fn parse_code_block(&mut self) -> Node {
+        let start = self.pos;
         self.consume(3);
 
         let header = self.read_line();
         let parts: Vec<&str> = header.split_whitespace().collect();
 
-        let lang = parts.get(0).unwrap_or(&"").to_string();
+        let lang = parts.first().unwrap_or(&"").to_string();
         let filename = parts.get(1).map(|s| s.to_string());
         let mut code = String::new();
 
@@ -339,6 +952,12 @@ impl Parser {
         }
         if self.starts_with("```") {
             self.consume(3);
+        } else {
+            self.warn(
+                "unclosed-code-fence",
+                "code fence is never closed",
+                start..self.pos,
+            );
         }
         Node::CodeBlock {
             lang,
@@ -352,16 +971,22 @@ impl Parser {
     /// |---|---|
     /// | 1 | 2 |
     fn parse_table(&mut self) -> Option<Node> {
+        let header_start = self.pos;
         let header_line = self.read_line();
+        let sep_start = self.pos;
         let sep_line = self.read_line();
 
         let alignments = self.parse_alignments(&sep_line);
-        let header = self.split_table_row(&header_line, &alignments);
+        let header = self.split_table_row(&header_line, &alignments, header_start..sep_start);
+        self.check_columns(header.len(), alignments.len(), sep_start..self.pos);
 
         let mut rows = Vec::new();
         while !self.is_eof() && self.is_table_start() {
+            let row_start = self.pos;
             let line = self.read_line();
-            rows.push(self.split_table_row(&line, &alignments));
+            let row = self.split_table_row(&line, &alignments, row_start..self.pos);
+            self.check_columns(header.len(), row.len(), row_start..self.pos);
+            rows.push(row);
         }
 
         Some(Node::Table { header, rows })
@@ -372,6 +997,7 @@ impl Parser {
     /// c^2 = a^2 + b^
     /// $$
     fn parse_block_math(&mut self) -> Node {
+        let start = self.pos;
         self.consume(2);
         let mut content = String::new();
         while !self.is_eof() && !self.starts_with("$$") {
@@ -379,6 +1005,12 @@ impl Parser {
         }
         if self.starts_with("$$") {
             self.consume(2);
+        } else {
+            self.warn(
+                "unclosed-math-block",
+                "`$$` math block is never closed",
+                start..self.pos,
+            );
         }
         Node::BlockMath {
             content: content.trim().to_string(),
@@ -394,39 +1026,161 @@ impl Parser {
             content.push(self.next_char());
         }
         self.consume_if('$');
-        Node::InlineMath { content: content }
+        Node::InlineMath { content }
+    }
+
+    /// Definition list parser
+    /// Term
+    /// : Definition
+    /// : Another definition
+    fn parse_definition_list(&mut self) -> Node {
+        let mut children = Vec::new();
+        loop {
+            let term = self.parse_inline_elements('\n');
+            self.consume_if('\n');
+            children.push(Node::DefinitionTerm { children: term });
+
+            let mut end;
+            loop {
+                children.push(self.parse_definition_description());
+                end = self.pos;
+                self.skip_blank_lines();
+                if definition_content(&self.peek_line()).is_none() {
+                    break;
+                }
+            }
+
+            // Blank lines are part of the list only when another term follows
+            if self.is_eof() || !self.is_definition_list_start() {
+                self.pos = end;
+                break;
+            }
+        }
+        Node::DefinitionList { children }
+    }
+
+    /// One `: ` line with its continuation: following lines indented by a
+    /// space or tab, also after blank lines.
+    fn parse_definition_description(&mut self) -> Node {
+        let start = self.pos;
+        let line = self.read_line();
+        let mut content = definition_content(&line).unwrap_or_default().to_string();
+        content.push('\n');
+
+        loop {
+            let end = self.pos;
+            let blank = self.skip_blank_lines();
+            let line = self.peek_line();
+            if self.is_eof()
+                || !line.starts_with([' ', '\t'])
+                || definition_content(&line).is_some()
+            {
+                self.pos = end;
+                break;
+            }
+            content.push_str(&"\n".repeat(blank));
+            content.push_str(strip_indent(&line));
+            content.push('\n');
+            self.read_line();
+        }
+
+        let children = if self.can_nest() {
+            let mut sub_parser = self.nested(&content, start);
+            let children = sub_parser.parse_document();
+            self.absorb(sub_parser, Some(start..self.pos));
+            children
+        } else {
+            vec![Node::Text { content }]
+        };
+        Node::DefinitionDescription { children }
+    }
+
+    /// Leaf directive parser; `None` when the line holds anything besides
+    /// the directive.
+    /// ::youtube[Intro]{#intro id=abc}
+    fn parse_leaf_directive(&mut self) -> Option<Node> {
+        let line: Vec<char> = self.peek_line().chars().collect();
+        let directive = read_directive(&line[2..], |i| closing(&line[2 + i..], '[', ']'))?;
+        if line[2 + directive.len..].iter().any(|c| !c.is_whitespace()) || !self.can_nest() {
+            return None;
+        }
+
+        let start = self.pos;
+        let label = self.parse_directive_label(&directive, start + 2);
+        self.read_line();
+        Some(Node::LeafDirective {
+            name: directive.name,
+            label,
+            attributes: directive.attributes,
+        })
+    }
+
+    /// Inline nodes of a directive's label, for a directive whose name
+    /// starts at `pos`.
+    fn parse_directive_label(&mut self, directive: &Directive, pos: usize) -> Vec<Node> {
+        let Some((offset, label)) = &directive.label else {
+            return Vec::new();
+        };
+        let start = pos + offset;
+        let mut sub_parser = self.nested(label, start);
+        let nodes = sub_parser.parse_inline_elements('\0');
+        self.absorb(sub_parser, Some(start..start + label.chars().count()));
+        nodes
     }
 
     /// Custom block parser
-    /// :::tab title=Tab 1
+    /// :::tab[Rust]{#rust .active}
+    /// :::tab title="Tab 1"
     /// content
     /// :::
     fn parse_custom_block(&mut self) -> Node {
+        let start = self.pos;
         self.consume(3);
 
-        let header = self.read_line();
-        let mut parts = header.split_whitespace();
-
-        let name = parts.next().unwrap_or("").to_string();
+        let header: Vec<char> = self.read_line().chars().collect();
+        let content_start = self.pos;
 
+        let lead = header.iter().take_while(|c| c.is_whitespace()).count();
+        let name_len = header[lead..]
+            .iter()
+            .take_while(|&&c| !c.is_whitespace() && c != '[' && c != '{')
+            .count();
+        let name: String = header[lead..lead + name_len].iter().collect();
+        let mut at = lead + name_len;
+
+        let mut label_range = None;
+        if let Some(close) = closing(&header[at..], '[', ']') {
+            label_range = Some(at + 1..at + close);
+            at += close + 1;
+        }
         let mut attributes = HashMap::new();
-        for part in parts {
-            if let Some((key, value)) = part.split_once('=') {
-                let clean_value = value.trim_matches('"').to_string();
-                attributes.insert(key.to_string(), clean_value);
-            }
+        if let Some(close) = closing(&header[at..], '{', '}') {
+            let inner: String = header[at + 1..at + close].iter().collect();
+            attributes = parse_attributes(&inner, true);
+            at += close + 1;
         }
+        // The older `key=value` form after the name
+        let rest: String = header[at..].iter().collect();
+        attributes.extend(parse_attributes(&rest, false));
+
+        let label = match label_range {
+            Some(range) if self.can_nest() => {
+                let text: String = header[range.clone()].iter().collect();
+                let label_start = start + 3 + range.start;
+                let mut sub_parser = self.nested(&text, label_start);
+                let label = sub_parser.parse_inline_elements('\0');
+                self.absorb(sub_parser, Some(label_start..start + 3 + range.end));
+                label
+            }
+            _ => Vec::new(),
+        };
 
         let mut inner_content = String::new();
         let mut nest_level = 1;
 
         while !self.is_eof() && nest_level > 0 {
             if self.starts_with(":::") {
-                let remaining = &self.input[self.pos..];
-                let line = remaining
-                    .iter()
-                    .take_while(|&&c| c != '\n')
-                    .collect::<String>();
+                let line = self.peek_line();
 
                 if line.trim() == ":::" {
                     nest_level -= 1;
@@ -442,7 +1196,7 @@ impl Parser {
                     nest_level += 1;
                     inner_content.push_str(&line);
                     inner_content.push('\n');
-                    self.consume(line.len());
+                    self.consume(line.chars().count());
                     self.consume_if('\n');
                 }
             } else {
@@ -450,16 +1204,114 @@ impl Parser {
             }
         }
 
-        let mut sub_parser = Parser::new(&inner_content);
+        if nest_level > 0 {
+            self.warn(
+                "unclosed-custom-block",
+                format!("custom block `:::{}` is never closed", name),
+                start..self.pos,
+            );
+        }
+
+        let children = if self.can_nest() {
+            let mut sub_parser = self.nested(&inner_content, content_start);
+            let children = sub_parser.parse_document();
+            self.absorb(sub_parser, None);
+            children
+        } else {
+            vec![Node::Text {
+                content: inner_content,
+            }]
+        };
         Node::CustomBlock {
             name,
+            label,
             attributes,
-            children: sub_parser.parse_document(),
+            children,
         }
     }
 }
 
 impl Parser {
+    /// Parser for a nested region starting at `pos` in this parser's input,
+    /// one level deeper and sharing this parser's budget.
+    fn nested(&self, input: &str, pos: usize) -> Parser {
+        let mut parser = Parser::with_options(input, self.options);
+        parser.offset = self.offset + pos;
+        parser.depth = self.depth + 1;
+        parser.budget = self.budget;
+        parser
+    }
+
+    /// Takes over a nested parser's diagnostics, optionally replacing their
+    /// spans with `span` when positions inside it do not map back.
+    fn absorb(&mut self, nested: Parser, span: Option<Range<usize>>) {
+        self.budget = nested.budget;
+        // Nested input can carry a newline the source lacks at its end.
+        let end = self.offset + self.input.len();
+        for mut diagnostic in nested.diagnostics {
+            diagnostic.span = match &span {
+                Some(span) => self.offset + span.start..self.offset + span.end,
+                None => diagnostic.span.start.min(end)..diagnostic.span.end.min(end),
+            };
+            self.diagnostics.push(diagnostic);
+        }
+    }
+
+    fn warn(&mut self, code: &str, message: impl Into<String>, span: Range<usize>) {
+        let span = self.offset + span.start..self.offset + span.end;
+        self.diagnostics
+            .push(Diagnostic::warning(code, message, span));
+    }
+
+    /// Whether another level of nesting is allowed here; reports the first
+    /// time it is not.
+    fn can_nest(&mut self) -> bool {
+        if self.depth < self.options.max_depth {
+            return true;
+        }
+        if !self.budget.depth_reported {
+            self.budget.depth_reported = true;
+            let at = self.pos;
+            self.warn(
+                "nesting-too-deep",
+                format!(
+                    "nesting deeper than {} levels is kept as text",
+                    self.options.max_depth
+                ),
+                at..at + 1,
+            );
+        }
+        false
+    }
+
+    fn nodes_exhausted(&self) -> bool {
+        self.budget.nodes >= self.options.max_nodes
+    }
+
+    fn warn_nodes(&mut self, span: Range<usize>) {
+        if !self.budget.nodes_reported {
+            self.budget.nodes_reported = true;
+            self.warn(
+                "node-limit-exceeded",
+                format!(
+                    "document has more than {} nodes; the rest is kept as text",
+                    self.options.max_nodes
+                ),
+                span,
+            );
+        }
+    }
+
+    fn check_columns(&mut self, expected: usize, found: usize, span: Range<usize>) {
+        if expected != found {
+            self.warn(
+                "table-column-mismatch",
+                format!("expected {} table columns, found {}", expected, found),
+                span,
+            );
+        }
+    }
+
     fn peek(&self) -> char {
         *self.input.get(self.pos).unwrap_or(&'\0')
     }
@@ -474,15 +1326,18 @@ impl Parser {
         self.pos >= self.input.len()
     }
 
+    /// Input from the current position on.
+    fn rest(&self) -> &[char] {
+        &self.input[self.pos.min(self.input.len())..]
+    }
+
     fn starts_with(&self, s: &str) -> bool {
-        self.input[self.pos..]
-            .iter()
-            .collect::<String>()
-            .starts_with(s)
+        let mut rest = self.rest().iter();
+        s.chars().all(|c| rest.next() == Some(&c))
     }
 
     fn consume(&mut self, n: usize) {
-        self.pos += n;
+        self.pos = (self.pos + n).min(self.input.len());
     }
 
     fn consume_if(&mut self, c: char) {
@@ -503,51 +1358,27 @@ impl Parser {
         }
     }
 
+    /// Skips whole blank lines, returning how many there were.
+    fn skip_blank_lines(&mut self) -> usize {
+        let mut count = 0;
+        while !self.is_eof() && self.peek_line().trim().is_empty() {
+            self.read_line();
+            count += 1;
+        }
+        count
+    }
+
     fn peek_line(&self) -> String {
-        self.input[self.pos..]
-            .iter()
-            .take_while(|&&c| c != '\n')
-            .collect()
+        self.rest().iter().take_while(|&&c| c != '\n').collect()
     }
 
     fn read_line(&mut self) -> String {
         let s = self.peek_line();
-        self.consume(s.len());
+        self.consume(s.chars().count());
         self.consume_if('\n');
         s
     }
 
-    fn slugify(&self, text: &str) -> String {
-        text.to_lowercase()
-            .chars()
-            .map(|c| if c.is_alphanumeric() { c } else { '-' })
-            .collect::<String>()
-            .split('-')
-            .filter(|s| !s.is_empty())
-            .collect::<Vec<_>>()
-            .join("-")
-    }
-
-    fn extract_plain_text(&self, nodes: &[Node]) -> String {
-        let mut text = String::new();
-        for node in nodes {
-            match node {
-                Node::Text { content: t } => text.push_str(t),
-                Node::Bold { children: c }
-                | Node::Italic { children: c }
-                | Node::Underline { children: c }
-                | Node::Strikethrough { children: c } => {
-                    text.push_str(&self.extract_plain_text(c));
-                }
-                Node::InlineCode { content: c } | Node::InlineMath { content: c } => {
-                    text.push_str(c)
-                }
-                _ => {}
-            }
-        }
-        text
-    }
-
     fn skip_whitespace_inline(&mut self) {
         while self.peek() == ' ' || self.peek() == '\t' {
             self.pos += 1;
@@ -555,26 +1386,50 @@ impl Parser {
     }
 
     fn flush_text(&self, text: &mut String, nodes: &mut Vec<Node>) {
-        if !text.is_empty() {
-            nodes.push(Node::Text {
-                content: text.drain(..).collect(),
-            });
+        if text.is_empty() {
+            return;
+        }
+        let text = std::mem::take(text);
+        if self.options.references() {
+            split_references(&text, &self.options, nodes);
+        } else {
+            nodes.push(Node::Text { content: text });
         }
     }
 
-    fn split_table_row(&self, line: &str, aligns: &[TableAlignment]) -> Vec<TableCell> {
-        line.trim()
-            .trim_matches('|')
-            .split('|')
-            .enumerate()
-            .map(|(i, s)| {
-                let mut p = Parser::new(s.trim());
-                TableCell {
-                    children: p.parse_inline_elements('\0'),
-                    alignment: *aligns.get(i).unwrap_or(&TableAlignment::None),
-                }
-            })
-            .collect()
+    fn split_table_row(
+        &mut self,
+        line: &str,
+        aligns: &[TableAlignment],
+        span: Range<usize>,
+    ) -> Vec<TableCell> {
+        let mut cells = Vec::new();
+        let parts = table_cells(line);
+
+        for (i, part) in parts.iter().enumerate() {
+            let alignment = *aligns.get(cells.len()).unwrap_or(&TableAlignment::None);
+            if self.nodes_exhausted() {
+                // Keep the rest of the row as the text of one last cell.
+                self.warn_nodes(span.clone());
+                cells.push(TableCell {
+                    children: vec![Node::Text {
+                        content: parts[i..].join("|"),
+                    }],
+                    alignment,
+                });
+                break;
+            }
+
+            self.budget.nodes += 1;
+            let mut sub_parser = self.nested(part.trim(), span.start);
+            let children = sub_parser.parse_inline_elements('\0');
+            self.absorb(sub_parser, Some(span.clone()));
+            cells.push(TableCell {
+                children,
+                alignment,
+            });
+        }
+        cells
     }
 
     fn parse_alignments(&self, sep: &str) -> Vec<TableAlignment> {
@@ -600,8 +1455,25 @@ impl Parser {
         trimmed.starts_with("- ")
             || trimmed.starts_with("* ")
             || trimmed.starts_with("+ ")
-            || (trimmed.chars().next().map_or(false, |c| c.is_ascii_digit())
-                && trimmed.contains(". "))
+            || trimmed
+                .find(". ")
+                .is_some_and(|dot| dot > 0 && trimmed[..dot].chars().all(|c| c.is_ascii_digit()))
+    }
+
+    /// A line followed directly by a `: ` definition line.
+    fn is_definition_list_start(&self) -> bool {
+        let rest = self.rest();
+        let Some(newline) = rest.iter().position(|&c| c == '\n') else {
+            return false;
+        };
+        let next: String = rest[newline + 1..]
+            .iter()
+            .take_while(|&&c| c != '\n')
+            .collect();
+        let line = self.peek_line();
+        !line.trim().is_empty()
+            && definition_content(&line).is_none()
+            && definition_content(&next).is_some()
     }
 
     fn is_table_start(&self) -> bool {
@@ -623,13 +1495,11 @@ impl Parser {
             }
         }
 
-        println!("Return falce");
         false
     }
 
     fn identify_list_type(&self) -> crate::ast::ListType {
         let line = self.peek_line().trim_start().to_string();
-        println!("Line: {}", line);
         if line.starts_with("- ") || line.starts_with("* ") || line.starts_with("+ ") {
             crate::ast::ListType::Unordered
         } else {
@@ -643,10 +1513,13 @@ impl Parser {
     }
 
     fn extract_checkbox(&self, line: &str) -> (Option<bool>, String) {
-        if line.starts_with("[ ] ") {
-            (Some(false), line[4..].to_string())
-        } else if line.starts_with("[x] ") || line.starts_with("[X] ") {
-            (Some(true), line[4..].to_string())
+        if let Some(rest) = line.strip_prefix("[ ] ") {
+            (Some(false), rest.to_string())
+        } else if let Some(rest) = line
+            .strip_prefix("[x] ")
+            .or_else(|| line.strip_prefix("[X] "))
+        {
+            (Some(true), rest.to_string())
         } else {
             (None, line.to_string())
         }
@@ -655,7 +1528,6 @@ impl Parser {
     fn clean_marker(&self, line: &str, kind: ListType) -> String {
         match kind {
             ListType::Unordered => {
-                println!("Parsed: {}", line);
                 if line.starts_with("- ") || line.starts_with("* ") || line.starts_with("+ ") {
                     line[2..].to_string()
                 } else {
@@ -672,12 +1544,431 @@ impl Parser {
         }
     }
 
+    /// Text up to `stop_char`, which is consumed. An escaped `stop_char`
+    /// is kept with its backslash.
     fn read_until(&mut self, stop_char: char) -> String {
         let mut result = String::new();
         while !self.is_eof() && self.peek() != stop_char {
+            if self.peek() == '\\' && self.rest().get(1) == Some(&stop_char) {
+                result.push(self.next_char());
+            }
             result.push(self.next_char());
         }
         self.consume_if(stop_char);
         result
     }
 }
+
+/// The `[!kind]` line that turns a blockquote into a callout.
+struct CalloutMarker<'a> {
+    kind: String,
+    foldable: Option<Fold>,
+    title: &'a str,
+    /// Quote content after the marker line.
+    body: &'a str,
+}
+
+/// Reads `[!kind]`, an optional `+` or `-`, and an optional title from the
+/// first line of a quote's content.
+fn callout_marker(content: &str) -> Option<CalloutMarker<'_>> {
+    let (line, body) = content.split_once('\n').unwrap_or((content, ""));
+    let (kind, rest) = line.strip_prefix("[!")?.split_once(']')?;
+    let kind = kind.to_lowercase();
+    if !crate::ast::is_callout_kind(&kind) {
+        return None;
+    }
+
+    let (foldable, title) = match rest.chars().next() {
+        Some('+') => (Some(Fold::Open), &rest[1..]),
+        Some('-') => (Some(Fold::Closed), &rest[1..]),
+        _ => (None, rest),
+    };
+    if !title.is_empty() && !title.starts_with(char::is_whitespace) {
+        return None;
+    }
+    Some(CalloutMarker {
+        kind,
+        foldable,
+        title: title.trim(),
+        body,
+    })
+}
+
+/// Text after the marker of a definition line: up to three spaces, `:`,
+/// and a space or tab before the text.
+pub(crate) fn definition_content(line: &str) -> Option<&str> {
+    let trimmed = line.trim_start_matches(' ');
+    if line.len() - trimmed.len() > 3 {
+        return None;
+    }
+    let rest = trimmed.strip_prefix(':')?;
+    (rest.is_empty() || rest.starts_with([' ', '\t'])).then(|| rest.trim_start())
+}
+
+/// Removes a tab or up to four spaces of indentation.
+fn strip_indent(line: &str) -> &str {
+    if let Some(rest) = line.strip_prefix('\t') {
+        return rest;
+    }
+    let spaces = line.chars().take(4).take_while(|&c| c == ' ').count();
+    &line[spaces..]
+}
+
+/// `name[label]{attributes}` as written after a directive's colons.
+struct Directive {
+    name: String,
+    /// Offset of the label text from the name, and the text.
+    label: Option<(usize, String)>,
+    attributes: HashMap<String, String>,
+    /// Characters taken up by the directive.
+    len: usize,
+}
+
+/// Reads a directive from the start of `chars`. It needs a label, attributes
+/// or both, so a colon before a plain word stays text. `label_closing`
+/// finds the `]` matching a `[` at an index of `chars`, relative to it.
+fn read_directive(
+    chars: &[char],
+    mut label_closing: impl FnMut(usize) -> Option<usize>,
+) -> Option<Directive> {
+    let mut len = chars
+        .iter()
+        .take_while(|&&c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        .count();
+    let name: String = chars[..len].iter().collect();
+    if !crate::ast::is_directive_name(&name) {
+        return None;
+    }
+
+    let mut label = None;
+    if chars.get(len) == Some(&'[')
+        && let Some(close) = label_closing(len)
+    {
+        label = Some((len + 1, chars[len + 1..len + close].iter().collect()));
+        len += close + 1;
+    }
+    let mut attributes = None;
+    if let Some(close) = closing(&chars[len..], '{', '}') {
+        let inner: String = chars[len + 1..len + close].iter().collect();
+        attributes = Some(parse_attributes(&inner, true));
+        len += close + 1;
+    }
+    if label.is_none() && attributes.is_none() {
+        return None;
+    }
+    Some(Directive {
+        name,
+        label,
+        attributes: attributes.unwrap_or_default(),
+        len,
+    })
+}
+
+/// Pushes `text` as text nodes around the mentions, issue and commit
+/// references and hashtags that `options` enable. A reference cannot
+/// follow a letter, digit or one of `_-./@#&`.
+fn split_references(text: &str, options: &ParseOptions, nodes: &mut Vec<Node>) {
+    let chars: Vec<char> = text.chars().collect();
+    let mut plain = String::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let starts =
+            i == 0 || !(chars[i - 1].is_alphanumeric() || "_-./@#&".contains(chars[i - 1]));
+        if starts && let Some((node, len)) = read_reference(&chars[i..], options) {
+            if !plain.is_empty() {
+                nodes.push(Node::Text {
+                    content: std::mem::take(&mut plain),
+                });
+            }
+            nodes.push(node);
+            i += len;
+            continue;
+        }
+        plain.push(chars[i]);
+        i += 1;
+    }
+    if !plain.is_empty() {
+        nodes.push(Node::Text { content: plain });
+    }
+}
+
+/// The enabled reference `chars` starts with and its length. It must not
+/// run on into a letter, digit or one of `_-/@#`.
+fn read_reference(chars: &[char], options: &ParseOptions) -> Option<(Node, usize)> {
+    let run = |from: usize, allowed: fn(char) -> bool| {
+        from + chars[from..].iter().take_while(|&&c| allowed(c)).count()
+    };
+    let text = |range: Range<usize>| chars[range].iter().collect::<String>();
+    let ends = |len: usize| {
+        chars
+            .get(len)
+            .is_none_or(|&c| !(c.is_alphanumeric() || "_-/@#".contains(c)))
+    };
+
+    let (node, len) = match chars[0] {
+        '@' if options.mentions => {
+            let len = run(1, |c| c.is_ascii_alphanumeric() || c == '-');
+            let username = text(1..len);
+            is_username(&username).then_some((Node::Mention { username }, len))?
+        }
+        '#' => {
+            let len = run(1, |c| c.is_alphanumeric() || c == '_' || c == '-');
+            let word = text(1..len);
+            if options.issues
+                && !word.starts_with('0')
+                && word.chars().all(|c| c.is_ascii_digit())
+                && let Ok(number) = word.parse()
+            {
+                (
+                    Node::IssueReference {
+                        repository: None,
+                        number,
+                    },
+                    len,
+                )
+            } else if options.hashtags && is_hashtag(&word) {
+                (Node::Hashtag { tag: word }, len)
+            } else {
+                return None;
+            }
+        }
+        _ => {
+            let name = run(0, |c| c.is_ascii_alphanumeric() || "-._/".contains(c));
+            let repository = Some(text(0..name)).filter(|name| is_repository(name));
+            match (chars.get(name), repository) {
+                (Some('#'), Some(repository)) if options.issues => {
+                    let len = run(name + 1, |c| c.is_ascii_digit());
+                    let number = text(name + 1..len);
+                    if number.starts_with('0') {
+                        return None;
+                    }
+                    let node = Node::IssueReference {
+                        repository: Some(repository),
+                        number: number.parse().ok()?,
+                    };
+                    (node, len)
+                }
+                (Some('@'), Some(repository)) if options.commits => {
+                    let len = run(name + 1, |c| c.is_ascii_hexdigit());
+                    let sha = text(name + 1..len);
+                    is_commit_sha(&sha).then_some((
+                        Node::CommitReference {
+                            repository: Some(repository),
+                            sha,
+                        },
+                        len,
+                    ))?
+                }
+                _ if options.commits => {
+                    let len = run(0, |c| c.is_ascii_hexdigit());
+                    let sha = text(0..len);
+                    is_commit_sha(&sha).then_some((
+                        Node::CommitReference {
+                            repository: None,
+                            sha,
+                        },
+                        len,
+                    ))?
+                }
+                _ => return None,
+            }
+        }
+    };
+    ends(len).then_some((node, len))
+}
+
+/// Plain text of a heading without its links, which its id is made from.
+fn heading_text(nodes: &[Node]) -> String {
+    struct HeadingText(String);
+
+    impl Visitor for HeadingText {
+        fn visit_node(&mut self, node: &Node) {
+            match node {
+                Node::Link { .. } => {}
+                Node::Bold { .. }
+                | Node::Italic { .. }
+                | Node::Strikethrough { .. }
+                | Node::Underline { .. }
+                | Node::Highlight { .. }
+                | Node::Superscript { .. }
+                | Node::Subscript { .. }
+                | Node::Inserted { .. } => walk_node(self, node),
+                _ => self.0.push_str(&plain_text(std::slice::from_ref(node))),
+            }
+        }
+    }
+
+    let mut text = HeadingText(String::new());
+    walk_nodes(&mut text, nodes);
+    text.0
+}
+
+/// Heading id for heading text.
+pub(crate) fn slugify(text: &str) -> String {
+    text.to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '-' })
+        .collect::<String>()
+        .split('-')
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Whether the character at `i` follows an odd run of backslashes.
+fn is_escaped(chars: &[char], i: usize) -> bool {
+    chars[..i].iter().rev().take_while(|&&c| c == '\\').count() % 2 == 1
+}
+
+/// Cells of a table row, split at the pipes that are not escaped. As in
+/// GFM, `\|` turns into `|` before the cell is parsed, even in code.
+fn table_cells(line: &str) -> Vec<String> {
+    let chars: Vec<char> = line.trim().trim_start_matches('|').chars().collect();
+    let mut end = chars.len();
+    while end > 0 && chars[end - 1] == '|' && !is_escaped(&chars, end - 1) {
+        end -= 1;
+    }
+
+    let mut cells = vec![String::new()];
+    for (i, &c) in chars[..end].iter().enumerate() {
+        let cell = cells.last_mut().unwrap();
+        if c != '|' {
+            cell.push(c);
+        } else if is_escaped(&chars, i) {
+            cell.pop();
+            cell.push(c);
+        } else {
+            cells.push(String::new());
+        }
+    }
+    cells
+}
+
+/// Index of the `close` matching the `open` that `chars` starts with,
+/// within the line. Brackets nest; braces skip quoted values and do not
+/// nest, so a search ends at the next unquoted `{`.
+fn closing(chars: &[char], open: char, close: char) -> Option<usize> {
+    if chars.first() != Some(&open) {
+        return None;
+    }
+    let mut depth = 0;
+    let mut quote = None;
+    for (i, &c) in chars.iter().enumerate() {
+        match c {
+            '\n' => return None,
+            _ if quote == Some(c) => quote = None,
+            _ if quote.is_some() => {}
+            '"' | '\'' if open == '{' && !is_escaped(chars, i) => quote = Some(c),
+            _ if (c == open || c == close) && is_escaped(chars, i) => {}
+            '{' if open == '{' && depth > 0 => return None,
+            _ if c == open => depth += 1,
+            _ if c == close => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Parses `#id .class key=value key="quoted value"`. Classes are joined
+/// into `class`. Without `bare`, words that are not `key=value` pairs are
+/// skipped rather than read as empty attributes.
+fn parse_attributes(text: &str, bare: bool) -> HashMap<String, String> {
+    let mut attributes = HashMap::new();
+    let mut classes: Vec<String> = Vec::new();
+    let mut chars = text.chars().peekable();
+
+    loop {
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+        let Some(&first) = chars.peek() else {
+            break;
+        };
+        if first == '#' || first == '.' {
+            chars.next();
+        }
+        let mut key = String::new();
+        while let Some(c) = chars.next_if(|&c| !c.is_whitespace() && c != '=') {
+            key.push(c);
+        }
+
+        if chars.next_if_eq(&'=').is_none() {
+            match first {
+                '#' => {
+                    attributes.insert("id".to_string(), key);
+                }
+                '.' => classes.push(key),
+                _ if bare && !key.is_empty() => {
+                    attributes.insert(key, String::new());
+                }
+                _ => {}
+            }
+            continue;
+        }
+
+        let mut value = String::new();
+        match chars.next_if(|&c| c == '"' || c == '\'') {
+            Some(quote) => value.extend(chars.by_ref().take_while(|&c| c != quote)),
+            None => {
+                while let Some(c) = chars.next_if(|c| !c.is_whitespace()) {
+                    value.push(c);
+                }
+            }
+        }
+        if key == "class" {
+            classes.push(value);
+        } else if !key.is_empty() {
+            attributes.insert(key, value);
+        }
+    }
+
+    if !classes.is_empty() {
+        attributes.insert("class".to_string(), classes.join(" "));
+    }
+    attributes
+}
+
+/// Decodes front matter into a JSON-like map; an empty block is an empty map
+/// and a value other than a table of keys is `None`.
+fn decode_front_matter(
+    format: FrontMatterFormat,
+    raw: &str,
+) -> Result<Option<Map<String, Value>>, String> {
+    match format {
+        FrontMatterFormat::Yaml => match serde_yaml::from_str::<Value>(raw) {
+            Ok(Value::Object(metadata)) => Ok(Some(metadata)),
+            Ok(_) => Ok(None),
+            Err(err) => Err(format!("invalid YAML front matter: {}", err)),
+        },
+        FrontMatterFormat::Toml => match raw.parse::<toml::Table>() {
+            Ok(table) => Ok(Some(
+                table
+                    .into_iter()
+                    .map(|(key, value)| (key, toml_to_json(value)))
+                    .collect(),
+            )),
+            Err(err) => Err(format!("invalid TOML front matter: {}", err.message())),
+        },
+    }
+}
+
+/// TOML dates and times become strings, as JSON has no such type.
+fn toml_to_json(value: toml::Value) -> Value {
+    match value {
+        toml::Value::String(value) => Value::String(value),
+        toml::Value::Integer(value) => Value::from(value),
+        toml::Value::Float(value) => Value::from(value),
+        toml::Value::Boolean(value) => Value::Bool(value),
+        toml::Value::Datetime(value) => Value::String(value.to_string()),
+        toml::Value::Array(values) => values.into_iter().map(toml_to_json).collect(),
+        toml::Value::Table(table) => Value::Object(
+            table
+                .into_iter()
+                .map(|(key, value)| (key, toml_to_json(value)))
+                .collect(),
+        ),
+    }
+}
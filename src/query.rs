@@ -0,0 +1,540 @@
+//! CSS-like selectors over the AST.
+//!
+//! Type names are the `type` tags of serialized nodes (`heading`,
+//! `codeBlock`, ...) plus `listItem` and `tableCell`. Compound selectors
+//! accept `[attr]` and `[attr=value]` filters and are combined with
+//! whitespace (descendant) or `>` (child); `,` separates alternatives.
+//!
+//! ```
+//! use mdkit::parser::Parser;
+//! use mdkit::query::query;
+//!
+//! let nodes = Parser::new("- [ ] Write docs\n- [x] Ship").parse_document();
+//! let open = query(&nodes, "list > listItem[checked=false]").unwrap();
+//! assert_eq!(open.len(), 1);
+//! assert_eq!(open[0].path, vec![0, 0]);
+//! ```
+
+use serde::Serialize;
+
+use crate::ast::{Fold, FrontMatterFormat, ListItem, ListType, Node, TableAlignment, TableCell};
+
+/// Borrowed view of anything a selector can match.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum NodeRef<'a> {
+    Node(&'a Node),
+    ListItem(&'a ListItem),
+    TableCell(&'a TableCell),
+}
+
+impl<'a> NodeRef<'a> {
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            NodeRef::ListItem(_) => "listItem",
+            NodeRef::TableCell(_) => "tableCell",
+            NodeRef::Node(node) => match node {
+                Node::FrontMatter { .. } => "frontMatter",
+                Node::Heading { .. } => "heading",
+                Node::HorizontalRule => "horizontalRule",
+                Node::Paragraph { .. } => "paragraph",
+                Node::LineBreak => "lineBreak",
+                Node::Link { .. } => "link",
+                Node::WikiLink { .. } => "wikiLink",
+                Node::Mention { .. } => "mention",
+                Node::IssueReference { .. } => "issueReference",
+                Node::CommitReference { .. } => "commitReference",
+                Node::Hashtag { .. } => "hashtag",
+                Node::Image { .. } => "image",
+                Node::Bold { .. } => "bold",
+                Node::Italic { .. } => "italic",
+                Node::Strikethrough { .. } => "strikethrough",
+                Node::Underline { .. } => "underline",
+                Node::Highlight { .. } => "highlight",
+                Node::Superscript { .. } => "superscript",
+                Node::Subscript { .. } => "subscript",
+                Node::Inserted { .. } => "inserted",
+                Node::Text { .. } => "text",
+                Node::InlineDirective { .. } => "inlineDirective",
+                Node::Emoji { .. } => "emoji",
+                Node::InlineMath { .. } => "inlineMath",
+                Node::BlockMath { .. } => "blockMath",
+                Node::InlineCode { .. } => "inlineCode",
+                Node::CodeBlock { .. } => "codeBlock",
+                Node::BlockQuote { .. } => "blockQuote",
+                Node::Callout { .. } => "callout",
+                Node::List { .. } => "list",
+                Node::DefinitionList { .. } => "definitionList",
+                Node::DefinitionTerm { .. } => "definitionTerm",
+                Node::DefinitionDescription { .. } => "definitionDescription",
+                Node::Table { .. } => "table",
+                Node::LeafDirective { .. } => "leafDirective",
+                Node::CustomBlock { .. } => "customBlock",
+            },
+        }
+    }
+
+    /// Children in document order; lists yield their items and tables
+    /// yield header cells followed by body cells.
+    pub fn children(&self) -> Vec<NodeRef<'a>> {
+        let nodes = |nodes: &'a [Node]| nodes.iter().map(NodeRef::Node).collect();
+
+        match *self {
+            NodeRef::ListItem(item) => item
+                .content
+                .iter()
+                .chain(&item.children)
+                .map(NodeRef::Node)
+                .collect(),
+            NodeRef::TableCell(cell) => nodes(&cell.children),
+            NodeRef::Node(node) => match node {
+                Node::Heading { children, .. }
+                | Node::Paragraph { children }
+                | Node::Bold { children }
+                | Node::Italic { children }
+                | Node::Strikethrough { children }
+                | Node::Underline { children }
+                | Node::Highlight { children }
+                | Node::Superscript { children }
+                | Node::Subscript { children }
+                | Node::Inserted { children }
+                | Node::BlockQuote { children }
+                | Node::DefinitionList { children }
+                | Node::DefinitionTerm { children }
+                | Node::DefinitionDescription { children } => nodes(children),
+                Node::Link { text, .. } => nodes(text),
+                Node::InlineDirective { label, .. } | Node::LeafDirective { label, .. } => {
+                    nodes(label)
+                }
+                Node::CustomBlock {
+                    label, children, ..
+                } => label.iter().chain(children).map(NodeRef::Node).collect(),
+                Node::Callout {
+                    title, children, ..
+                } => title.iter().chain(children).map(NodeRef::Node).collect(),
+                Node::List { items, .. } => items.iter().map(NodeRef::ListItem).collect(),
+                Node::Table { header, rows } => header
+                    .iter()
+                    .chain(rows.iter().flatten())
+                    .map(NodeRef::TableCell)
+                    .collect(),
+                _ => Vec::new(),
+            },
+        }
+    }
+
+    /// String value of a scalar field, a directive / link attribute, or a
+    /// front matter key.
+    pub fn attribute(&self, name: &str) -> Option<String> {
+        match *self {
+            NodeRef::ListItem(item) => match name {
+                "checked" => item.checked.map(|c| c.to_string()),
+                _ => None,
+            },
+            NodeRef::TableCell(cell) => match name {
+                "alignment" => Some(alignment_name(cell.alignment).to_string()),
+                _ => None,
+            },
+            NodeRef::Node(node) => match (node, name) {
+                (Node::Heading { level, .. }, "level") => Some(level.to_string()),
+                (Node::Heading { id, .. }, "id") => Some(id.clone()),
+                (Node::Link { url, .. } | Node::Image { url, .. }, "url") => Some(url.clone()),
+                (Node::Image { alt, .. }, "alt") => Some(alt.clone()),
+                (Node::Link { attributes, .. } | Node::Image { attributes, .. }, _) => {
+                    attributes.get(name).cloned()
+                }
+                (
+                    Node::Text { content }
+                    | Node::InlineMath { content }
+                    | Node::BlockMath { content }
+                    | Node::InlineCode { content },
+                    "content",
+                ) => Some(content.clone()),
+                (Node::Mention { username }, "username") => Some(username.clone()),
+                (
+                    Node::IssueReference { repository, .. }
+                    | Node::CommitReference { repository, .. },
+                    "repository",
+                ) => repository.clone(),
+                (Node::IssueReference { number, .. }, "number") => Some(number.to_string()),
+                (Node::CommitReference { sha, .. }, "sha") => Some(sha.clone()),
+                (Node::Hashtag { tag }, "tag") => Some(tag.clone()),
+                (Node::WikiLink { target, .. }, "target") => Some(target.clone()),
+                (Node::WikiLink { heading, .. }, "heading") => heading.clone(),
+                (Node::WikiLink { alias, .. }, "alias") => alias.clone(),
+                (Node::Emoji { shortcode, .. }, "shortcode") => Some(shortcode.clone()),
+                (Node::Emoji { unicode, .. }, "unicode") => Some(unicode.clone()),
+                (Node::CodeBlock { lang, .. }, "lang") => Some(lang.clone()),
+                (Node::CodeBlock { filename, .. }, "filename") => filename.clone(),
+                (Node::CodeBlock { code, .. }, "code") => Some(code.clone()),
+                (Node::List { kind, .. }, "kind") => Some(
+                    match kind {
+                        ListType::Ordered => "ordered",
+                        ListType::Unordered => "unordered",
+                    }
+                    .to_string(),
+                ),
+                (Node::FrontMatter { format, .. }, "format") => Some(
+                    match format {
+                        FrontMatterFormat::Yaml => "yaml",
+                        FrontMatterFormat::Toml => "toml",
+                    }
+                    .to_string(),
+                ),
+                (Node::FrontMatter { metadata, .. }, _) => match metadata.get(name)? {
+                    serde_json::Value::String(value) => Some(value.clone()),
+                    value => Some(value.to_string()),
+                },
+                (Node::Callout { kind, .. }, "kind") => Some(kind.clone()),
+                (Node::Callout { foldable, .. }, "foldable") => foldable.map(|fold| {
+                    match fold {
+                        Fold::Open => "open",
+                        Fold::Closed => "closed",
+                    }
+                    .to_string()
+                }),
+                (
+                    Node::CustomBlock { name, .. }
+                    | Node::InlineDirective { name, .. }
+                    | Node::LeafDirective { name, .. },
+                    "name",
+                ) => Some(name.clone()),
+                (
+                    Node::CustomBlock { attributes, .. }
+                    | Node::InlineDirective { attributes, .. }
+                    | Node::LeafDirective { attributes, .. },
+                    _,
+                ) => attributes.get(name).cloned(),
+                _ => None,
+            },
+        }
+    }
+}
+
+fn alignment_name(alignment: TableAlignment) -> &'static str {
+    match alignment {
+        TableAlignment::Left => "left",
+        TableAlignment::Center => "center",
+        TableAlignment::Right => "right",
+        TableAlignment::None => "none",
+    }
+}
+
+/// A matched node and its index path from the document root.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct QueryMatch<'a> {
+    pub path: Vec<usize>,
+    pub node: NodeRef<'a>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SelectorError {
+    pub position: usize,
+    pub message: String,
+}
+
+impl std::fmt::Display for SelectorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "selector error at {}: {}", self.position, self.message)
+    }
+}
+
+impl std::error::Error for SelectorError {}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Combinator {
+    Descendant,
+    Child,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct AttributeFilter {
+    name: String,
+    value: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Compound {
+    type_name: Option<String>,
+    filters: Vec<AttributeFilter>,
+}
+
+impl Compound {
+    fn matches(&self, node: &NodeRef) -> bool {
+        if let Some(type_name) = &self.type_name
+            && type_name != node.type_name()
+        {
+            return false;
+        }
+
+        self.filters.iter().all(
+            |filter| match (node.attribute(&filter.name), &filter.value) {
+                (Some(actual), Some(expected)) => &actual == expected,
+                (Some(_), None) => true,
+                (None, _) => false,
+            },
+        )
+    }
+}
+
+/// A parsed selector, reusable across documents.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Selector {
+    // Each alternative is a chain of compounds and the combinators joining
+    // them: combinator `i` sits between compound `i` and compound `i + 1`.
+    alternatives: Vec<(Vec<Compound>, Vec<Combinator>)>,
+}
+
+impl Selector {
+    pub fn parse(selector: &str) -> Result<Self, SelectorError> {
+        SelectorParser {
+            input: selector.chars().collect(),
+            pos: 0,
+        }
+        .parse()
+    }
+
+    pub fn query<'a>(&self, nodes: &'a [Node]) -> Vec<QueryMatch<'a>> {
+        let mut matches = Vec::new();
+        let mut ancestors = Vec::new();
+        let mut path = Vec::new();
+
+        for (i, node) in nodes.iter().enumerate() {
+            path.push(i);
+            self.collect(NodeRef::Node(node), &mut ancestors, &mut path, &mut matches);
+            path.pop();
+        }
+        matches
+    }
+
+    pub fn matches(&self, node: NodeRef, ancestors: &[NodeRef]) -> bool {
+        self.alternatives
+            .iter()
+            .any(|(compounds, links)| match_chain(compounds, links, node, ancestors))
+    }
+
+    fn collect<'a>(
+        &self,
+        node: NodeRef<'a>,
+        ancestors: &mut Vec<NodeRef<'a>>,
+        path: &mut Vec<usize>,
+        matches: &mut Vec<QueryMatch<'a>>,
+    ) {
+        if self.matches(node, ancestors) {
+            matches.push(QueryMatch {
+                path: path.clone(),
+                node,
+            });
+        }
+
+        ancestors.push(node);
+        for (i, child) in node.children().into_iter().enumerate() {
+            path.push(i);
+            self.collect(child, ancestors, path, matches);
+            path.pop();
+        }
+        ancestors.pop();
+    }
+}
+
+fn match_chain(
+    compounds: &[Compound],
+    links: &[Combinator],
+    node: NodeRef,
+    ancestors: &[NodeRef],
+) -> bool {
+    let (last, rest) = match compounds.split_last() {
+        Some(split) => split,
+        None => return false,
+    };
+    if !last.matches(&node) {
+        return false;
+    }
+    if rest.is_empty() {
+        return true;
+    }
+
+    let link = &links[rest.len() - 1];
+    let links = &links[..rest.len() - 1];
+
+    match link {
+        Combinator::Child => match ancestors.split_last() {
+            Some((parent, above)) => match_chain(rest, links, *parent, above),
+            None => false,
+        },
+        Combinator::Descendant => (0..ancestors.len())
+            .rev()
+            .any(|i| match_chain(rest, links, ancestors[i], &ancestors[..i])),
+    }
+}
+
+/// Runs `selector` over `nodes` and returns every match in document order.
+pub fn query<'a>(nodes: &'a [Node], selector: &str) -> Result<Vec<QueryMatch<'a>>, SelectorError> {
+    Ok(Selector::parse(selector)?.query(nodes))
+}
+
+/// Resolves a path returned by [`query`] back to its node.
+pub fn node_at<'a>(nodes: &'a [Node], path: &[usize]) -> Option<NodeRef<'a>> {
+    let (first, rest) = path.split_first()?;
+    let mut current = NodeRef::Node(nodes.get(*first)?);
+    for &i in rest {
+        current = *current.children().get(i)?;
+    }
+    Some(current)
+}
+
+struct SelectorParser {
+    input: Vec<char>,
+    pos: usize,
+}
+
+impl SelectorParser {
+    fn parse(&mut self) -> Result<Selector, SelectorError> {
+        let mut alternatives = Vec::new();
+
+        loop {
+            alternatives.push(self.parse_chain()?);
+            self.skip_whitespace();
+            if self.peek() == Some(',') {
+                self.pos += 1;
+            } else if self.peek().is_none() {
+                break;
+            } else {
+                return Err(self.error("unexpected character"));
+            }
+        }
+
+        Ok(Selector { alternatives })
+    }
+
+    fn parse_chain(&mut self) -> Result<(Vec<Compound>, Vec<Combinator>), SelectorError> {
+        let mut compounds = Vec::new();
+        let mut links = Vec::new();
+
+        self.skip_whitespace();
+        compounds.push(self.parse_compound()?);
+
+        loop {
+            let had_space = self.skip_whitespace();
+            match self.peek() {
+                Some('>') => {
+                    self.pos += 1;
+                    self.skip_whitespace();
+                    links.push(Combinator::Child);
+                }
+                Some(',') | None => break,
+                Some(_) if had_space => links.push(Combinator::Descendant),
+                Some(_) => return Err(self.error("unexpected character")),
+            }
+            compounds.push(self.parse_compound()?);
+        }
+
+        Ok((compounds, links))
+    }
+
+    fn parse_compound(&mut self) -> Result<Compound, SelectorError> {
+        let type_name = if self.peek() == Some('*') {
+            self.pos += 1;
+            None
+        } else {
+            let name = self.read_identifier();
+            if name.is_empty() && self.peek() != Some('[') {
+                return Err(self.error("expected a node type"));
+            }
+            (!name.is_empty()).then_some(name)
+        };
+
+        let mut filters = Vec::new();
+        while self.peek() == Some('[') {
+            self.pos += 1;
+            self.skip_whitespace();
+            let name = self.read_identifier();
+            if name.is_empty() {
+                return Err(self.error("expected an attribute name"));
+            }
+            self.skip_whitespace();
+
+            let value = if self.peek() == Some('=') {
+                self.pos += 1;
+                self.skip_whitespace();
+                Some(self.read_value()?)
+            } else {
+                None
+            };
+
+            self.skip_whitespace();
+            if self.peek() != Some(']') {
+                return Err(self.error("expected `]`"));
+            }
+            self.pos += 1;
+            filters.push(AttributeFilter { name, value });
+        }
+
+        Ok(Compound { type_name, filters })
+    }
+
+    fn read_identifier(&mut self) -> String {
+        let mut ident = String::new();
+        while let Some(c) = self.peek() {
+            if c.is_alphanumeric() || c == '-' || c == '_' {
+                ident.push(c);
+                self.pos += 1;
+            } else {
+                break;
+            }
+        }
+        ident
+    }
+
+    fn read_value(&mut self) -> Result<String, SelectorError> {
+        match self.peek() {
+            Some(quote @ ('"' | '\'')) => {
+                self.pos += 1;
+                let mut value = String::new();
+                loop {
+                    match self.peek() {
+                        Some(c) if c == quote => {
+                            self.pos += 1;
+                            return Ok(value);
+                        }
+                        Some(c) => {
+                            value.push(c);
+                            self.pos += 1;
+                        }
+                        None => return Err(self.error("unterminated string")),
+                    }
+                }
+            }
+            _ => {
+                let mut value = String::new();
+                while let Some(c) = self.peek() {
+                    if c == ']' || c.is_whitespace() {
+                        break;
+                    }
+                    value.push(c);
+                    self.pos += 1;
+                }
+                Ok(value)
+            }
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.input.get(self.pos).copied()
+    }
+
+    fn skip_whitespace(&mut self) -> bool {
+        let start = self.pos;
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.pos += 1;
+        }
+        self.pos > start
+    }
+
+    fn error(&self, message: &str) -> SelectorError {
+        SelectorError {
+            position: self.pos,
+            message: message.to_string(),
+        }
+    }
+}
@@ -1,6 +1,172 @@
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use serde::{Deserialize, Serialize};
+use serde_wasm_bindgen::from_value;
+use wasm_bindgen::prelude::*;
+
+use crate::ast::Fold;
 use crate::ast::ListType;
 use crate::ast::Node;
 use crate::ast::TableAlignment;
+use crate::ast::{reference_text, wiki_link_text};
+use crate::emoji;
+use crate::parser::{Parser, slugify};
+use crate::wiki;
+
+pub mod directives;
+
+/// Which of the three directive forms a [`Directive`] was written in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DirectiveKind {
+    /// `:name[label]{attributes}`
+    Inline,
+    /// `::name[label]{attributes}`
+    Leaf,
+    /// `:::name[label]{attributes}` with content, a [`Node::CustomBlock`].
+    Container,
+}
+
+/// A directive node as passed to a [`DirectiveHandler`].
+#[derive(Debug, Clone, Copy)]
+pub struct Directive<'a> {
+    pub kind: DirectiveKind,
+    pub name: &'a str,
+    pub label: &'a [Node],
+    pub attributes: &'a HashMap<String, String>,
+    /// Content of a container directive; empty for the other kinds.
+    pub children: &'a [Node],
+}
+
+impl Directive<'_> {
+    /// The node the directive was read from.
+    pub fn to_node(&self) -> Node {
+        let name = self.name.to_string();
+        let label = self.label.to_vec();
+        let attributes = self.attributes.clone();
+        match self.kind {
+            DirectiveKind::Inline => Node::InlineDirective {
+                name,
+                label,
+                attributes,
+            },
+            DirectiveKind::Leaf => Node::LeafDirective {
+                name,
+                label,
+                attributes,
+            },
+            DirectiveKind::Container => Node::CustomBlock {
+                name,
+                label,
+                attributes,
+                children: self.children.to_vec(),
+            },
+        }
+    }
+}
+
+/// Renders directives of one name. Returning `None` falls back to the
+/// default markup, so a handler can decline forms it does not support.
+pub trait DirectiveHandler {
+    fn render(&self, directive: &Directive, renderer: &Renderer) -> Option<String>;
+}
+
+impl<F> DirectiveHandler for F
+where
+    F: Fn(&Directive, &Renderer) -> Option<String>,
+{
+    fn render(&self, directive: &Directive, renderer: &Renderer) -> Option<String> {
+        self(directive, renderer)
+    }
+}
+
+/// Maps wiki link targets to URLs; `None` marks the page as missing.
+pub trait WikiLinkResolver {
+    fn resolve(&self, target: &str) -> Option<String>;
+}
+
+impl<F> WikiLinkResolver for F
+where
+    F: Fn(&str) -> Option<String>,
+{
+    fn resolve(&self, target: &str) -> Option<String> {
+        self(target)
+    }
+}
+
+/// Maps mention, issue reference, commit reference and hashtag nodes to
+/// URLs; `None` leaves the reference unlinked.
+pub trait ReferenceResolver {
+    fn resolve(&self, reference: &Node) -> Option<String>;
+}
+
+impl<F> ReferenceResolver for F
+where
+    F: Fn(&Node) -> Option<String>,
+{
+    fn resolve(&self, reference: &Node) -> Option<String> {
+        self(reference)
+    }
+}
+
+/// URL templates for references. `{username}`, `{repository}`, `{number}`,
+/// `{sha}` and `{tag}` are filled in; references without a repository use
+/// `repository`, and are left unlinked when their template needs one and
+/// there is none.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ReferenceTemplates {
+    pub repository: Option<String>,
+    pub mention: Option<String>,
+    pub issue: Option<String>,
+    pub commit: Option<String>,
+    pub hashtag: Option<String>,
+}
+
+impl ReferenceTemplates {
+    /// GitHub's URLs for users, issues and commits of `repository`.
+    pub fn github(repository: &str) -> Self {
+        Self {
+            repository: Some(repository.to_string()),
+            mention: Some("https://github.com/{username}".to_string()),
+            issue: Some("https://github.com/{repository}/issues/{number}".to_string()),
+            commit: Some("https://github.com/{repository}/commit/{sha}".to_string()),
+            hashtag: None,
+        }
+    }
+}
+
+impl ReferenceResolver for ReferenceTemplates {
+    fn resolve(&self, reference: &Node) -> Option<String> {
+        let (template, repository, values) = match reference {
+            Node::Mention { username } => {
+                (&self.mention, None, vec![("{username}", username.clone())])
+            }
+            Node::IssueReference { repository, number } => (
+                &self.issue,
+                repository.as_ref(),
+                vec![("{number}", number.to_string())],
+            ),
+            Node::CommitReference { repository, sha } => (
+                &self.commit,
+                repository.as_ref(),
+                vec![("{sha}", sha.clone())],
+            ),
+            Node::Hashtag { tag } => (&self.hashtag, None, vec![("{tag}", tag.clone())]),
+            _ => return None,
+        };
+        let mut url = template.clone()?;
+        if url.contains("{repository}") {
+            let repository = repository.or(self.repository.as_ref())?;
+            url = url.replace("{repository}", repository);
+        }
+        for (placeholder, value) in values {
+            url = url.replace(placeholder, &value);
+        }
+        Some(url)
+    }
+}
 
 fn render_alignment(align: TableAlignment) -> &'static str {
     match align {
@@ -11,186 +177,776 @@ fn render_alignment(align: TableAlignment) -> &'static str {
     }
 }
 
+fn render_attributes(attributes: &HashMap<String, String>) -> String {
+    let mut keys: Vec<&String> = attributes.keys().collect();
+    keys.sort();
+
+    keys.into_iter()
+        .map(|key| format!(" {}='{}'", key, attributes[key]))
+        .collect()
+}
+
+/// `value` escaped for a quoted HTML attribute.
+pub(crate) fn escape_attribute(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '\'' => escaped.push_str("&#39;"),
+            '"' => escaped.push_str("&quot;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Directive attributes as HTML: the name and any `.class` names form the
+/// class, `#id` becomes the id, and everything else a `data-` attribute.
+fn render_directive_attributes(name: &str, attributes: &HashMap<String, String>) -> String {
+    let mut class = name.to_string();
+    if let Some(extra) = attributes.get("class") {
+        class.push(' ');
+        class.push_str(extra);
+    }
+    let mut html = format!(" class='{}'", escape_attribute(&class));
+    if let Some(id) = attributes.get("id") {
+        html.push_str(&format!(" id='{}'", escape_attribute(id)));
+    }
+
+    let mut keys: Vec<&String> = attributes
+        .keys()
+        .filter(|key| *key != "class" && *key != "id")
+        .collect();
+    keys.sort();
+    for key in keys {
+        html.push_str(&format!(
+            " data-{}='{}'",
+            key,
+            escape_attribute(&attributes[key])
+        ));
+    }
+    html
+}
+
+/// Icon for GitHub's alert kinds and common Obsidian callout kinds and
+/// aliases; other kinds use the note icon.
+fn callout_icon(kind: &str) -> &'static str {
+    match kind {
+        "tip" | "hint" => "💡",
+        "important" => "❗",
+        "warning" | "attention" => "⚠️",
+        "caution" | "danger" | "error" => "🛑",
+        "abstract" | "summary" | "tldr" => "📋",
+        "todo" => "☑️",
+        "success" | "check" | "done" => "✅",
+        "question" | "help" | "faq" => "❓",
+        "failure" | "fail" | "missing" => "❌",
+        "bug" => "🐛",
+        "example" => "📑",
+        "quote" | "cite" => "💬",
+        _ => "ℹ️",
+    }
+}
+
+/// Renders nodes with the default markup for every directive.
 pub fn render(nodes: &[Node]) -> String {
-    let mut html = String::new();
-    for node in nodes {
-        match node {
-            // Heading rendering
-            Node::Heading {
-                level,
-                id,
-                children,
-            } => {
-                html.push_str(&format!(
-                    "<h{} id=\"{}\">{}</h{}>\n",
-                    level,
-                    id,
-                    render(children),
-                    level
-                ));
-            }
+    Renderer::new().render(nodes)
+}
+
+/// HTML renderer with handlers for named directives and emoji output
+/// options.
+///
+/// ```
+/// use mdkit::parser::Parser;
+/// use mdkit::renderer::{Directive, Renderer};
+///
+/// let mut renderer = Renderer::new();
+/// renderer.builtin_directives().directive(
+///     "badge",
+///     |directive: &Directive, renderer: &Renderer| {
+///         Some(format!("<span class='badge'>{}</span>", renderer.render(directive.label)))
+///     },
+/// );
+///
+/// let nodes = Parser::new("Status: :badge[beta]").parse_document();
+/// assert_eq!(renderer.render(&nodes), "<p>Status: <span class='badge'>beta</span></p>\n");
+/// ```
+#[derive(Default)]
+pub struct Renderer {
+    directives: HashMap<String, Box<dyn DirectiveHandler>>,
+    /// Tab sets rendered so far, numbering the ids of the next one.
+    tab_sets: Cell<usize>,
+    /// Image URL template for emoji; `None` outputs the characters.
+    emoji_images: Option<String>,
+    /// `None` uses [`wiki::page_url`].
+    wiki_links: Option<Box<dyn WikiLinkResolver>>,
+    /// `None` leaves references unlinked.
+    references: Option<Box<dyn ReferenceResolver>>,
+}
+
+impl Renderer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Renders directives called `name` with `handler`, replacing any
+    /// earlier handler for the name.
+    pub fn directive<H: DirectiveHandler + 'static>(
+        &mut self,
+        name: &str,
+        handler: H,
+    ) -> &mut Self {
+        self.directives.insert(name.to_string(), Box::new(handler));
+        self
+    }
+
+    /// Registers the handlers in [`directives`]: `tabs`, `tab`, `details`,
+    /// `figure` and `youtube`.
+    pub fn builtin_directives(&mut self) -> &mut Self {
+        self.directive("tabs", directives::tabs)
+            .directive("tab", directives::tab)
+            .directive("details", directives::details)
+            .directive("figure", directives::figure)
+            .directive("youtube", directives::youtube)
+    }
+
+    /// Renders emoji as `<img>` elements whose `src` is `template` with
+    /// `{shortcode}` and `{codepoints}` (see [`emoji::codepoints`])
+    /// filled in, e.g. [`emoji::GITHUB_IMAGES`]. By default emoji are
+    /// output as Unicode characters.
+    pub fn emoji_images(&mut self, template: &str) -> &mut Self {
+        self.emoji_images = Some(template.to_string());
+        self
+    }
+
+    /// Resolves wiki link targets with `resolver`. Without one, every page
+    /// exists at [`wiki::page_url`].
+    pub fn wiki_links<R: WikiLinkResolver + 'static>(&mut self, resolver: R) -> &mut Self {
+        self.wiki_links = Some(Box::new(resolver));
+        self
+    }
+
+    /// A link to the resolved page, or a `missing` span when the resolver
+    /// does not know it. Headings link to their id.
+    fn render_wiki_link(&self, target: &str, heading: Option<&str>, alias: Option<&str>) -> String {
+        let text = wiki_link_text(target, heading, alias);
+        let url = match &self.wiki_links {
+            _ if target.is_empty() => Some(String::new()),
+            Some(resolver) => resolver.resolve(target),
+            None => Some(wiki::page_url(target)),
+        };
+        match url {
+            Some(url) => format!(
+                "<a class='wiki-link' href='{}{}'>{}</a>",
+                url,
+                heading
+                    .map(|heading| format!("#{}", slugify(heading)))
+                    .unwrap_or_default(),
+                text
+            ),
+            None => format!("<span class='wiki-link missing'>{}</span>", text),
+        }
+    }
 
-            // Paragraph rendering
-            Node::Paragraph { children } => {
-                html.push_str(&format!("<p>{}</p>\n", render(children)));
+    /// Links mentions, issue and commit references and hashtags with
+    /// `resolver`, e.g. [`ReferenceTemplates`]. By default they are
+    /// unlinked.
+    pub fn references<R: ReferenceResolver + 'static>(&mut self, resolver: R) -> &mut Self {
+        self.references = Some(Box::new(resolver));
+        self
+    }
+
+    /// A link with the reference's kind as class, or a `span` when it does
+    /// not resolve. Commits show 7 digits of their SHA.
+    fn render_reference(&self, reference: &Node) -> String {
+        let (class, text) = match reference {
+            Node::Mention { .. } => ("mention", reference_text(reference)),
+            Node::IssueReference { .. } => ("issue-reference", reference_text(reference)),
+            Node::CommitReference { repository, sha } => {
+                let short: String = sha.chars().take(7).collect();
+                let text = match repository {
+                    Some(repository) => format!("{}@{}", repository, short),
+                    None => short,
+                };
+                ("commit-reference", Some(text))
             }
+            _ => ("hashtag", reference_text(reference)),
+        };
+        let text = text.unwrap_or_default();
+        match self
+            .references
+            .as_ref()
+            .and_then(|resolver| resolver.resolve(reference))
+        {
+            Some(url) => format!("<a class='{}' href='{}'>{}</a>", class, url, text),
+            None => format!("<span class='{}'>{}</span>", class, text),
+        }
+    }
+
+    fn render_emoji(&self, shortcode: &str, unicode: &str) -> String {
+        match &self.emoji_images {
+            Some(template) => format!(
+                "<img class='emoji' src='{}' alt='{}' title=':{}:' />",
+                template
+                    .replace("{shortcode}", shortcode)
+                    .replace("{codepoints}", &emoji::codepoints(unicode)),
+                unicode,
+                shortcode
+            ),
+            None => unicode.to_string(),
+        }
+    }
 
-            Node::Link { text, url } => {
-                // Link: <a href="url">text</a>
-                html.push_str(&format!("<a href='{}'>{}</a>", url, render(text)));
+    /// Numbers tab sets so each gets unique element ids.
+    fn next_tab_set(&self) -> usize {
+        let n = self.tab_sets.get() + 1;
+        self.tab_sets.set(n);
+        n
+    }
+
+    fn render_directive(&self, directive: &Directive) -> String {
+        if let Some(handler) = self.directives.get(directive.name)
+            && let Some(html) = handler.render(directive, self)
+        {
+            return html;
+        }
+        self.render_default_directive(directive)
+    }
+
+    /// Markup for directives without a handler: a `div`, or a `span` for
+    /// inline directives, with the name as class.
+    pub fn render_default_directive(&self, directive: &Directive) -> String {
+        let attributes = render_directive_attributes(directive.name, directive.attributes);
+        match directive.kind {
+            DirectiveKind::Inline => {
+                format!(
+                    "<span{}>{}</span>",
+                    attributes,
+                    self.render(directive.label)
+                )
             }
-            Node::Image { alt, url } => {
-                // Image: <img src="url" alt="alt" />
-                html.push_str(&format!("<img src='{}' alt='{}' />", url, alt));
+            DirectiveKind::Leaf => {
+                format!(
+                    "<div{}>{}</div>\n",
+                    attributes,
+                    self.render(directive.label)
+                )
             }
-
-            // List rendering
-            Node::List { kind, items } => {
-                let tag = match kind {
-                    ListType::Ordered => "ol",
-                    ListType::Unordered => "ul",
+            DirectiveKind::Container => {
+                let label = if directive.label.is_empty() {
+                    String::new()
+                } else {
+                    format!(
+                        "<p class='directive-label'>{}</p>\n",
+                        self.render(directive.label)
+                    )
                 };
+                format!(
+                    "<div{}>{}{}</div>\n",
+                    attributes,
+                    label,
+                    self.render(directive.children)
+                )
+            }
+        }
+    }
 
-                html.push_str(&format!("<{}>\n", tag));
+    pub fn render(&self, nodes: &[Node]) -> String {
+        let mut html = String::new();
+        for node in nodes {
+            match node {
+                // Heading rendering
+                Node::Heading {
+                    level,
+                    id,
+                    children,
+                } => {
+                    html.push_str(&format!(
+                        "<h{} id=\"{}\">{}</h{}>\n",
+                        level,
+                        id,
+                        self.render(children),
+                        level
+                    ));
+                }
 
-                for item in items {
-                    html.push_str("  <li>");
+                // Paragraph rendering
+                Node::Paragraph { children } => {
+                    html.push_str(&format!("<p>{}</p>\n", self.render(children)));
+                }
 
-                    if let Some(checked) = item.checked {
-                        let check_attr = if checked { "checked" } else { "" };
-                        html.push_str(&format!(
-                            "<input type='checkbox' disabled {} style='margin-right: 5px;' />",
-                            check_attr
-                        ));
-                    }
+                Node::Link {
+                    text,
+                    url,
+                    attributes,
+                } => {
+                    // Link: <a href="url">text</a>
+                    html.push_str(&format!(
+                        "<a href='{}'{}>{}</a>",
+                        url,
+                        render_attributes(attributes),
+                        self.render(text)
+                    ));
+                }
+                Node::Image {
+                    alt,
+                    url,
+                    attributes,
+                } => {
+                    // Image: <img src="url" alt="alt" />
+                    html.push_str(&format!(
+                        "<img src='{}' alt='{}'{} />",
+                        url,
+                        alt,
+                        render_attributes(attributes)
+                    ));
+                }
+
+                // List rendering
+                Node::List { kind, items } => {
+                    let tag = match kind {
+                        ListType::Ordered => "ol",
+                        ListType::Unordered => "ul",
+                    };
 
-                    html.push_str(&render(&item.content));
+                    html.push_str(&format!("<{}>\n", tag));
 
-                    if !item.children.is_empty() {
-                        html.push('\n');
-                        let child_html = render(&item.children);
+                    for item in items {
+                        html.push_str("  <li>");
 
-                        for line in child_html.lines() {
-                            html.push_str(&format!("    {}\n", line));
+                        if let Some(checked) = item.checked {
+                            let check_attr = if checked { "checked" } else { "" };
+                            html.push_str(&format!(
+                                "<input type='checkbox' disabled {} style='margin-right: 5px;' />",
+                                check_attr
+                            ));
                         }
+
+                        html.push_str(&self.render(&item.content));
+
+                        if !item.children.is_empty() {
+                            html.push('\n');
+                            let child_html = self.render(&item.children);
+
+                            for line in child_html.lines() {
+                                html.push_str(&format!("    {}\n", line));
+                            }
+                        }
+
+                        html.push_str("</li>\n");
                     }
 
-                    html.push_str("</li>\n");
+                    html.push_str(&format!("</{}>\n", tag));
                 }
 
-                html.push_str(&format!("</{}>\n", tag));
-            }
+                // Definition list rendering
+                Node::DefinitionList { children } => {
+                    html.push_str(&format!("<dl>\n{}</dl>\n", self.render(children)));
+                }
+                Node::DefinitionTerm { children } => {
+                    html.push_str(&format!("<dt>{}</dt>\n", self.render(children)));
+                }
+                Node::DefinitionDescription { children } => match children.as_slice() {
+                    // A single paragraph stays tight, as in `<dd>text</dd>`
+                    [Node::Paragraph { children }] => {
+                        html.push_str(&format!("<dd>{}</dd>\n", self.render(children)));
+                    }
+                    _ => html.push_str(&format!("<dd>\n{}</dd>\n", self.render(children))),
+                },
 
-            // Blockquote rendering
-            Node::BlockQuote { children: content } => {
-                html.push_str(&format!("<blockquote>\n{}</blockquote>\n", render(content)));
-            }
+                // Blockquote rendering
+                Node::BlockQuote { children: content } => {
+                    html.push_str(&format!(
+                        "<blockquote>\n{}</blockquote>\n",
+                        self.render(content)
+                    ));
+                }
 
-            // Block math rendering
-            Node::BlockMath { content: math } => {
-                html.push_str(&format!("<div class='math-block'>\\[ {} \\]</div>\n", math));
-            }
+                // Callout rendering, GitHub alert markup
+                Node::Callout {
+                    kind,
+                    title,
+                    foldable,
+                    children,
+                } => {
+                    let class = format!("markdown-alert markdown-alert-{}", kind);
+                    let title = if title.is_empty() {
+                        let mut chars = kind.chars();
+                        chars
+                            .next()
+                            .map(|first| first.to_uppercase().chain(chars).collect())
+                            .unwrap_or_default()
+                    } else {
+                        self.render(title)
+                    };
+                    let icon = format!(
+                        "<span class='markdown-alert-icon' aria-hidden='true'>{}</span> ",
+                        callout_icon(kind)
+                    );
 
-            // Code block rendering
-            Node::CodeBlock {
-                lang,
-                filename,
-                code,
-            } => {
-                html.push_str(&format!(
-                    "<pre><code class=\"language-{}\">{:?}{}</code></pre>\n",
-                    lang, filename, code
-                ));
-            }
+                    match foldable {
+                        Some(fold) => html.push_str(&format!(
+                            "<details class='{}'{}>\n<summary class='markdown-alert-title'>{}{}</summary>\n{}</details>\n",
+                            class,
+                            if *fold == Fold::Open { " open" } else { "" },
+                            icon,
+                            title,
+                            self.render(children)
+                        )),
+                        None => html.push_str(&format!(
+                            "<div class='{}'>\n<p class='markdown-alert-title'>{}{}</p>\n{}</div>\n",
+                            class,
+                            icon,
+                            title,
+                            self.render(children)
+                        )),
+                    }
+                }
+
+                // Block math rendering
+                Node::BlockMath { content: math } => {
+                    html.push_str(&format!("<div class='math-block'>\\[ {} \\]</div>\n", math));
+                }
 
-            // Table rendering
-            Node::Table { header, rows } => {
-                html.push_str("<table>\n<thead>\n<tr>\n");
-                for cell in header {
-                    let align = render_alignment(cell.alignment);
-                    html.push_str(&format!("<th{}>{}</th>", align, render(&cell.children)));
+                // Code block rendering
+                Node::CodeBlock {
+                    lang,
+                    filename,
+                    code,
+                } => {
+                    html.push_str(&format!(
+                        "<pre><code class=\"language-{}\">{:?}{}</code></pre>\n",
+                        lang, filename, code
+                    ));
                 }
-                html.push_str("\n</tr>\n</thead>\n<tbody>\n");
 
-                for row in rows {
-                    html.push_str("<tr>\n");
-                    for cell in row {
+                // Table rendering
+                Node::Table { header, rows } => {
+                    html.push_str("<table>\n<thead>\n<tr>\n");
+                    for cell in header {
                         let align = render_alignment(cell.alignment);
-                        html.push_str(&format!("<td{}>{}</td>", align, render(&cell.children)));
+                        html.push_str(&format!(
+                            "<th{}>{}</th>",
+                            align,
+                            self.render(&cell.children)
+                        ));
                     }
-                    html.push_str("\n</tr>\n");
+                    html.push_str("\n</tr>\n</thead>\n<tbody>\n");
+
+                    for row in rows {
+                        html.push_str("<tr>\n");
+                        for cell in row {
+                            let align = render_alignment(cell.alignment);
+                            html.push_str(&format!(
+                                "<td{}>{}</td>",
+                                align,
+                                self.render(&cell.children)
+                            ));
+                        }
+                        html.push_str("\n</tr>\n");
+                    }
+                    html.push_str("</tbody>\n</table>\n");
                 }
-                html.push_str("</tbody>\n</table>\n");
-            }
 
-            // Custom block rendering
-            Node::CustomBlock {
-                name,
-                attributes,
-                children,
-            } => {
-                let mut attr_str = String::new();
+                // Custom block rendering
+                Node::CustomBlock {
+                    name,
+                    label,
+                    attributes,
+                    children,
+                } => {
+                    let directive = Directive {
+                        kind: DirectiveKind::Container,
+                        name,
+                        label,
+                        attributes,
+                        children,
+                    };
+                    html.push_str(&self.render_directive(&directive));
+                }
 
-                for (key, val) in attributes {
-                    attr_str.push_str(&format!(" data-{}='{}'", key, val));
+                // ::name[label]{attributes}
+                Node::LeafDirective {
+                    name,
+                    label,
+                    attributes,
+                } => {
+                    let directive = Directive {
+                        kind: DirectiveKind::Leaf,
+                        name,
+                        label,
+                        attributes,
+                        children: &[],
+                    };
+                    html.push_str(&self.render_directive(&directive));
                 }
 
-                html.push_str(&format!(
-                    "<div class='{}' {}>{}</div>\n",
+                // :name[label]{attributes}
+                Node::InlineDirective {
                     name,
-                    attr_str,
-                    render(children)
-                ));
-            }
+                    label,
+                    attributes,
+                } => {
+                    let directive = Directive {
+                        kind: DirectiveKind::Inline,
+                        name,
+                        label,
+                        attributes,
+                        children: &[],
+                    };
+                    html.push_str(&self.render_directive(&directive));
+                }
 
-            // Inline elements rendering
-            // HR rendering
-            Node::HorizontalRule => {
-                html.push_str("<hr />\n");
-            }
+                // Front matter is metadata, not content
+                Node::FrontMatter { .. } => {}
 
-            // Line break rendering
-            Node::LineBreak => {
-                html.push_str("<br />\n");
-            }
+                // Inline elements rendering
+                // HR rendering
+                Node::HorizontalRule => {
+                    html.push_str("<hr />\n");
+                }
 
-            // Text rendering
-            Node::Text { content: t } => html.push_str(t),
+                // Line break rendering
+                Node::LineBreak => {
+                    html.push_str("<br />\n");
+                }
 
-            // Bold rendering
-            Node::Bold { children } => {
-                html.push_str(&format!("<strong>{}</strong>", render(children)));
-            }
+                // Text rendering
+                Node::Text { content: t } => html.push_str(t),
 
-            // Italic rendering
-            Node::Italic { children } => {
-                html.push_str(&format!("<em>{}</em>", render(children)));
-            }
+                // Bold rendering
+                Node::Bold { children } => {
+                    html.push_str(&format!("<strong>{}</strong>", self.render(children)));
+                }
 
-            // Strikethrough rendering
-            Node::Strikethrough { children } => {
-                html.push_str(&format!("<del>{}</del>", render(children)));
-            }
+                // Italic rendering
+                Node::Italic { children } => {
+                    html.push_str(&format!("<em>{}</em>", self.render(children)));
+                }
+
+                // Strikethrough rendering
+                Node::Strikethrough { children } => {
+                    html.push_str(&format!("<del>{}</del>", self.render(children)));
+                }
 
-            // Underline rendering
-            Node::Underline { children } => {
-                html.push_str(&format!("<u>{}</u>", render(children)));
+                // Underline rendering
+                Node::Underline { children } => {
+                    html.push_str(&format!("<u>{}</u>", self.render(children)));
+                }
+
+                // Highlight, superscript, subscript and inserted text
+                Node::Highlight { children } => {
+                    html.push_str(&format!("<mark>{}</mark>", self.render(children)));
+                }
+                Node::Superscript { children } => {
+                    html.push_str(&format!("<sup>{}</sup>", self.render(children)));
+                }
+                Node::Subscript { children } => {
+                    html.push_str(&format!("<sub>{}</sub>", self.render(children)));
+                }
+                Node::Inserted { children } => {
+                    html.push_str(&format!("<ins>{}</ins>", self.render(children)));
+                }
+
+                Node::WikiLink {
+                    target,
+                    heading,
+                    alias,
+                } => {
+                    html.push_str(&self.render_wiki_link(
+                        target,
+                        heading.as_deref(),
+                        alias.as_deref(),
+                    ));
+                }
+
+                Node::Mention { .. }
+                | Node::IssueReference { .. }
+                | Node::CommitReference { .. }
+                | Node::Hashtag { .. } => {
+                    html.push_str(&self.render_reference(node));
+                }
+
+                Node::Emoji { shortcode, unicode } => {
+                    html.push_str(&self.render_emoji(shortcode, unicode));
+                }
+
+                // Inline math rendering
+                Node::InlineMath { content: math } => {
+                    html.push_str(&format!(
+                        "<span class='math-inline'>\\( {} \\)</span>",
+                        math
+                    ));
+                }
+
+                // Code span rendering
+                Node::InlineCode { content: code } => {
+                    html.push_str(&format!("<code>{}</code>", code));
+                }
             }
+        }
+
+        html
+    }
+}
+
+/// Rendered parts of a directive, passed to JavaScript handlers.
+#[derive(Serialize)]
+struct DirectiveContent {
+    label: String,
+    children: String,
+}
+
+/// JavaScript handle over a [`Renderer`]. Directive callbacks receive the
+/// directive node and its rendered label and content, and return HTML or
+/// nothing to keep the default markup. Wiki link callbacks return the URL
+/// of a page, or nothing when it does not exist; reference callbacks get
+/// the reference node and return its URL, or nothing to leave it unlinked.
+#[wasm_bindgen(js_name = Renderer)]
+#[derive(Default)]
+pub struct JsRenderer {
+    inner: Renderer,
+    /// First error thrown by a callback during the current render.
+    error: Rc<RefCell<Option<JsValue>>>,
+}
+
+#[wasm_bindgen(js_class = Renderer)]
+impl JsRenderer {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[wasm_bindgen(js_name = builtinDirectives)]
+    pub fn builtin_directives(&mut self) {
+        self.inner.builtin_directives();
+    }
 
-            // Inline math rendering
-            Node::InlineMath { content: math } => {
-                html.push_str(&format!(
-                    "<span class='math-inline'>\\( {} \\)</span>",
-                    math
-                ));
+    #[wasm_bindgen(js_name = emojiImages)]
+    pub fn emoji_images(&mut self, template: &str) {
+        self.inner.emoji_images(template);
+    }
+
+    #[wasm_bindgen(js_name = referenceTemplates)]
+    pub fn reference_templates(
+        &mut self,
+        #[wasm_bindgen(unchecked_param_type = "ReferenceTemplates")] templates: JsValue,
+    ) -> Result<(), JsValue> {
+        let templates: ReferenceTemplates = from_value(templates)?;
+        self.inner.references(templates);
+        Ok(())
+    }
+
+    pub fn references(
+        &mut self,
+        #[wasm_bindgen(
+            unchecked_param_type = "(reference: ReferenceNode) => string | null | void"
+        )]
+        callback: js_sys::Function,
+    ) {
+        let error = Rc::clone(&self.error);
+        self.inner.references(move |reference: &Node| {
+            if error.borrow().is_some() {
+                return None;
+            }
+            let call = || -> Result<JsValue, JsValue> {
+                callback.call1(&JsValue::NULL, &crate::to_js(reference)?)
+            };
+            match call() {
+                Ok(url) => url.as_string(),
+                Err(err) => {
+                    *error.borrow_mut() = Some(err);
+                    None
+                }
             }
+        });
+    }
 
-            // Code span rendering
-            Node::InlineCode { content: code } => {
-                html.push_str(&format!("<code>{}</code>", code));
+    #[wasm_bindgen(js_name = wikiLinks)]
+    pub fn wiki_links(
+        &mut self,
+        #[wasm_bindgen(unchecked_param_type = "(target: string) => string | null | void")]
+        callback: js_sys::Function,
+    ) {
+        let error = Rc::clone(&self.error);
+        self.inner.wiki_links(move |target: &str| {
+            if error.borrow().is_some() {
+                return None;
             }
-        }
+            match callback.call1(&JsValue::NULL, &JsValue::from_str(target)) {
+                Ok(url) => url.as_string(),
+                Err(err) => {
+                    *error.borrow_mut() = Some(err);
+                    None
+                }
+            }
+        });
+    }
+
+    pub fn directive(
+        &mut self,
+        name: &str,
+        #[wasm_bindgen(
+            unchecked_param_type = "(directive: DirectiveNode, content: DirectiveContent) => string | void"
+        )]
+        callback: js_sys::Function,
+    ) {
+        let error = Rc::clone(&self.error);
+        self.inner
+            .directive(name, move |directive: &Directive, renderer: &Renderer| {
+                if error.borrow().is_some() {
+                    return None;
+                }
+                let content = DirectiveContent {
+                    label: renderer.render(directive.label),
+                    children: renderer.render(directive.children),
+                };
+                let call = || -> Result<JsValue, JsValue> {
+                    let node = crate::to_js(&directive.to_node())?;
+                    let content = crate::to_js(&content)?;
+                    callback.call2(&JsValue::NULL, &node, &content)
+                };
+                match call() {
+                    Ok(html) => html.as_string(),
+                    Err(err) => {
+                        *error.borrow_mut() = Some(err);
+                        None
+                    }
+                }
+            });
+    }
+
+    #[wasm_bindgen(js_name = toHtml)]
+    pub fn to_html(
+        &self,
+        input: &str,
+        #[wasm_bindgen(unchecked_param_type = "ParseOptions | undefined")] options: JsValue,
+    ) -> Result<String, JsValue> {
+        let options = crate::options_from_js(options)?;
+        let nodes = Parser::with_options(input, options).parse_document();
+        self.render(&nodes)
+    }
+
+    #[wasm_bindgen(js_name = astToHtml)]
+    pub fn ast_to_html(
+        &self,
+        #[wasm_bindgen(unchecked_param_type = "Node[]")] ast: JsValue,
+    ) -> Result<String, JsValue> {
+        let nodes: Vec<Node> = from_value(ast)?;
+        crate::ast::validate(&nodes).map_err(|err| JsValue::from_str(&err.to_string()))?;
+        self.render(&nodes)
     }
+}
 
-    return html;
+impl JsRenderer {
+    fn render(&self, nodes: &[Node]) -> Result<String, JsValue> {
+        self.error.borrow_mut().take();
+        let html = self.inner.render(nodes);
+        match self.error.borrow_mut().take() {
+            Some(err) => Err(err),
+            None => Ok(html),
+        }
+    }
 }
@@ -0,0 +1,217 @@
+//! Built-in directive handlers, registered by
+//! [`Renderer::builtin_directives`](super::Renderer::builtin_directives).
+//!
+//! ```markdown
+//! :::tabs
+//! :::tab[Rust]
+//! `cargo add mdkit`
+//! :::
+//! :::tab[npm]
+//! `npm install mdkit`
+//! :::
+//! :::
+//!
+//! :::details[Why?]{open}
+//! Because.
+//! :::
+//!
+//! ::figure[A caption]{src=chart.png alt="Chart"}
+//!
+//! ::youtube[Intro]{id=dQw4w9WgXcQ}
+//! ```
+
+use std::collections::HashMap;
+
+use super::{Directive, DirectiveKind, Renderer, escape_attribute, render_directive_attributes};
+use crate::ast::{Node, plain_text};
+
+/// A WAI-ARIA tab list with one panel per `tab` child; the first tab is
+/// selected. Other children are rendered before the tab list.
+///
+/// Panels other than the first are `hidden`, and switching tabs is left
+/// to the page: a script has to move `aria-selected`, `tabindex` and
+/// `hidden` when a tab is clicked.
+pub fn tabs(directive: &Directive, renderer: &Renderer) -> Option<String> {
+    if directive.kind != DirectiveKind::Container {
+        return None;
+    }
+    let set = renderer.next_tab_set();
+    let base = directive
+        .attributes
+        .get("id")
+        .map(|id| escape_attribute(id))
+        .unwrap_or_else(|| format!("tabs-{}", set));
+
+    let mut other = String::new();
+    let mut buttons = String::new();
+    let mut panels = String::new();
+    let mut count = 0;
+    for child in directive.children {
+        let Node::CustomBlock {
+            name,
+            label,
+            attributes,
+            children,
+        } = child
+        else {
+            other.push_str(&renderer.render(std::slice::from_ref(child)));
+            continue;
+        };
+        if name != "tab" {
+            other.push_str(&renderer.render(std::slice::from_ref(child)));
+            continue;
+        }
+
+        count += 1;
+        let tab = format!("{}-tab-{}", base, count);
+        let panel = format!("{}-panel-{}", base, count);
+        let selected = count == 1;
+        buttons.push_str(&format!(
+            "<button type='button' role='tab' id='{}' aria-controls='{}' aria-selected='{}' tabindex='{}'>{}</button>\n",
+            tab,
+            panel,
+            selected,
+            if selected { "0" } else { "-1" },
+            title(label, attributes, renderer).unwrap_or_else(|| format!("Tab {}", count))
+        ));
+        panels.push_str(&format!(
+            "<div role='tabpanel' id='{}' aria-labelledby='{}' tabindex='0'{}>\n{}</div>\n",
+            panel,
+            tab,
+            if selected { "" } else { " hidden" },
+            renderer.render(children)
+        ));
+    }
+
+    let list_label = if directive.label.is_empty() {
+        String::new()
+    } else {
+        format!(
+            " aria-label='{}'",
+            escape_attribute(&plain_text(directive.label))
+        )
+    };
+    Some(format!(
+        "<div{}>\n{}<div role='tablist'{}>\n{}</div>\n{}</div>\n",
+        render_directive_attributes("tabs", directive.attributes),
+        other,
+        list_label,
+        buttons,
+        panels
+    ))
+}
+
+/// A `tab` outside of `tabs`: a labelled section.
+pub fn tab(directive: &Directive, renderer: &Renderer) -> Option<String> {
+    if directive.kind != DirectiveKind::Container {
+        return None;
+    }
+    let label = match directive.label {
+        [] => directive.attributes.get("title").cloned(),
+        label => Some(plain_text(label)),
+    };
+    Some(format!(
+        "<section{}{}>\n{}</section>\n",
+        render_directive_attributes("tab", directive.attributes),
+        label
+            .map(|label| format!(" aria-label='{}'", escape_attribute(&label)))
+            .unwrap_or_default(),
+        renderer.render(directive.children)
+    ))
+}
+
+/// A disclosure widget; the label (or `title`) is the summary and an
+/// `open` attribute expands it.
+pub fn details(directive: &Directive, renderer: &Renderer) -> Option<String> {
+    if directive.kind != DirectiveKind::Container {
+        return None;
+    }
+    let mut attributes = directive.attributes.clone();
+    let open = attributes.remove("open").is_some();
+    let summary = title(directive.label, &attributes, renderer);
+    attributes.remove("title");
+
+    Some(format!(
+        "<details{}{}>\n<summary>{}</summary>\n{}</details>\n",
+        render_directive_attributes("details", &attributes),
+        if open { " open" } else { "" },
+        summary.unwrap_or_else(|| "Details".to_string()),
+        renderer.render(directive.children)
+    ))
+}
+
+/// A figure with the label as caption, around the content of a container
+/// or, for a leaf, an image from `src` and `alt`.
+pub fn figure(directive: &Directive, renderer: &Renderer) -> Option<String> {
+    let mut attributes = directive.attributes.clone();
+    let content = match directive.kind {
+        DirectiveKind::Container => renderer.render(directive.children),
+        DirectiveKind::Leaf => {
+            let src = attributes.remove("src")?;
+            let alt = attributes.remove("alt").unwrap_or_default();
+            format!(
+                "<img src='{}' alt='{}' />\n",
+                escape_attribute(&src),
+                escape_attribute(&alt)
+            )
+        }
+        DirectiveKind::Inline => return None,
+    };
+    let caption = if directive.label.is_empty() {
+        String::new()
+    } else {
+        format!(
+            "<figcaption>{}</figcaption>\n",
+            renderer.render(directive.label)
+        )
+    };
+
+    Some(format!(
+        "<figure{}>\n{}{}</figure>\n",
+        render_directive_attributes("figure", &attributes),
+        content,
+        caption
+    ))
+}
+
+/// An embedded YouTube player for the video in `id` (or `v`), using the
+/// privacy-enhanced domain. Declines ids that are not plain video ids.
+pub fn youtube(directive: &Directive, _renderer: &Renderer) -> Option<String> {
+    if directive.kind == DirectiveKind::Inline {
+        return None;
+    }
+    let mut attributes = directive.attributes.clone();
+    let video = attributes.remove("id").or_else(|| attributes.remove("v"))?;
+    if video.is_empty()
+        || !video
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return None;
+    }
+    let title = match directive.label {
+        [] => attributes.get("title").cloned(),
+        label => Some(plain_text(label)),
+    };
+    attributes.remove("title");
+
+    Some(format!(
+        "<div{}><iframe src='https://www.youtube-nocookie.com/embed/{}' title='{}' loading='lazy' allow='accelerometer; clipboard-write; encrypted-media; gyroscope; picture-in-picture' allowfullscreen></iframe></div>\n",
+        render_directive_attributes("youtube", &attributes),
+        video,
+        escape_attribute(&title.unwrap_or_else(|| "YouTube video".to_string()))
+    ))
+}
+
+/// Rendered label, or the `title` attribute when there is no label.
+fn title(
+    label: &[Node],
+    attributes: &HashMap<String, String>,
+    renderer: &Renderer,
+) -> Option<String> {
+    if label.is_empty() {
+        attributes.get("title").cloned()
+    } else {
+        Some(renderer.render(label))
+    }
+}
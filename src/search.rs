@@ -0,0 +1,252 @@
+//! Full-text search over heading sections.
+//!
+//! [`sections`] splits a document at its top-level headings, keeping code
+//! apart from prose. A [`SearchIndex`] maps every term to the sections that
+//! contain it and serializes to compact JSON, which the browser can load and
+//! query with the wasm `search` function.
+//!
+//! ```
+//! use mdkit::parser::Parser;
+//! use mdkit::search::SearchIndex;
+//!
+//! let mut index = SearchIndex::new();
+//! index.add(
+//!     "guide.html",
+//!     &Parser::new("# Guide\n\n## Install\n\nRun the installer.").parse_document(),
+//! );
+//!
+//! let hits = index.search("inst");
+//! assert_eq!(hits[0].entry.id, "install");
+//! assert_eq!(hits[0].entry.headings, vec!["Guide", "Install"]);
+//! ```
+
+use std::collections::{BTreeMap, HashMap};
+
+use serde::{Deserialize, Serialize};
+
+use crate::ast::visit::{Visitor, walk_list_item, walk_node, walk_table_cell};
+use crate::ast::{ListItem, Node, TableCell, plain_text};
+
+/// Characters of section text kept in each index entry.
+const EXCERPT_LENGTH: usize = 160;
+
+/// A heading and the content up to the next top-level heading.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Section {
+    /// Heading text; empty for content before the first heading.
+    pub title: String,
+    /// Titles of the enclosing headings, ending with this one.
+    pub headings: Vec<String>,
+    /// Anchor id of the heading.
+    pub id: String,
+    /// Plain text with whitespace collapsed, without code blocks.
+    pub text: String,
+    pub code: Vec<String>,
+}
+
+pub fn sections(nodes: &[Node]) -> Vec<Section> {
+    let mut sections = Vec::new();
+    let mut open: Vec<(usize, String)> = Vec::new();
+    let mut current = Section::default();
+    let mut body = Body::default();
+
+    for node in nodes {
+        let Node::Heading {
+            level,
+            id,
+            children,
+        } = node
+        else {
+            body.visit_node(node);
+            continue;
+        };
+
+        finish(&mut current, body, &mut sections);
+        body = Body::default();
+
+        while open.last().is_some_and(|(open, _)| open >= level) {
+            open.pop();
+        }
+        let title = plain_text(children).trim().to_string();
+        open.push((*level, title.clone()));
+        current = Section {
+            title,
+            headings: open.iter().map(|(_, title)| title.clone()).collect(),
+            id: id.clone(),
+            ..Section::default()
+        };
+    }
+    finish(&mut current, body, &mut sections);
+    sections
+}
+
+/// Pushes `section` with its body, skipping empty content before the first
+/// heading.
+fn finish(section: &mut Section, body: Body, sections: &mut Vec<Section>) {
+    section.text = body.text.split_whitespace().collect::<Vec<_>>().join(" ");
+    section.code = body.code;
+    if !section.headings.is_empty() || !section.text.is_empty() || !section.code.is_empty() {
+        sections.push(std::mem::take(section));
+    }
+}
+
+/// Text and code blocks of a section's nodes.
+#[derive(Default)]
+struct Body {
+    text: String,
+    code: Vec<String>,
+}
+
+impl Visitor for Body {
+    fn visit_node(&mut self, node: &Node) {
+        match node {
+            Node::CodeBlock { code, .. } => self.code.push(code.clone()),
+            Node::Text { content }
+            | Node::InlineCode { content }
+            | Node::InlineMath { content }
+            | Node::BlockMath { content } => self.text.push_str(content),
+            Node::Image { alt, .. } => self.text.push_str(alt),
+            Node::WikiLink { .. }
+            | Node::Mention { .. }
+            | Node::IssueReference { .. }
+            | Node::CommitReference { .. }
+            | Node::Hashtag { .. } => self.text.push_str(&plain_text(std::slice::from_ref(node))),
+            Node::LineBreak => self.text.push(' '),
+            Node::Paragraph { .. } | Node::Heading { .. } | Node::DefinitionTerm { .. } => {
+                walk_node(self, node);
+                // Keeps words of adjacent blocks apart.
+                self.text.push(' ');
+            }
+            _ => walk_node(self, node),
+        }
+    }
+
+    fn visit_list_item(&mut self, item: &ListItem) {
+        walk_list_item(self, item);
+        self.text.push(' ');
+    }
+
+    fn visit_table_cell(&mut self, cell: &TableCell) {
+        walk_table_cell(self, cell);
+        self.text.push(' ');
+    }
+}
+
+/// A section as stored in the index, with a short excerpt instead of its
+/// full text.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SearchEntry {
+    /// Page the section belongs to, as passed to [`SearchIndex::add`].
+    pub path: String,
+    pub id: String,
+    pub title: String,
+    pub headings: Vec<String>,
+    pub excerpt: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SearchHit {
+    pub entry: SearchEntry,
+    pub score: u32,
+}
+
+/// Inverted index from terms to `[entry, weight]` pairs.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SearchIndex {
+    pub entries: Vec<SearchEntry>,
+    pub terms: BTreeMap<String, Vec<(usize, u32)>>,
+}
+
+impl SearchIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Indexes the sections of one page. Title terms weigh most, then code,
+    /// then text.
+    pub fn add(&mut self, path: &str, nodes: &[Node]) -> &mut Self {
+        for section in sections(nodes) {
+            let entry = self.entries.len();
+            let mut weights: HashMap<String, u32> = HashMap::new();
+            let mut count = |text: &str, weight: u32| {
+                for term in terms(text).filter(|term| term.chars().count() > 1) {
+                    *weights.entry(term).or_default() += weight;
+                }
+            };
+            count(&section.title, 10);
+            for code in &section.code {
+                count(code, 2);
+            }
+            count(&section.text, 1);
+
+            for (term, weight) in weights {
+                self.terms.entry(term).or_default().push((entry, weight));
+            }
+            self.entries.push(SearchEntry {
+                path: path.to_string(),
+                id: section.id,
+                title: section.title,
+                headings: section.headings,
+                excerpt: excerpt(&section.text),
+            });
+        }
+        self
+    }
+
+    /// Entries containing every query word, best first. Words match terms
+    /// they are a prefix of, and whole-term matches count double.
+    pub fn search(&self, query: &str) -> Vec<SearchHit> {
+        let mut scores: Option<HashMap<usize, u32>> = None;
+        for word in terms(query) {
+            let mut matches: HashMap<usize, u32> = HashMap::new();
+            for (term, postings) in self
+                .terms
+                .range(word.clone()..)
+                .take_while(|(term, _)| term.starts_with(&word))
+            {
+                let factor = if *term == word { 2 } else { 1 };
+                for &(entry, weight) in postings {
+                    *matches.entry(entry).or_default() += weight * factor;
+                }
+            }
+
+            scores = Some(match scores {
+                None => matches,
+                Some(scores) => scores
+                    .into_iter()
+                    .filter_map(|(entry, score)| Some((entry, score + matches.get(&entry)?)))
+                    .collect(),
+            });
+        }
+
+        let mut hits: Vec<(usize, u32)> = scores.unwrap_or_default().into_iter().collect();
+        hits.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        hits.into_iter()
+            .filter_map(|(entry, score)| {
+                Some(SearchHit {
+                    entry: self.entries.get(entry)?.clone(),
+                    score,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Lowercased words; anything but letters and digits separates them, so
+/// `parse_document` yields `parse` and `document`.
+fn terms(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+}
+
+fn excerpt(text: &str) -> String {
+    match text.char_indices().nth(EXCERPT_LENGTH) {
+        Some((end, _)) => {
+            // Cut at a word boundary when there is one.
+            let cut = text[..end].rfind(' ').unwrap_or(end);
+            format!("{}…", &text[..cut])
+        }
+        None => text.to_string(),
+    }
+}
@@ -0,0 +1,314 @@
+//! Static documentation site builder.
+//!
+//! Every `.md` file below the source directory becomes an `.html` page at
+//! the same relative path, rendered into a template. Other files are copied
+//! as assets, and files or folders starting with `.` are skipped. Relative
+//! links to `.md` files are rewritten to the generated `.html` pages.
+//!
+//! Templates can use these placeholders:
+//!
+//! - `{{title}}`: the front matter `title`, the page's first level-1
+//!   heading, or its file name
+//! - `{{content}}`: the rendered page
+//! - `{{toc}}`: the page's table of contents
+//! - `{{nav}}`: a sidebar of all pages, nested by folder
+//! - `{{root}}`: relative path from the page to the site root, e.g. `../`
+//!
+//! Without an `index.md` at the top level, `index.html` lists every page.
+//! With [`Site::search`], `search.json` holds a [`SearchIndex`] of all pages.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::ast::visit::{VisitorMut, walk_node_mut, walk_nodes_mut};
+use crate::ast::{Document, Node, plain_text};
+use crate::parser::Parser;
+use crate::search::SearchIndex;
+use crate::{renderer, toc};
+
+pub const DEFAULT_TEMPLATE: &str = "<!DOCTYPE html>
+<html>
+<head>
+<meta charset='utf-8'>
+<title>{{title}}</title>
+</head>
+<body>
+<nav>
+{{nav}}</nav>
+<main>
+{{content}}</main>
+<aside>
+{{toc}}</aside>
+</body>
+</html>
+";
+
+/// Files written by [`Site::build`], relative to the output directory.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SiteReport {
+    pub pages: Vec<PathBuf>,
+    pub assets: Vec<PathBuf>,
+    pub search_index: Option<PathBuf>,
+}
+
+pub struct Site {
+    source: PathBuf,
+    template: String,
+    title: Option<String>,
+    search: bool,
+}
+
+struct Page {
+    /// Output path relative to the site root, with forward slashes.
+    path: String,
+    title: String,
+    nodes: Vec<Node>,
+}
+
+impl Site {
+    pub fn new(source: impl Into<PathBuf>) -> Self {
+        Self {
+            source: source.into(),
+            template: DEFAULT_TEMPLATE.to_string(),
+            title: None,
+            search: false,
+        }
+    }
+
+    pub fn template(&mut self, template: impl Into<String>) -> &mut Self {
+        self.template = template.into();
+        self
+    }
+
+    /// Title of the generated index page; defaults to the source folder name.
+    pub fn title(&mut self, title: impl Into<String>) -> &mut Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Also writes `search.json` for the wasm `search` function.
+    pub fn search(&mut self, search: bool) -> &mut Self {
+        self.search = search;
+        self
+    }
+
+    pub fn build(&self, output: &Path) -> io::Result<SiteReport> {
+        let mut files = Vec::new();
+        collect_files(&self.source, Path::new(""), &mut files)?;
+
+        let mut report = SiteReport::default();
+        let mut pages = Vec::new();
+        for file in files {
+            if file.extension().is_some_and(|ext| ext == "md") {
+                let text = fs::read_to_string(self.source.join(&file))?;
+                pages.push(page(&file, &text));
+            } else {
+                let target = output.join(&file);
+                if let Some(parent) = target.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                fs::copy(self.source.join(&file), target)?;
+                report.assets.push(file);
+            }
+        }
+
+        // Grouped by folder for the sidebar: top-level pages first, and
+        // `index` leading each folder.
+        pages.sort_by(|a, b| sort_key(&a.path).cmp(&sort_key(&b.path)));
+
+        if !pages.iter().any(|page| page.path == "index.html") {
+            let title = self.title.clone().unwrap_or_else(|| {
+                self.source
+                    .canonicalize()
+                    .ok()
+                    .and_then(|path| path.file_name().map(|name| name.to_string_lossy().into()))
+                    .unwrap_or_else(|| "Index".to_string())
+            });
+            pages.insert(
+                0,
+                Page {
+                    path: "index.html".to_string(),
+                    title,
+                    nodes: Vec::new(),
+                },
+            );
+        }
+
+        for page in &pages {
+            let html = self.render_page(page, &pages);
+            let target = output.join(&page.path);
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(target, html)?;
+            report.pages.push(PathBuf::from(&page.path));
+        }
+
+        if self.search {
+            let mut index = SearchIndex::new();
+            for page in &pages {
+                index.add(&page.path, &page.nodes);
+            }
+            let json = serde_json::to_string(&index).map_err(io::Error::other)?;
+            fs::write(output.join("search.json"), json)?;
+            report.search_index = Some(PathBuf::from("search.json"));
+        }
+        Ok(report)
+    }
+
+    fn render_page(&self, page: &Page, pages: &[Page]) -> String {
+        let root = "../".repeat(page.path.matches('/').count());
+        let nav = render_nav(pages, &page.path, &root);
+
+        // Generated index pages list every page in their content.
+        let content = if page.nodes.is_empty() && page.path == "index.html" {
+            format!("<h1>{}</h1>\n{}", page.title, nav)
+        } else {
+            renderer::render(&page.nodes)
+        };
+        let toc_markdown = toc::render(&toc::toc(&page.nodes));
+        let toc_html = renderer::render(&Parser::new(&toc_markdown).parse_document());
+
+        // Content goes last so placeholders inside it are left alone.
+        self.template
+            .replace("{{title}}", &page.title)
+            .replace("{{root}}", &root)
+            .replace("{{nav}}", &nav)
+            .replace("{{toc}}", &toc_html)
+            .replace("{{content}}", &content)
+    }
+}
+
+fn page(file: &Path, text: &str) -> Page {
+    let mut nodes = Parser::new(text).parse_document();
+    walk_nodes_mut(&mut RewriteLinks, &mut nodes);
+
+    let document = Document::new(nodes);
+    let title = document
+        .metadata_str("title")
+        .map(str::to_string)
+        .or_else(|| {
+            document.children.iter().find_map(|node| match node {
+                Node::Heading {
+                    level: 1, children, ..
+                } => Some(plain_text(children).trim().to_string()),
+                _ => None,
+            })
+        })
+        .unwrap_or_else(|| {
+            file.file_stem()
+                .map_or(String::new(), |stem| stem.to_string_lossy().into())
+        });
+
+    let path: Vec<String> = file
+        .with_extension("html")
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy().into())
+        .collect();
+
+    Page {
+        path: path.join("/"),
+        title,
+        nodes: document.children,
+    }
+}
+
+fn sort_key(path: &str) -> (Vec<&str>, bool, &str) {
+    let (folder, name) = path.rsplit_once('/').unwrap_or(("", path));
+    let folders = if folder.is_empty() {
+        Vec::new()
+    } else {
+        folder.split('/').collect()
+    };
+    (folders, name != "index.html", name)
+}
+
+/// Relative paths of the files below `dir`, sorted, skipping dotfiles.
+fn collect_files(root: &Path, dir: &Path, files: &mut Vec<PathBuf>) -> io::Result<()> {
+    let mut entries: Vec<_> = fs::read_dir(root.join(dir))?.collect::<Result<_, _>>()?;
+    entries.sort_by_key(|entry| entry.file_name());
+
+    for entry in entries {
+        let name = entry.file_name();
+        if name.to_string_lossy().starts_with('.') {
+            continue;
+        }
+        let relative = dir.join(&name);
+        if entry.file_type()?.is_dir() {
+            collect_files(root, &relative, files)?;
+        } else {
+            files.push(relative);
+        }
+    }
+    Ok(())
+}
+
+/// Points relative links at `.md` files to the generated `.html` pages.
+struct RewriteLinks;
+
+impl VisitorMut for RewriteLinks {
+    fn visit_node_mut(&mut self, node: &mut Node) {
+        if let Node::Link { url, .. } = node
+            && let Some(rewritten) = rewrite_link(url)
+        {
+            *url = rewritten;
+        }
+        walk_node_mut(self, node);
+    }
+}
+
+fn rewrite_link(url: &str) -> Option<String> {
+    if url.contains("://") || url.starts_with("mailto:") || url.starts_with('/') {
+        return None;
+    }
+    let (path, fragment) = match url.split_once('#') {
+        Some((path, fragment)) => (path, format!("#{}", fragment)),
+        None => (url, String::new()),
+    };
+    let stem = path.strip_suffix(".md")?;
+    Some(format!("{}.html{}", stem, fragment))
+}
+
+/// Sidebar of all pages as nested lists, one per folder.
+fn render_nav(pages: &[Page], current: &str, root: &str) -> String {
+    let mut html = String::from("<ul>\n");
+    let mut open: Vec<&str> = Vec::new();
+
+    for page in pages {
+        let folders: Vec<&str> = match page.path.rsplit_once('/') {
+            Some((folder, _)) => folder.split('/').collect(),
+            None => Vec::new(),
+        };
+
+        let common = open
+            .iter()
+            .zip(&folders)
+            .take_while(|(a, b)| a == b)
+            .count();
+        for _ in common..open.len() {
+            html.push_str("</ul></li>\n");
+        }
+        open.truncate(common);
+        for folder in &folders[common..] {
+            html.push_str(&format!("<li><span>{}</span><ul>\n", folder));
+            open.push(folder);
+        }
+
+        let class = if page.path == current {
+            " class='active'"
+        } else {
+            ""
+        };
+        html.push_str(&format!(
+            "<li><a href='{}{}'{}>{}</a></li>\n",
+            root, page.path, class, page.title
+        ));
+    }
+
+    for _ in 0..open.len() {
+        html.push_str("</ul></li>\n");
+    }
+    html.push_str("</ul>\n");
+    html
+}
@@ -0,0 +1,320 @@
+//! Incremental rendering for text that arrives in chunks, e.g. LLM output.
+//!
+//! A block is finalized once a blank line follows it outside of any open
+//! code fence, `$$` math block, `:::` custom block or leading front matter.
+//! A leading `---` or `+++` stops being held as front matter at the first
+//! line that cannot be YAML or TOML, or after
+//! [`MAX_FRONT_MATTER_LINES`] lines.
+//! Blank lines inside a definition list only count once the lines after
+//! them show the list has ended.
+//! Finalized blocks are parsed and rendered exactly once; everything after
+//! the last boundary is the provisional tail, re-rendered on every push in a
+//! form that does not flicker while markers are half-typed.
+//!
+//! ```
+//! use mdkit::streaming::StreamingParser;
+//!
+//! let mut stream = StreamingParser::new();
+//! let update = stream.push("# Title\n\nSome **bo");
+//! assert!(update.stable.contains("<h1"));
+//! assert_eq!(update.tail, "<p>Some <strong>bo</strong></p>\n");
+//!
+//! let update = stream.push("ld** text\n\n");
+//! assert!(update.stable.contains("<strong>bold</strong>"));
+//! assert!(update.tail.is_empty());
+//! ```
+
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+use crate::ast::{FrontMatterFormat, Node};
+use crate::parser::{ParseOptions, Parser, definition_content};
+use crate::renderer;
+use crate::to_js;
+
+/// Lines a leading `---` or `+++` block may run before it is treated as
+/// content.
+pub const MAX_FRONT_MATTER_LINES: usize = 100;
+
+/// Result of a push: HTML to append after previously stable output, and
+/// HTML that replaces the previous provisional tail.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct StreamUpdate {
+    pub stable: String,
+    pub tail: String,
+}
+
+#[derive(Debug, Default)]
+pub struct StreamingParser {
+    buffer: String,
+    // Byte offset up to which blocks are finalized.
+    committed: usize,
+    // Byte offset of the first line not yet scanned for boundaries.
+    scanned: usize,
+    in_fence: bool,
+    in_math: bool,
+    // Front matter that is still open, and the lines read into it.
+    front_matter: Option<(FrontMatterFormat, usize)>,
+    custom_depth: usize,
+    // Inside a definition list, after a `: ` line.
+    definitions: bool,
+    // End of the first blank line since the last definition; a boundary if
+    // the list turns out to have ended there.
+    held: Option<usize>,
+    // Whether the last line may be the term of a further definition.
+    term: bool,
+    nodes: Vec<Node>,
+}
+
+impl StreamingParser {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, chunk: &str) -> StreamUpdate {
+        self.buffer.push_str(chunk);
+
+        let mut boundary = None;
+        while let Some(len) = self.buffer[self.scanned..].find('\n') {
+            let line_end = self.scanned + len + 1;
+            let line = self.buffer[self.scanned..line_end - 1].to_string();
+            let line_boundary = self.scan_line(&line, line_end);
+            self.scanned = line_end;
+
+            if line_boundary.is_some() {
+                boundary = line_boundary;
+            }
+        }
+
+        let stable = match boundary {
+            Some(end) => self.commit(end),
+            None => String::new(),
+        };
+
+        StreamUpdate {
+            stable,
+            tail: self.render_tail(),
+        }
+    }
+
+    /// Finalizes whatever is left in the buffer.
+    pub fn finish(&mut self) -> StreamUpdate {
+        let end = self.buffer.len();
+        StreamUpdate {
+            stable: self.commit(end),
+            tail: String::new(),
+        }
+    }
+
+    /// Finalized blocks parsed so far.
+    pub fn nodes(&self) -> &[Node] {
+        &self.nodes
+    }
+
+    /// Full text received so far.
+    pub fn source(&self) -> &str {
+        &self.buffer
+    }
+
+    fn commit(&mut self, end: usize) -> String {
+        let segment = &self.buffer[self.committed..end];
+        let options = self.options();
+        self.committed = end;
+        self.scanned = self.scanned.max(end);
+
+        let nodes = Parser::with_options(segment, options).parse_document();
+        let html = renderer::render(&nodes);
+        self.nodes.extend(nodes);
+        html
+    }
+
+    /// Updates fence/math/custom block state with a complete line ending at
+    /// `line_end` and returns the end of a block boundary it confirms.
+    fn scan_line(&mut self, line: &str, line_end: usize) -> Option<usize> {
+        let trimmed = line.trim();
+
+        if let Some((format, lines)) = self.front_matter {
+            if line.trim_end() == format.delimiter() {
+                self.front_matter = None;
+            } else if lines < MAX_FRONT_MATTER_LINES && front_matter_line(format, line) {
+                self.front_matter = Some((format, lines + 1));
+            } else {
+                // Not front matter after all: scan what was held as content.
+                self.front_matter = None;
+                return self.rescan(line_end);
+            }
+            return None;
+        }
+        if self.scanned == 0 {
+            self.front_matter = match line.trim_end() {
+                "---" => Some((FrontMatterFormat::Yaml, 0)),
+                "+++" => Some((FrontMatterFormat::Toml, 0)),
+                _ => None,
+            };
+            if self.front_matter.is_some() {
+                return None;
+            }
+        }
+
+        if self.in_fence {
+            if trimmed.starts_with("```") {
+                self.in_fence = false;
+            }
+            return None;
+        }
+
+        if self.in_math || trimmed.starts_with("$$") {
+            let toggles = trimmed.matches("$$").count();
+            if toggles % 2 == 1 {
+                self.in_math = !self.in_math;
+            }
+            return None;
+        }
+
+        if trimmed.starts_with("```") {
+            self.in_fence = true;
+        } else if trimmed == ":::" {
+            self.custom_depth = self.custom_depth.saturating_sub(1);
+        } else if trimmed
+            .strip_prefix(":::")
+            .is_some_and(|name| !name.trim_start().starts_with(['[', '{']))
+        {
+            self.custom_depth += 1;
+        }
+
+        if self.custom_depth > 0 {
+            None
+        } else if self.definitions {
+            self.scan_definition_line(line, line_end)
+        } else {
+            self.definitions = definition_content(line).is_some();
+            trimmed.is_empty().then_some(line_end)
+        }
+    }
+
+    /// Scans the uncommitted lines up to `end` again and returns the last
+    /// boundary among them.
+    fn rescan(&mut self, end: usize) -> Option<usize> {
+        let held = self.buffer[self.committed..end].to_string();
+        let mut line_end = self.committed;
+        let mut boundary = None;
+        for line in held.split_inclusive('\n') {
+            line_end += line.len();
+            boundary = self
+                .scan_line(line.trim_end_matches('\n'), line_end)
+                .or(boundary);
+        }
+        boundary
+    }
+
+    /// A blank line in a definition list is held back: definitions and
+    /// indented continuations after it keep the list open, and so does a
+    /// term directly followed by its `: ` line.
+    fn scan_definition_line(&mut self, line: &str, line_end: usize) -> Option<usize> {
+        let continues = definition_content(line).is_some()
+            || (!self.term && line.starts_with([' ', '\t']) && !line.trim().is_empty());
+        if continues {
+            self.held = None;
+            self.term = false;
+            return None;
+        }
+        if !self.term {
+            if line.trim().is_empty() {
+                self.held.get_or_insert(line_end);
+            } else {
+                self.term = true;
+            }
+            return None;
+        }
+
+        // The line before was not a term, so the list ended at the held
+        // blank line, or at this one.
+        let boundary = if line.trim().is_empty() {
+            Some(line_end)
+        } else {
+            self.held
+        };
+        self.definitions = false;
+        self.held = None;
+        self.term = false;
+        boundary
+    }
+
+    fn render_tail(&self) -> String {
+        let mut tail = self.buffer[self.committed..].to_string();
+        if tail.trim().is_empty() {
+            return String::new();
+        }
+
+        // The unterminated last line may itself open or close a fence.
+        let partial = self.buffer[self.scanned..].trim_start();
+        let open_fence = self.in_fence != partial.starts_with("```");
+
+        if open_fence {
+            // Keep the partial code visible as an already closed block.
+            if !tail.ends_with('\n') {
+                tail.push('\n');
+            }
+            tail.push_str("```\n");
+        } else if !self.in_math {
+            // Hide marker runs that are still being typed so `*` does not
+            // flash as an empty emphasis before becoming `**`.
+            let kept = tail.trim_end_matches(['*', '_', '~', '`', '$', '[', '!', '(']);
+            tail.truncate(kept.len());
+        }
+
+        renderer::render(&Parser::with_options(&tail, self.options()).parse_document())
+    }
+
+    /// Front matter is only recognized at the start of the stream.
+    fn options(&self) -> ParseOptions {
+        ParseOptions {
+            front_matter: self.committed == 0,
+            ..ParseOptions::default()
+        }
+    }
+}
+
+/// Whether `line` can be part of a front matter block, as a key, comment,
+/// continuation or list or table line.
+fn front_matter_line(format: FrontMatterFormat, line: &str) -> bool {
+    if line.trim().is_empty() || line.starts_with([' ', '\t', '#', '"', '\'', '[', ']']) {
+        return true;
+    }
+    match format {
+        FrontMatterFormat::Yaml => line.starts_with(['-', '{', '}']) || line.contains(':'),
+        FrontMatterFormat::Toml => line.contains('='),
+    }
+}
+
+/// JavaScript handle over [`StreamingParser`]. `push` returns
+/// `{ stable, tail }`: append `stable` to the output and replace the
+/// previously rendered tail with `tail`.
+#[wasm_bindgen(js_name = StreamingParser)]
+#[derive(Default)]
+pub struct JsStreamingParser {
+    inner: StreamingParser,
+}
+
+#[wasm_bindgen(js_class = StreamingParser)]
+impl JsStreamingParser {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[wasm_bindgen(unchecked_return_type = "StreamUpdate")]
+    pub fn push(&mut self, chunk: &str) -> Result<JsValue, JsError> {
+        Ok(to_js(&self.inner.push(chunk))?)
+    }
+
+    #[wasm_bindgen(unchecked_return_type = "StreamUpdate")]
+    pub fn finish(&mut self) -> Result<JsValue, JsError> {
+        Ok(to_js(&self.inner.finish())?)
+    }
+
+    #[wasm_bindgen(unchecked_return_type = "Node[]")]
+    pub fn nodes(&self) -> Result<JsValue, JsError> {
+        Ok(to_js(self.inner.nodes())?)
+    }
+}
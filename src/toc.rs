@@ -0,0 +1,61 @@
+//! Table of contents built from a document's top-level headings.
+//!
+//! ```
+//! use mdkit::parser::Parser;
+//! use mdkit::toc;
+//!
+//! let nodes = Parser::new("# Guide\n\n## Install\n\n## Usage").parse_document();
+//! let entries = toc::toc(&nodes);
+//!
+//! assert_eq!(
+//!     toc::render(&entries),
+//!     "- [Guide](#guide)\n  - [Install](#install)\n  - [Usage](#usage)\n"
+//! );
+//! ```
+
+use serde::Serialize;
+
+use crate::ast::{Node, plain_text};
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TocEntry {
+    pub level: usize,
+    pub id: String,
+    pub text: String,
+}
+
+pub fn toc(nodes: &[Node]) -> Vec<TocEntry> {
+    nodes
+        .iter()
+        .filter_map(|node| match node {
+            Node::Heading {
+                level,
+                id,
+                children,
+            } => Some(TocEntry {
+                level: *level,
+                id: id.clone(),
+                text: plain_text(children).trim().to_string(),
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Renders entries as a nested Markdown list of links. An entry is nested
+/// under the closest preceding entry of a lower level, so skipped levels do
+/// not produce empty list items.
+pub fn render(entries: &[TocEntry]) -> String {
+    let mut output = String::new();
+    let mut open: Vec<usize> = Vec::new();
+
+    for entry in entries {
+        while open.last().is_some_and(|&level| level >= entry.level) {
+            open.pop();
+        }
+        output.push_str(&"  ".repeat(open.len()));
+        output.push_str(&format!("- [{}](#{})\n", entry.text, entry.id));
+        open.push(entry.level);
+    }
+    output
+}
@@ -0,0 +1,521 @@
+//! Transform plugins that run over the AST between parsing and rendering.
+//!
+//! ```
+//! use mdkit::transform::{ExternalLinks, HeadingShift, Pipeline};
+//!
+//! let mut pipeline = Pipeline::new();
+//! pipeline.add(HeadingShift(1)).add(ExternalLinks::default());
+//!
+//! let html = pipeline.render("# Title\n\n[Rust](https://rust-lang.org)");
+//! assert!(html.contains("<h2"));
+//! assert!(html.contains("rel='noopener noreferrer'"));
+//! ```
+
+use serde_wasm_bindgen::from_value;
+use wasm_bindgen::prelude::*;
+
+use crate::ast::visit::{
+    VisitorMut, walk_list_item_mut, walk_node_mut, walk_nodes_mut, walk_table_cell_mut,
+};
+use crate::ast::{ListItem, Node, TableCell};
+use crate::parser::{ParseOptions, Parser};
+use crate::renderer;
+use crate::to_js;
+
+pub trait Transform {
+    fn transform(&self, nodes: &mut Vec<Node>);
+}
+
+impl<F> Transform for F
+where
+    F: Fn(&mut Vec<Node>),
+{
+    fn transform(&self, nodes: &mut Vec<Node>) {
+        self(nodes)
+    }
+}
+
+/// Ordered list of transforms applied to a parsed document.
+#[derive(Default)]
+pub struct Pipeline {
+    options: ParseOptions,
+    transforms: Vec<Box<dyn Transform>>,
+}
+
+impl Pipeline {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A pipeline whose `parse` and `render` read input with `options`.
+    pub fn with_options(options: ParseOptions) -> Self {
+        Self {
+            options,
+            ..Self::default()
+        }
+    }
+
+    pub fn add<T: Transform + 'static>(&mut self, transform: T) -> &mut Self {
+        self.transforms.push(Box::new(transform));
+        self
+    }
+
+    pub fn apply(&self, nodes: &mut Vec<Node>) {
+        for transform in &self.transforms {
+            transform.transform(nodes);
+        }
+    }
+
+    pub fn parse(&self, input: &str) -> Vec<Node> {
+        let mut nodes = Parser::with_options(input, self.options).parse_document();
+        self.apply(&mut nodes);
+        nodes
+    }
+
+    pub fn render(&self, input: &str) -> String {
+        renderer::render(&self.parse(input))
+    }
+}
+
+/// Shifts every heading level by the given amount, clamped to `1..=6`.
+pub struct HeadingShift(pub i32);
+
+impl Transform for HeadingShift {
+    fn transform(&self, nodes: &mut Vec<Node>) {
+        struct Shift(i32);
+
+        impl VisitorMut for Shift {
+            fn visit_node_mut(&mut self, node: &mut Node) {
+                if let Node::Heading { level, .. } = node {
+                    *level = (*level as i32 + self.0).clamp(1, 6) as usize;
+                }
+                walk_node_mut(self, node);
+            }
+        }
+
+        walk_nodes_mut(&mut Shift(self.0), nodes);
+    }
+}
+
+/// Adds `rel` and `target` attributes to links pointing off-site.
+pub struct ExternalLinks {
+    pub rel: String,
+    pub target: Option<String>,
+    /// Hosts treated as internal even when linked with an absolute URL.
+    pub internal_hosts: Vec<String>,
+}
+
+impl Default for ExternalLinks {
+    fn default() -> Self {
+        Self {
+            rel: "noopener noreferrer".to_string(),
+            target: Some("_blank".to_string()),
+            internal_hosts: Vec::new(),
+        }
+    }
+}
+
+impl ExternalLinks {
+    fn is_external(&self, url: &str) -> bool {
+        let rest = match url
+            .strip_prefix("https://")
+            .or_else(|| url.strip_prefix("http://"))
+            .or_else(|| url.strip_prefix("//"))
+        {
+            Some(rest) => rest,
+            None => return false,
+        };
+
+        let host = rest.split(['/', '?', '#']).next().unwrap_or("");
+        !self.internal_hosts.iter().any(|internal| internal == host)
+    }
+}
+
+impl Transform for ExternalLinks {
+    fn transform(&self, nodes: &mut Vec<Node>) {
+        struct Mark<'a>(&'a ExternalLinks);
+
+        impl VisitorMut for Mark<'_> {
+            fn visit_node_mut(&mut self, node: &mut Node) {
+                if let Node::Link {
+                    url, attributes, ..
+                } = node
+                    && self.0.is_external(url)
+                {
+                    attributes.insert("rel".to_string(), self.0.rel.clone());
+                    if let Some(target) = &self.0.target {
+                        attributes.insert("target".to_string(), target.clone());
+                    }
+                }
+                walk_node_mut(self, node);
+            }
+        }
+
+        walk_nodes_mut(&mut Mark(self), nodes);
+    }
+}
+
+/// Marks every image with `loading="lazy"` and `decoding="async"`.
+pub struct LazyImages;
+
+impl Transform for LazyImages {
+    fn transform(&self, nodes: &mut Vec<Node>) {
+        struct Lazy;
+
+        impl VisitorMut for Lazy {
+            fn visit_node_mut(&mut self, node: &mut Node) {
+                if let Node::Image { attributes, .. } = node {
+                    attributes.insert("loading".to_string(), "lazy".to_string());
+                    attributes.insert("decoding".to_string(), "async".to_string());
+                }
+                walk_node_mut(self, node);
+            }
+        }
+
+        walk_nodes_mut(&mut Lazy, nodes);
+    }
+}
+
+/// Replaces straight quotes in text with typographic ones, in the English
+/// style. See [`SmartPunctuation`] for other styles, dashes and ellipses.
+pub struct SmartQuotes;
+
+impl Transform for SmartQuotes {
+    fn transform(&self, nodes: &mut Vec<Node>) {
+        SmartPunctuation {
+            dashes: false,
+            ellipses: false,
+            ..SmartPunctuation::default()
+        }
+        .transform(nodes);
+    }
+}
+
+/// Opening and closing marks for quotes, and for quotes within quotes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QuoteStyle {
+    pub double: [&'static str; 2],
+    pub single: [&'static str; 2],
+}
+
+impl QuoteStyle {
+    /// “…” and ‘…’
+    pub const ENGLISH: Self = Self::new(["\u{201C}", "\u{201D}"], ["\u{2018}", "\u{2019}"]);
+    /// „…“ and ‚…‘
+    pub const GERMAN: Self = Self::new(["\u{201E}", "\u{201C}"], ["\u{201A}", "\u{2018}"]);
+    /// «…» and ‹…›, as in Switzerland.
+    pub const SWISS: Self = Self::new(["\u{AB}", "\u{BB}"], ["\u{2039}", "\u{203A}"]);
+    /// « … » with no-break spaces, and “…”.
+    pub const FRENCH: Self = Self::new(["\u{AB}\u{A0}", "\u{A0}\u{BB}"], ["\u{201C}", "\u{201D}"]);
+    /// «…» and “…”, as in Spanish and Italian.
+    pub const SPANISH: Self = Self::new(["\u{AB}", "\u{BB}"], ["\u{201C}", "\u{201D}"]);
+    /// «…» and „…“
+    pub const RUSSIAN: Self = Self::new(["\u{AB}", "\u{BB}"], ["\u{201E}", "\u{201C}"]);
+    /// „…” and «…»
+    pub const POLISH: Self = Self::new(["\u{201E}", "\u{201D}"], ["\u{AB}", "\u{BB}"]);
+    /// »…« and ›…‹
+    pub const DANISH: Self = Self::new(["\u{BB}", "\u{AB}"], ["\u{203A}", "\u{2039}"]);
+    /// ”…” and ’…’
+    pub const SWEDISH: Self = Self::new(["\u{201D}", "\u{201D}"], ["\u{2019}", "\u{2019}"]);
+    /// 「…」 and 『…』
+    pub const CJK: Self = Self::new(["\u{300C}", "\u{300D}"], ["\u{300E}", "\u{300F}"]);
+
+    pub const fn new(double: [&'static str; 2], single: [&'static str; 2]) -> Self {
+        Self { double, single }
+    }
+
+    /// The style for a language tag such as `de`, `fr-CH` or `zh_Hant`.
+    pub fn for_locale(locale: &str) -> Option<Self> {
+        let tag = locale.to_ascii_lowercase().replace('_', "-");
+        let mut subtags = tag.split('-');
+        let language = subtags.next()?;
+        let subtags: Vec<&str> = subtags.collect();
+        let has = |subtag: &str| subtags.contains(&subtag);
+
+        Some(match language {
+            "de" | "fr" | "it" | "rm" if has("ch") || has("li") => Self::SWISS,
+            "zh" if has("hant") || has("tw") || has("hk") || has("mo") => Self::CJK,
+            "en" | "nl" | "tr" | "ga" | "ko" | "zh" => Self::ENGLISH,
+            "de" | "cs" | "sk" | "sl" | "is" | "lt" => Self::GERMAN,
+            "fr" => Self::FRENCH,
+            "es" | "it" | "pt" | "ca" | "el" | "no" | "nb" | "nn" => Self::SPANISH,
+            "ru" | "uk" | "be" => Self::RUSSIAN,
+            "pl" | "ro" | "hu" => Self::POLISH,
+            "da" => Self::DANISH,
+            "sv" | "fi" => Self::SWEDISH,
+            "ja" => Self::CJK,
+            _ => return None,
+        })
+    }
+}
+
+impl Default for QuoteStyle {
+    fn default() -> Self {
+        Self::ENGLISH
+    }
+}
+
+/// Typographic quotes, dashes and ellipses in text; code and math keep
+/// their characters. `--` becomes an en dash, `---` an em dash and `...`
+/// an ellipsis, and an apostrophe within a word is always ’.
+///
+/// ```
+/// use mdkit::transform::{Pipeline, SmartPunctuation};
+///
+/// let mut pipeline = Pipeline::new();
+/// pipeline.add(SmartPunctuation::locale("de").unwrap());
+///
+/// let html = pipeline.render("\"Ja\" -- sagte sie...");
+/// assert_eq!(html, "<p>\u{201E}Ja\u{201C} \u{2013} sagte sie\u{2026}</p>\n");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SmartPunctuation {
+    pub quotes: QuoteStyle,
+    pub dashes: bool,
+    pub ellipses: bool,
+}
+
+impl Default for SmartPunctuation {
+    fn default() -> Self {
+        Self {
+            quotes: QuoteStyle::ENGLISH,
+            dashes: true,
+            ellipses: true,
+        }
+    }
+}
+
+impl SmartPunctuation {
+    /// Quotes in the style of `locale`, see [`QuoteStyle::for_locale`].
+    pub fn locale(locale: &str) -> Option<Self> {
+        Some(Self {
+            quotes: QuoteStyle::for_locale(locale)?,
+            ..Self::default()
+        })
+    }
+}
+
+impl Transform for SmartPunctuation {
+    fn transform(&self, nodes: &mut Vec<Node>) {
+        struct Punctuation<'a> {
+            options: &'a SmartPunctuation,
+            prev: char,
+            /// Whether the previous character opened a quote, so that a
+            /// quote right after it opens too.
+            opened: bool,
+        }
+
+        impl Punctuation<'_> {
+            fn text(&mut self, content: &str) -> String {
+                let chars: Vec<char> = content.chars().collect();
+                let style = self.options.quotes;
+                let mut out = String::with_capacity(content.len());
+                let mut i = 0;
+                while i < chars.len() {
+                    let ch = chars[i];
+                    let next = chars.get(i + 1).copied();
+                    let opening = self.opened
+                        || self.prev.is_whitespace()
+                        || "([{\u{2013}\u{2014}".contains(self.prev);
+                    let marks = match ch {
+                        '"' => Some(style.double),
+                        '\'' if self.prev.is_alphanumeric()
+                            && next.is_some_and(char::is_alphanumeric) =>
+                        {
+                            Some(["\u{2019}"; 2])
+                        }
+                        '\'' => Some(style.single),
+                        _ => None,
+                    };
+
+                    self.opened = false;
+                    let mut len = 1;
+                    if let Some([open, close]) = marks {
+                        out.push_str(if opening { open } else { close });
+                        self.opened = opening;
+                    } else if self.options.dashes && ch == '-' && next == Some('-') {
+                        if chars.get(i + 2) == Some(&'-') {
+                            out.push('\u{2014}');
+                            len = 3;
+                        } else {
+                            out.push('\u{2013}');
+                            len = 2;
+                        }
+                    } else if self.options.ellipses && chars[i..].starts_with(&['.'; 3]) {
+                        out.push('\u{2026}');
+                        len = 3;
+                    } else {
+                        out.push(ch);
+                    }
+                    self.prev = out.chars().next_back().unwrap_or(ch);
+                    i += len;
+                }
+                out
+            }
+
+            fn reset(&mut self, prev: char) {
+                self.prev = prev;
+                self.opened = false;
+            }
+        }
+
+        impl VisitorMut for Punctuation<'_> {
+            fn visit_node_mut(&mut self, node: &mut Node) {
+                match node {
+                    Node::Text { content } => *content = self.text(content),
+                    Node::InlineCode { .. }
+                    | Node::InlineMath { .. }
+                    | Node::Emoji { .. }
+                    | Node::WikiLink { .. }
+                    | Node::Mention { .. }
+                    | Node::IssueReference { .. }
+                    | Node::CommitReference { .. }
+                    | Node::Hashtag { .. } => self.reset('x'),
+                    Node::Bold { .. }
+                    | Node::Italic { .. }
+                    | Node::Strikethrough { .. }
+                    | Node::Underline { .. }
+                    | Node::Highlight { .. }
+                    | Node::Superscript { .. }
+                    | Node::Subscript { .. }
+                    | Node::Inserted { .. }
+                    | Node::Link { .. } => walk_node_mut(self, node),
+                    _ => {
+                        self.reset(' ');
+                        walk_node_mut(self, node);
+                    }
+                }
+            }
+
+            fn visit_list_item_mut(&mut self, item: &mut ListItem) {
+                self.reset(' ');
+                walk_list_item_mut(self, item);
+            }
+
+            fn visit_table_cell_mut(&mut self, cell: &mut TableCell) {
+                self.reset(' ');
+                walk_table_cell_mut(self, cell);
+            }
+        }
+
+        walk_nodes_mut(
+            &mut Punctuation {
+                options: self,
+                prev: ' ',
+                opened: false,
+            },
+            nodes,
+        );
+    }
+}
+
+enum JsStep {
+    Native(Box<dyn Transform>),
+    Callback(js_sys::Function),
+}
+
+/// JavaScript handle over a transform pipeline. Callbacks receive the AST
+/// and either return a new one or mutate it in place and return nothing.
+#[wasm_bindgen(js_name = Pipeline)]
+#[derive(Default)]
+pub struct JsPipeline {
+    steps: Vec<JsStep>,
+}
+
+#[wasm_bindgen(js_class = Pipeline)]
+impl JsPipeline {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[wasm_bindgen(js_name = shiftHeadings)]
+    pub fn shift_headings(&mut self, by: i32) {
+        self.steps.push(JsStep::Native(Box::new(HeadingShift(by))));
+    }
+
+    #[wasm_bindgen(js_name = markExternalLinks)]
+    pub fn mark_external_links(&mut self) {
+        self.steps
+            .push(JsStep::Native(Box::new(ExternalLinks::default())));
+    }
+
+    #[wasm_bindgen(js_name = lazyImages)]
+    pub fn lazy_images(&mut self) {
+        self.steps.push(JsStep::Native(Box::new(LazyImages)));
+    }
+
+    #[wasm_bindgen(js_name = smartQuotes)]
+    pub fn smart_quotes(&mut self) {
+        self.steps.push(JsStep::Native(Box::new(SmartQuotes)));
+    }
+
+    /// Quotes, dashes and ellipses, with quotes in the style of `locale`.
+    #[wasm_bindgen(js_name = smartPunctuation)]
+    pub fn smart_punctuation(&mut self, locale: Option<String>) -> Result<(), JsValue> {
+        let punctuation = match locale {
+            Some(locale) => SmartPunctuation::locale(&locale)
+                .ok_or_else(|| JsValue::from_str(&format!("unknown locale {:?}", locale)))?,
+            None => SmartPunctuation::default(),
+        };
+        self.steps.push(JsStep::Native(Box::new(punctuation)));
+        Ok(())
+    }
+
+    #[wasm_bindgen(js_name = use)]
+    pub fn use_callback(
+        &mut self,
+        #[wasm_bindgen(unchecked_param_type = "(ast: Node[]) => Node[] | void")]
+        callback: js_sys::Function,
+    ) {
+        self.steps.push(JsStep::Callback(callback));
+    }
+
+    #[wasm_bindgen(js_name = toAst, unchecked_return_type = "Node[]")]
+    pub fn to_ast(
+        &self,
+        input: &str,
+        #[wasm_bindgen(unchecked_param_type = "ParseOptions | undefined")] options: JsValue,
+    ) -> Result<JsValue, JsValue> {
+        let nodes = self.run(input, options)?;
+        Ok(to_js(&nodes)?)
+    }
+
+    #[wasm_bindgen(js_name = toHtml)]
+    pub fn to_html(
+        &self,
+        input: &str,
+        #[wasm_bindgen(unchecked_param_type = "ParseOptions | undefined")] options: JsValue,
+    ) -> Result<String, JsValue> {
+        let nodes = self.run(input, options)?;
+        Ok(renderer::render(&nodes))
+    }
+}
+
+impl JsPipeline {
+    fn run(&self, input: &str, options: JsValue) -> Result<Vec<Node>, JsValue> {
+        let options = crate::options_from_js(options)?;
+        let mut nodes = Parser::with_options(input, options).parse_document();
+
+        for step in &self.steps {
+            match step {
+                JsStep::Native(transform) => transform.transform(&mut nodes),
+                JsStep::Callback(callback) => {
+                    let ast = to_js(&nodes)?;
+                    let returned = callback.call1(&JsValue::NULL, &ast)?;
+                    let result = if returned.is_undefined() {
+                        ast
+                    } else {
+                        returned
+                    };
+
+                    nodes = from_value(result)?;
+                    crate::ast::validate(&nodes)
+                        .map_err(|err| JsValue::from_str(&err.to_string()))?;
+                }
+            }
+        }
+
+        Ok(nodes)
+    }
+}
@@ -0,0 +1,158 @@
+//! Wiki links between pages of a knowledge base.
+//!
+//! [`page_url`] is the default target of a `[[Page]]` link. A [`LinkGraph`]
+//! collects the wiki links of many pages and reports, per page, the pages
+//! it links to, the pages linking back and the targets that do not exist,
+//! as well as the orphans no other page links to. Page names match
+//! case-insensitively, as in Obsidian.
+//!
+//! ```
+//! use mdkit::wiki::LinkGraph;
+//!
+//! let mut graph = LinkGraph::new();
+//! graph.add("Home", "See [[Rust]] and [[Go|golang]].");
+//! graph.add("Rust", "Back to [[home#intro]].");
+//! graph.add("Drafts", "Nothing links here.");
+//!
+//! let pages = graph.pages();
+//! assert_eq!(pages["Rust"].backlinks, vec!["Home"]);
+//! assert_eq!(pages["Home"].missing, vec!["Go"]);
+//! assert_eq!(graph.orphans(), vec!["Drafts"]);
+//! ```
+
+use std::collections::{BTreeMap, HashMap};
+
+use serde::{Serialize, Serializer};
+
+use crate::ast::Node;
+use crate::ast::visit::{Visitor, walk_node, walk_nodes};
+use crate::parser::{ParseOptions, Parser};
+
+/// URL of the page `mdkit site` writes for `<page>.md`, with characters
+/// other than letters, digits and `-_.~/` percent-encoded.
+pub fn page_url(page: &str) -> String {
+    let mut url = String::new();
+    for c in page.chars() {
+        if c.is_alphanumeric() || "-_.~/".contains(c) {
+            url.push(c);
+        } else {
+            for byte in c.encode_utf8(&mut [0; 4]).bytes() {
+                url.push_str(&format!("%{:02X}", byte));
+            }
+        }
+    }
+    url + ".html"
+}
+
+/// Wiki links of one page within a [`LinkGraph`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct PageLinks {
+    /// Pages this page links to, in document order.
+    pub links: Vec<String>,
+    /// Other pages linking to this one, by name.
+    pub backlinks: Vec<String>,
+    /// Link targets that are not a page, as written.
+    pub missing: Vec<String>,
+}
+
+/// Wiki link targets of a set of pages. Serializes to its
+/// [`pages`](Self::pages) and [`orphans`](Self::orphans).
+#[derive(Debug, Clone, Default)]
+pub struct LinkGraph {
+    targets: BTreeMap<String, Vec<String>>,
+}
+
+impl LinkGraph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a page under the name other pages link to it by, parsing its
+    /// wiki links. Links to a heading of the same page are ignored.
+    pub fn add(&mut self, name: &str, text: &str) -> &mut Self {
+        struct Targets(Vec<String>);
+
+        impl Visitor for Targets {
+            fn visit_node(&mut self, node: &Node) {
+                if let Node::WikiLink { target, .. } = node
+                    && !target.is_empty()
+                {
+                    self.0.push(target.clone());
+                }
+                walk_node(self, node);
+            }
+        }
+
+        let options = ParseOptions {
+            wiki_links: true,
+            ..ParseOptions::default()
+        };
+        let mut targets = Targets(Vec::new());
+        walk_nodes(
+            &mut targets,
+            &Parser::with_options(text, options).parse_document(),
+        );
+        self.targets.insert(name.to_string(), targets.0);
+        self
+    }
+
+    /// Links, backlinks and missing targets of every page, by name.
+    pub fn pages(&self) -> BTreeMap<String, PageLinks> {
+        let names: HashMap<String, &String> =
+            self.targets.keys().map(|name| (key(name), name)).collect();
+        let mut pages: BTreeMap<String, PageLinks> = self
+            .targets
+            .keys()
+            .map(|name| (name.clone(), PageLinks::default()))
+            .collect();
+
+        for (name, targets) in &self.targets {
+            for target in targets {
+                let Some(&page) = names.get(&key(target)) else {
+                    push_new(&mut pages.get_mut(name).unwrap().missing, target);
+                    continue;
+                };
+                push_new(&mut pages.get_mut(name).unwrap().links, page);
+                if page != name {
+                    push_new(&mut pages.get_mut(page).unwrap().backlinks, name);
+                }
+            }
+        }
+        pages
+    }
+
+    /// Pages no other page links to.
+    pub fn orphans(&self) -> Vec<String> {
+        self.pages()
+            .into_iter()
+            .filter(|(_, links)| links.backlinks.is_empty())
+            .map(|(name, _)| name)
+            .collect()
+    }
+}
+
+impl Serialize for LinkGraph {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        #[derive(Serialize)]
+        struct Graph {
+            pages: BTreeMap<String, PageLinks>,
+            orphans: Vec<String>,
+        }
+
+        Graph {
+            pages: self.pages(),
+            orphans: self.orphans(),
+        }
+        .serialize(serializer)
+    }
+}
+
+fn key(name: &str) -> String {
+    name.trim().to_lowercase()
+}
+
+fn push_new(list: &mut Vec<String>, name: &str) {
+    if !list.iter().any(|existing| existing == name) {
+        list.push(name.to_string());
+    }
+}
@@ -0,0 +1,148 @@
+#[cfg(test)]
+mod ast_tests {
+    use mdkit::ast::visit::{VisitorMut, walk_node_mut, walk_nodes_mut};
+    use mdkit::ast::{Node, validate};
+    use mdkit::markdown;
+    use mdkit::parser::Parser;
+    use mdkit::renderer::render;
+
+    #[test]
+    fn test_ast_json_roundtrip() {
+        let input = "# Title\n\n- [x] Done\n- [ ] **Todo**\n\n| A | B |\n|:---|---:|\n| 1 | 2 |";
+        let nodes = Parser::new(input).parse_document();
+
+        let json = serde_json::to_string(&nodes).unwrap();
+        let restored: Vec<Node> = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(nodes, restored);
+    }
+
+    #[test]
+    fn test_ast_from_client_json() {
+        let json = r#"[
+            {"type": "heading", "level": 2, "id": "intro", "children": [{"type": "text", "content": "Intro"}]},
+            {"type": "paragraph", "children": [{"type": "link", "url": "/docs", "text": [{"type": "text", "content": "Docs"}]}]}
+        ]"#;
+        let nodes: Vec<Node> = serde_json::from_str(json).unwrap();
+        let html = render(&nodes);
+
+        assert!(html.contains("<h2 id=\"intro\">Intro</h2>"));
+        assert!(html.contains("<a href='/docs'>Docs</a>"));
+    }
+
+    #[test]
+    fn test_malformed_ast_is_rejected() {
+        let unknown_type = r#"[{"type": "marquee", "children": []}]"#;
+        assert!(serde_json::from_str::<Vec<Node>>(unknown_type).is_err());
+
+        let missing_field = r#"[{"type": "heading", "level": 1}]"#;
+        assert!(serde_json::from_str::<Vec<Node>>(missing_field).is_err());
+
+        let bad_level = r#"[{"type": "paragraph", "children": [
+            {"type": "bold", "children": []},
+            {"type": "heading", "level": 9, "id": "x", "children": []}
+        ]}]"#;
+        let nodes: Vec<Node> = serde_json::from_str(bad_level).unwrap();
+        let err = validate(&nodes).unwrap_err();
+
+        assert_eq!(err.path, "nodes[0].children[1]");
+    }
+
+    #[test]
+    fn test_markdown_roundtrip() {
+        let input = "## Setup\n\nSome **bold** and *italic* with `code` and $x^2$.\n\n1. First\n2. [Link](https://example.com)\n  - Nested\n\n> Quote\n\n```rust main.rs\nfn main() {}\n```\n\n:::note kind=\"info\"\nInside\n:::";
+        let nodes = Parser::new(input).parse_document();
+
+        let markdown = markdown::render(&nodes);
+        let reparsed = Parser::new(&markdown).parse_document();
+
+        assert_eq!(nodes, reparsed);
+    }
+
+    #[test]
+    fn test_bare_fences_are_text() {
+        let nodes = Parser::new(":::\nNot a block\n:::\n\n:::{.x}").parse_document();
+        assert!(validate(&nodes).is_ok());
+        assert!(
+            matches!(&nodes[0], Node::Paragraph { children } if children[0] == Node::Text {
+                content: ":::".to_string()
+            })
+        );
+
+        let markdown = markdown::render(&nodes);
+        assert_eq!(Parser::new(&markdown).parse_document(), nodes);
+    }
+
+    #[test]
+    fn test_edited_text_roundtrips() {
+        struct Edit<'a>(std::slice::Iter<'a, &'a str>);
+
+        impl VisitorMut for Edit<'_> {
+            fn visit_node_mut(&mut self, node: &mut Node) {
+                if let Node::Text { content } = node
+                    && let Some(text) = self.0.next()
+                {
+                    *content = text.to_string();
+                }
+                walk_node_mut(self, node);
+            }
+        }
+
+        let texts = [
+            "*not italic*, [x](y), `tick`, $5, ~a~, ^b^ and a\\b",
+            "# not a heading",
+            "> not a quote",
+            "- not a list",
+            "1. not a list",
+            "| not a table |",
+            ":::note",
+            "---",
+            "C++, a==b, x=, 10:30: :smile: and :a{b}",
+            "snake_case, __init__ and _",
+            "a | b",
+            "c \\| d",
+        ];
+        let input = "Text\n\n".repeat(9) + "__Text__\n\n| Head | B |\n|---|---|";
+        let mut nodes = Parser::new(&input).parse_document();
+        walk_nodes_mut(&mut Edit(texts.iter()), &mut nodes);
+
+        let markdown = markdown::render(&nodes);
+        assert!(markdown.starts_with("\\*not italic\\*, \\[x\\](y), \\`tick\\`, \\$5"));
+        assert!(markdown.contains("\n\n\\# not a heading\n\n"));
+        assert_eq!(Parser::new(&markdown).parse_document(), nodes);
+    }
+
+    #[test]
+    fn test_line_breaks_and_spaces_roundtrip() {
+        let text = |content: &str| Node::Text {
+            content: content.to_string(),
+        };
+        let nodes = vec![
+            Node::Paragraph {
+                children: vec![text("a"), Node::LineBreak, text("(b) and - c ")],
+            },
+            Node::Paragraph {
+                children: vec![text("d"), Node::LineBreak, text("# e"), Node::LineBreak],
+            },
+            Node::Paragraph {
+                children: vec![
+                    text("trailing "),
+                    Node::Bold {
+                        children: vec![text("f")],
+                    },
+                    text(" "),
+                ],
+            },
+        ];
+
+        let markdown = markdown::render(&nodes);
+        assert!(markdown.starts_with("a\\\n(b) and - c \n\nd\\\n\\# e\\\n\n"));
+        assert_eq!(Parser::new(&markdown).parse_document(), nodes);
+
+        let input = "- one\\\n  - still one\n- two\\\n- x\\y\n\n> q\\\n> (r)";
+        let nodes = Parser::new(input).parse_document();
+        let markdown = markdown::render(&nodes);
+        assert!(markdown.contains("- x\n  y\n"));
+        assert_eq!(Parser::new(&markdown).parse_document(), nodes);
+    }
+}
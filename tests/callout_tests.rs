@@ -0,0 +1,70 @@
+#[cfg(test)]
+mod callout_tests {
+    use mdkit::ast::{Fold, Node};
+    use mdkit::parser::Parser;
+    use mdkit::{markdown, renderer};
+
+    #[test]
+    fn test_github_alert_renders_with_icon_and_classes() {
+        let nodes = Parser::new("> [!WARNING]\n> Mind the **gap**.").parse_document();
+        assert!(matches!(
+            &nodes[0],
+            Node::Callout { kind, title, foldable: None, .. }
+                if kind == "warning" && title.is_empty()
+        ));
+        assert_eq!(
+            renderer::render(&nodes),
+            "<div class='markdown-alert markdown-alert-warning'>\n\
+             <p class='markdown-alert-title'><span class='markdown-alert-icon' aria-hidden='true'>⚠️</span> Warning</p>\n\
+             <p>Mind the <strong>gap</strong>.</p>\n\
+             </div>\n"
+        );
+    }
+
+    #[test]
+    fn test_obsidian_foldable_callout_renders_details() {
+        let nodes = Parser::new("> [!info]- Read *more*\n> Hidden text.").parse_document();
+        let Node::Callout {
+            kind,
+            title,
+            foldable,
+            ..
+        } = &nodes[0]
+        else {
+            panic!("expected a callout, got {:?}", nodes[0]);
+        };
+        assert_eq!(kind, "info");
+        assert_eq!(*foldable, Some(Fold::Closed));
+        assert!(matches!(&title[1], Node::Italic { .. }));
+
+        let html = renderer::render(&nodes);
+        assert!(html.starts_with("<details class='markdown-alert markdown-alert-info'>\n<summary"));
+        assert!(html.contains("Read <em>more</em></summary>"));
+
+        let open = renderer::render(&Parser::new("> [!tip]+\n> Shown.").parse_document());
+        assert!(open.starts_with("<details class='markdown-alert markdown-alert-tip' open>"));
+    }
+
+    #[test]
+    fn test_marker_must_open_the_quote() {
+        for input in ["> Text\n> [!NOTE]", "> [!NOTE]title", "> [!not a kind]"] {
+            let nodes = Parser::new(input).parse_document();
+            assert!(
+                matches!(nodes[0], Node::BlockQuote { .. }),
+                "{:?} parsed as {:?}",
+                input,
+                nodes[0]
+            );
+        }
+    }
+
+    #[test]
+    fn test_markdown_round_trip() {
+        for input in ["> [!NOTE]\n> Body\n", "> [!faq]- Why?\n> Because.\n"] {
+            let nodes = Parser::new(input).parse_document();
+            let output = markdown::render(&nodes);
+            assert_eq!(output, input);
+            assert_eq!(Parser::new(&output).parse_document(), nodes);
+        }
+    }
+}
@@ -0,0 +1,135 @@
+#[cfg(all(test, feature = "cli"))]
+mod cli_tests {
+    use std::fs;
+    use std::io::Write;
+    use std::path::PathBuf;
+    use std::process::{Command, Output, Stdio};
+
+    fn mdkit(args: &[&str], stdin: &str) -> Output {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_mdkit"))
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .unwrap();
+        child
+            .stdin
+            .take()
+            .unwrap()
+            .write_all(stdin.as_bytes())
+            .unwrap();
+        child.wait_with_output().unwrap()
+    }
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("mdkit-cli-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_converts_stdin_to_stdout() {
+        let output = mdkit(&["html"], "# Hi *there*");
+        assert!(output.status.success());
+        assert_eq!(
+            String::from_utf8_lossy(&output.stdout),
+            "<h1 id=\"hi-there\">Hi <em>there</em></h1>\n"
+        );
+
+        let output = mdkit(&["toc", "-"], "# A\n\n## B\n");
+        assert_eq!(
+            String::from_utf8_lossy(&output.stdout),
+            "- [A](#a)\n  - [B](#b)\n"
+        );
+    }
+
+    #[test]
+    fn test_glob_into_out_dir_keeps_structure() {
+        let dir = scratch_dir("glob");
+        fs::create_dir_all(dir.join("docs/guide")).unwrap();
+        fs::write(dir.join("docs/index.md"), "# Index").unwrap();
+        fs::write(dir.join("docs/guide/start.md"), "# Start").unwrap();
+
+        let pattern = dir.join("docs/**/*.md");
+        let out = dir.join("out");
+        let output = mdkit(
+            &[
+                "html",
+                pattern.to_str().unwrap(),
+                "--out-dir",
+                out.to_str().unwrap(),
+            ],
+            "",
+        );
+
+        assert!(output.status.success());
+        assert!(
+            fs::read_to_string(out.join("index.html"))
+                .unwrap()
+                .contains("<h1")
+        );
+        assert!(
+            fs::read_to_string(out.join("guide/start.html"))
+                .unwrap()
+                .contains("Start")
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_lint_reports_and_fixes() {
+        let dir = scratch_dir("lint");
+        let file = dir.join("doc.md");
+        fs::write(&file, "# A\n\n### B\n").unwrap();
+
+        let output = mdkit(&["lint", file.to_str().unwrap()], "");
+        assert_eq!(output.status.code(), Some(1));
+        assert!(
+            String::from_utf8_lossy(&output.stdout)
+                .contains("doc.md:3:1: warning [heading-increment]")
+        );
+
+        let output = mdkit(&["lint", "--fix", file.to_str().unwrap()], "");
+        assert!(output.status.success());
+        assert_eq!(fs::read_to_string(&file).unwrap(), "# A\n\n## B\n");
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_links_reports_broken_links() {
+        let dir = scratch_dir("links");
+        fs::write(
+            dir.join("a.md"),
+            "# A\n\n[B](b.md#top) [C](c.md) [web](https://example.invalid)\n",
+        )
+        .unwrap();
+        fs::write(dir.join("b.md"), "# Top\n").unwrap();
+
+        let root = dir.to_str().unwrap();
+        let a = dir.join("a.md");
+        let output = mdkit(
+            &["links", "--root", root, "--external", a.to_str().unwrap()],
+            "",
+        );
+        assert_eq!(output.status.code(), Some(1));
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(stdout.contains("a.md:3:15: broken link `c.md`: `c.md` does not exist"));
+        assert!(stdout.contains("a.md:3:25: external `https://example.invalid`"));
+        assert!(!stdout.contains("b.md#top"));
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_fmt_check_and_missing_files() {
+        let output = mdkit(&["fmt", "--check"], "* a\n* b\n");
+        assert_eq!(output.status.code(), Some(1));
+
+        let output = mdkit(&["fmt"], "* a\n* b\n");
+        assert_eq!(String::from_utf8_lossy(&output.stdout), "- a\n- b\n");
+
+        let output = mdkit(&["html", "does-not-exist.md"], "");
+        assert_eq!(output.status.code(), Some(2));
+    }
+}
@@ -0,0 +1,91 @@
+#[cfg(test)]
+mod definition_list_tests {
+    use mdkit::ast::Node;
+    use mdkit::incremental::IncrementalDocument;
+    use mdkit::parser::Parser;
+    use mdkit::streaming::StreamingParser;
+    use mdkit::{markdown, renderer};
+
+    const INPUT: &str = "parse(input)\n: Parses a document.\n: Returns the **nodes**.\n\n    Blank lines keep indented paragraphs in the definition.\n\nrender(nodes)\n: Renders HTML.\n\nThe end.\n";
+
+    #[test]
+    fn test_terms_with_several_definitions() {
+        let nodes = Parser::new(INPUT).parse_document();
+        assert_eq!(nodes.len(), 2);
+
+        let Node::DefinitionList { children } = &nodes[0] else {
+            panic!("expected a definition list, got {:?}", nodes[0]);
+        };
+        let kinds: Vec<&str> = children
+            .iter()
+            .map(|child| match child {
+                Node::DefinitionTerm { .. } => "term",
+                Node::DefinitionDescription { .. } => "description",
+                _ => "other",
+            })
+            .collect();
+        assert_eq!(
+            kinds,
+            vec!["term", "description", "description", "term", "description"]
+        );
+        assert!(matches!(
+            &children[2],
+            Node::DefinitionDescription { children } if children.len() == 2
+        ));
+
+        assert_eq!(
+            renderer::render(&nodes),
+            "<dl>\n\
+             <dt>parse(input)</dt>\n\
+             <dd>Parses a document.</dd>\n\
+             <dd>\n<p>Returns the <strong>nodes</strong>.</p>\n<p>Blank lines keep indented paragraphs in the definition.</p>\n</dd>\n\
+             <dt>render(nodes)</dt>\n\
+             <dd>Renders HTML.</dd>\n\
+             </dl>\n\
+             <p>The end.</p>\n"
+        );
+    }
+
+    #[test]
+    fn test_definition_must_follow_its_term() {
+        for input in [
+            "Term\n\n: Not a definition",
+            ": No term",
+            "Term\n:Missing space",
+        ] {
+            let nodes = Parser::new(input).parse_document();
+            assert!(
+                nodes
+                    .iter()
+                    .all(|node| matches!(node, Node::Paragraph { .. })),
+                "{:?} parsed as {:?}",
+                input,
+                nodes
+            );
+        }
+    }
+
+    #[test]
+    fn test_markdown_round_trip() {
+        let nodes = Parser::new(INPUT).parse_document();
+        let output = markdown::render(&nodes);
+        assert_eq!(Parser::new(&output).parse_document(), nodes);
+    }
+
+    #[test]
+    fn test_streaming_and_editing_keep_lists_whole() {
+        let mut stream = StreamingParser::new();
+        for line in INPUT.split_inclusive('\n') {
+            stream.push(line);
+        }
+        stream.finish();
+        assert_eq!(stream.nodes(), Parser::new(INPUT).parse_document());
+
+        // Turning the last paragraph into a term extends the list above it.
+        let mut doc = IncrementalDocument::new(INPUT);
+        let end = INPUT.chars().count();
+        doc.edit(end..end, ": A closing definition.\n");
+        assert_eq!(doc.nodes(), Parser::new(&doc.text()).parse_document());
+        assert_eq!(doc.nodes().len(), 1);
+    }
+}
@@ -0,0 +1,68 @@
+#[cfg(test)]
+mod diagnostics_tests {
+    use mdkit::diagnostics::{Severity, line_col};
+    use mdkit::parser::Parser;
+
+    fn codes(input: &str) -> Vec<String> {
+        Parser::new(input)
+            .parse_with_diagnostics()
+            .diagnostics
+            .into_iter()
+            .map(|d| d.code)
+            .collect()
+    }
+
+    #[test]
+    fn test_clean_document_has_no_diagnostics() {
+        let input = "# Title\n\n| a | b |\n|---|---|\n| 1 | 2 |\n\n```rust\nfn main() {}\n```\n";
+        assert!(codes(input).is_empty());
+    }
+
+    #[test]
+    fn test_unclosed_constructs_are_reported() {
+        let result = Parser::new("Intro\n\n```rust\nlet x = 1;\n").parse_with_diagnostics();
+
+        assert_eq!(result.diagnostics.len(), 1);
+        let diagnostic = &result.diagnostics[0];
+        assert_eq!(diagnostic.code, "unclosed-code-fence");
+        assert_eq!(diagnostic.severity, Severity::Warning);
+        assert_eq!(diagnostic.span.start, 7);
+        assert_eq!(
+            line_col("Intro\n\n```rust\n", diagnostic.span.start),
+            (3, 1)
+        );
+
+        // The code block is still recovered.
+        assert_eq!(result.nodes.len(), 2);
+
+        assert_eq!(codes("$$\nx^2\n"), vec!["unclosed-math-block"]);
+        assert_eq!(codes(":::note\nBody\n"), vec!["unclosed-custom-block"]);
+    }
+
+    #[test]
+    fn test_table_column_mismatch_points_at_row() {
+        let input = "| a | b |\n|---|---|\n| 1 | 2 | 3 |\n";
+        let result = Parser::new(input).parse_with_diagnostics();
+
+        assert_eq!(result.diagnostics.len(), 1);
+        let diagnostic = &result.diagnostics[0];
+        assert_eq!(diagnostic.code, "table-column-mismatch");
+        assert_eq!(line_col(input, diagnostic.span.start), (3, 1));
+    }
+
+    #[test]
+    fn test_nested_diagnostics_map_to_document_offsets() {
+        let input = "Intro\n\n:::note\n```\ncode\n:::\n";
+        let result = Parser::new(input).parse_with_diagnostics();
+        let fence = result
+            .diagnostics
+            .iter()
+            .find(|d| d.code == "unclosed-code-fence")
+            .unwrap();
+        assert_eq!(line_col(input, fence.span.start), (4, 1));
+
+        let quoted = "> ```\n> code\n";
+        let result = Parser::new(quoted).parse_with_diagnostics();
+        assert_eq!(result.diagnostics[0].span.start, 0);
+    }
+}
@@ -0,0 +1,93 @@
+#[cfg(test)]
+mod diff_tests {
+    use mdkit::diff::{Change, TextEdit, diff, diff_text, render_redline};
+    use mdkit::parser::Parser;
+
+    fn parse(input: &str) -> Vec<mdkit::ast::Node> {
+        Parser::new(input).parse_document()
+    }
+
+    #[test]
+    fn test_identical_documents_have_no_changes() {
+        let doc = parse("# Title\n\n- a\n- b");
+        assert!(diff(&doc, &doc).is_empty());
+    }
+
+    #[test]
+    fn test_insert_delete_and_text_update() {
+        let old = parse("# Title\n\nThe quick fox\n\nGone");
+        let new = parse("# Title\n\nThe slow fox\n\n---");
+        let changes = diff(&old, &new);
+
+        assert!(changes.contains(&Change::Update {
+            old_path: vec![1, 0],
+            new_path: vec![1, 0],
+            text: Some(vec![
+                TextEdit::Equal("The ".to_string()),
+                TextEdit::Delete("quick".to_string()),
+                TextEdit::Insert("slow".to_string()),
+                TextEdit::Equal(" fox".to_string()),
+            ]),
+        }));
+        assert!(changes.contains(&Change::Delete { path: vec![2] }));
+        assert!(changes.contains(&Change::Insert { path: vec![2] }));
+    }
+
+    #[test]
+    fn test_move_and_list_item_changes() {
+        let old = parse("## A\n\n## B\n\n- [ ] task");
+        let new = parse("## B\n\n## A\n\n- [x] task");
+        let changes = diff(&old, &new);
+
+        assert!(changes.iter().any(|c| matches!(c, Change::Move { .. })));
+        assert!(changes.contains(&Change::Update {
+            old_path: vec![2, 0],
+            new_path: vec![2, 0],
+            text: None,
+        }));
+    }
+
+    #[test]
+    fn test_labels_and_titles_change_once() {
+        let old = parse(":::note[Old]\nBody\n:::\n\n> [!TIP] First\n> Text");
+        let new = parse(":::note[New]\nBody\n:::\n\n> [!TIP] Second\n> Text");
+        let changes = diff(&old, &new);
+
+        assert_eq!(changes.len(), 2);
+        assert!(changes.iter().all(|change| matches!(
+            change,
+            Change::Update { old_path, text: Some(_), .. } if old_path.len() == 2
+        )));
+    }
+
+    #[test]
+    fn test_large_inputs_diff_in_bounded_space() {
+        let old = "word ".repeat(100_000) + "old " + &"tail ".repeat(100_000);
+        let new = "word ".repeat(100_000) + "new " + &"tail ".repeat(100_000);
+        let edits = diff_text(&old, &new);
+        assert_eq!(edits[1], TextEdit::Delete("old".to_string()));
+        assert_eq!(edits[2], TextEdit::Insert("new".to_string()));
+
+        let old = "a ".repeat(50_000);
+        let new = "b ".repeat(50_000);
+        let edits = diff_text(&old, &new);
+        assert_eq!(edits.len(), 3);
+
+        let old = parse(&"Para\n\n".repeat(5_000));
+        let new = parse(&"Other\n\n".repeat(5_000));
+        assert_eq!(diff(&old, &new).len(), 5_000);
+    }
+
+    #[test]
+    fn test_redline_html() {
+        let old = parse("# Title\n\nHello world\n\nRemoved");
+        let new = parse("# Title\n\nHello there world\n\n> Added");
+        let html = render_redline(&old, &new);
+
+        assert!(html.contains("<h1 id=\"title\">Title</h1>"));
+        assert!(html.contains("<p>Hello <ins>there </ins>world</p>"));
+        assert!(html.contains("<del><p>Removed</p></del>"));
+        assert!(html.contains("<ins><blockquote>"));
+        assert_eq!(diff_text("a b", "a b").len(), 1);
+    }
+}
@@ -0,0 +1,106 @@
+#[cfg(test)]
+mod directive_handler_tests {
+    use mdkit::parser::Parser;
+    use mdkit::renderer::{self, Directive, DirectiveKind, Renderer};
+
+    fn render(input: &str) -> String {
+        let mut renderer = Renderer::new();
+        renderer.builtin_directives();
+        renderer.render(&Parser::new(input).parse_document())
+    }
+
+    #[test]
+    fn test_tabs_render_an_aria_tablist() {
+        let html =
+            render(":::tabs[Install]\n:::tab[Rust]\nCargo\n:::\n:::tab{title=npm}\nNode\n:::\n:::");
+
+        assert_eq!(
+            html,
+            "<div class='tabs'>\n\
+             <div role='tablist' aria-label='Install'>\n\
+             <button type='button' role='tab' id='tabs-1-tab-1' aria-controls='tabs-1-panel-1' aria-selected='true' tabindex='0'>Rust</button>\n\
+             <button type='button' role='tab' id='tabs-1-tab-2' aria-controls='tabs-1-panel-2' aria-selected='false' tabindex='-1'>npm</button>\n\
+             </div>\n\
+             <div role='tabpanel' id='tabs-1-panel-1' aria-labelledby='tabs-1-tab-1' tabindex='0'>\n<p>Cargo</p>\n</div>\n\
+             <div role='tabpanel' id='tabs-1-panel-2' aria-labelledby='tabs-1-tab-2' tabindex='0' hidden>\n<p>Node</p>\n</div>\n\
+             </div>\n"
+        );
+
+        let twice = render(":::tabs\n:::tab\nA\n:::\n:::\n\n:::tabs{#more}\n:::tab\nB\n:::\n:::");
+        assert!(twice.contains("id='tabs-1-tab-1'"));
+        assert!(twice.contains("id='more-tab-1'"));
+        assert!(twice.contains(">Tab 1</button>"));
+    }
+
+    #[test]
+    fn test_builtin_details_figure_and_youtube() {
+        assert_eq!(
+            render(":::details[Why *not*?]{open}\nBecause.\n:::"),
+            "<details class='details' open>\n<summary>Why <em>not</em>?</summary>\n<p>Because.</p>\n</details>\n"
+        );
+        assert_eq!(
+            render("::figure[A chart]{src=chart.png alt=\"Sales\"}"),
+            "<figure class='figure'>\n<img src='chart.png' alt='Sales' />\n<figcaption>A chart</figcaption>\n</figure>\n"
+        );
+        assert!(render("::youtube[Intro]{#dQw4w9WgXcQ}").contains(
+            "<iframe src='https://www.youtube-nocookie.com/embed/dQw4w9WgXcQ' title='Intro'"
+        ));
+
+        // Unsupported forms fall back to the default markup.
+        assert_eq!(
+            render("::youtube{id=\"x'><script>\"}"),
+            renderer::render(&Parser::new("::youtube{id=\"x'><script>\"}").parse_document())
+        );
+    }
+
+    #[test]
+    fn test_builtin_attributes_are_escaped() {
+        assert!(
+            render("::youtube[It's]{id=abc}")
+                .contains("embed/abc' title='It&#39;s' loading='lazy'")
+        );
+        assert!(
+            render("::figure{src=\"a'b.png\" alt=\"<x>\"}")
+                .contains("<img src='a&#39;b.png' alt='&lt;x&gt;' />")
+        );
+
+        let html = render(
+            ":::tabs[Rock 'n' roll]{#a&b}\n:::tab[One]\nFirst\n:::\n:::tab[Two]\nSecond\n:::\n:::",
+        );
+        assert!(html.contains("<div role='tablist' aria-label='Rock &#39;n&#39; roll'>"));
+        assert!(html.contains("id='a&amp;b-tab-2'"));
+        // Hidden panels are still in the markup for a script to reveal.
+        assert!(html.contains("tabindex='0' hidden>\n<p>Second</p>\n</div>"));
+    }
+
+    #[test]
+    fn test_user_handlers_and_fallback() {
+        let mut custom = Renderer::new();
+        custom
+            .directive("badge", |directive: &Directive, renderer: &Renderer| {
+                if directive.kind != DirectiveKind::Inline {
+                    return None;
+                }
+                Some(format!(
+                    "<span class='badge badge-{}'>{}</span>",
+                    directive.attributes.get("tone")?,
+                    renderer.render(directive.label)
+                ))
+            })
+            .directive("note", |_: &Directive, _: &Renderer| {
+                Some("<aside></aside>\n".to_string())
+            });
+
+        let nodes = Parser::new(
+            "A :badge[new]{tone=info} :badge[plain]\n\n:::note\nx\n:::\n\n::badge[leaf]{tone=x}\n\n:::other\ny\n:::",
+        )
+        .parse_document();
+        assert_eq!(
+            custom.render(&nodes),
+            "<p>A <span class='badge badge-info'>new</span> <span class='badge'>plain</span></p>\n\
+             <aside></aside>\n\
+             <div class='badge' data-tone='x'>leaf</div>\n\
+             <div class='other'><p>y</p>\n</div>\n"
+        );
+    }
+}
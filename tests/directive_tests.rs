@@ -0,0 +1,105 @@
+#[cfg(test)]
+mod directive_tests {
+    use std::collections::HashMap;
+
+    use mdkit::ast::Node;
+    use mdkit::parser::Parser;
+    use mdkit::{markdown, renderer};
+
+    fn attributes(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_inline_directive_with_label_and_attributes() {
+        let nodes = Parser::new("The :abbr[**HTML**]{title=\"HyperText Markup\"} spec, note:x{y}")
+            .parse_document();
+        let Node::Paragraph { children } = &nodes[0] else {
+            panic!("expected a paragraph, got {:?}", nodes[0]);
+        };
+
+        let Node::InlineDirective {
+            name,
+            label,
+            attributes: attrs,
+        } = &children[1]
+        else {
+            panic!("expected a directive, got {:?}", children[1]);
+        };
+        assert_eq!(name, "abbr");
+        assert!(matches!(label[0], Node::Bold { .. }));
+        assert_eq!(*attrs, attributes(&[("title", "HyperText Markup")]));
+        assert!(matches!(&children[2], Node::Text { content } if content == " spec, note:x{y}"));
+
+        assert_eq!(
+            renderer::render(&nodes),
+            "<p>The <span class='abbr' data-title='HyperText Markup'><strong>HTML</strong></span> spec, note:x{y}</p>\n"
+        );
+    }
+
+    #[test]
+    fn test_leaf_directive_needs_its_own_line() {
+        let nodes = Parser::new("::youtube{#intro .wide .dark id2=abc}\n\n::youtube{id=abc} text")
+            .parse_document();
+
+        assert_eq!(
+            nodes[0],
+            Node::LeafDirective {
+                name: "youtube".to_string(),
+                label: Vec::new(),
+                attributes: attributes(&[("id", "intro"), ("class", "wide dark"), ("id2", "abc")]),
+            }
+        );
+        assert!(matches!(nodes[1], Node::Paragraph { .. }));
+        assert_eq!(
+            renderer::render(&nodes[..1]),
+            "<div class='youtube wide dark' id='intro' data-id2='abc'></div>\n"
+        );
+    }
+
+    #[test]
+    fn test_container_label_and_quoted_attributes() {
+        let input = ":::tab[Tab *One*]{.active}\nBody\n:::\n\n:::tab title=\"Tab Two\" lang='en gb'\nMore\n:::";
+        let nodes = Parser::new(input).parse_document();
+
+        let Node::CustomBlock {
+            label,
+            attributes: attrs,
+            ..
+        } = &nodes[0]
+        else {
+            panic!("expected a custom block, got {:?}", nodes[0]);
+        };
+        assert!(matches!(label[1], Node::Italic { .. }));
+        assert_eq!(*attrs, attributes(&[("class", "active")]));
+        assert!(matches!(
+            &nodes[1],
+            Node::CustomBlock { attributes: attrs, .. }
+                if *attrs == attributes(&[("title", "Tab Two"), ("lang", "en gb")])
+        ));
+    }
+
+    #[test]
+    fn test_markdown_round_trip() {
+        let input = "See :kbd[Ctrl]{#save .key title=\"Save file\"} now\n\n::embed[Demo]{src=\"a b.mp4\"}\n\n:::tabs[All] group=\"x y\"\n::tab[One]\n:::";
+        let nodes = Parser::new(input).parse_document();
+        let output = markdown::render(&nodes);
+
+        assert!(output.contains(":kbd[Ctrl]{#save .key title=\"Save file\"}"));
+        assert_eq!(Parser::new(&output).parse_document(), nodes);
+    }
+
+    #[test]
+    fn test_attribute_values_are_escaped() {
+        let input = ":::note title=\"Don't panic\" #a&b .x<y>\nBody\n:::\n\nSee :abbr[it]{title=\"x' onclick='y\"}";
+        let html = renderer::render(&Parser::new(input).parse_document());
+
+        assert!(html.starts_with(
+            "<div class='note x&lt;y&gt;' id='a&amp;b' data-title='Don&#39;t panic'>"
+        ));
+        assert!(html.contains("<span class='abbr' data-title='x&#39; onclick=&#39;y'>it</span>"));
+    }
+}
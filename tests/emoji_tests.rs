@@ -0,0 +1,95 @@
+#[cfg(test)]
+mod emoji_tests {
+    use mdkit::ast::{Node, validate};
+    use mdkit::emoji;
+    use mdkit::markdown;
+    use mdkit::parser::Parser;
+    use mdkit::renderer::{Renderer, render};
+
+    fn rocket() -> Node {
+        Node::Emoji {
+            shortcode: "rocket".to_string(),
+            unicode: "\u{1F680}".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_emoji_render_as_characters_or_images() {
+        let nodes = vec![Node::Paragraph {
+            children: vec![
+                Node::Text {
+                    content: "Launch ".to_string(),
+                },
+                rocket(),
+            ],
+        }];
+        assert_eq!(render(&nodes), "<p>Launch \u{1F680}</p>\n");
+        assert_eq!(markdown::render(&nodes), "Launch :rocket:\n");
+
+        let mut renderer = Renderer::new();
+        renderer.emoji_images("/emoji/{shortcode}-{codepoints}.png");
+        assert_eq!(
+            renderer.render(&nodes),
+            "<p>Launch <img class='emoji' src='/emoji/rocket-1f680.png' alt='\u{1F680}' title=':rocket:' /></p>\n"
+        );
+
+        let invalid = vec![Node::Emoji {
+            shortcode: "Rocket!".to_string(),
+            unicode: "\u{1F680}".to_string(),
+        }];
+        assert!(validate(&invalid).is_err());
+    }
+
+    #[cfg(feature = "emoji")]
+    #[test]
+    fn test_known_shortcodes_parse() {
+        let input = "Ship it :rocket: :+1: at 10:30: but not :no_such_emoji:\n";
+        let nodes = Parser::new(input).parse_document();
+
+        assert_eq!(
+            nodes,
+            vec![Node::Paragraph {
+                children: vec![
+                    Node::Text {
+                        content: "Ship it ".to_string()
+                    },
+                    rocket(),
+                    Node::Text {
+                        content: " ".to_string()
+                    },
+                    Node::Emoji {
+                        shortcode: "+1".to_string(),
+                        unicode: "\u{1F44D}".to_string(),
+                    },
+                    Node::Text {
+                        content: " at 10:30: but not :no_such_emoji:".to_string()
+                    },
+                ],
+            }]
+        );
+        assert_eq!(markdown::render(&nodes), input);
+    }
+
+    #[cfg(feature = "emoji")]
+    #[test]
+    fn test_table_is_sorted_and_valid() {
+        let table = emoji::shortcodes();
+
+        assert!(table.windows(2).all(|pair| pair[0].0 < pair[1].0));
+        for &(shortcode, unicode) in table {
+            assert!(emoji::is_shortcode(shortcode), "{}", shortcode);
+            assert_eq!(emoji::lookup(shortcode), Some(unicode));
+        }
+        assert_eq!(emoji::codepoints(emoji::lookup("heart").unwrap()), "2764");
+    }
+
+    #[cfg(not(feature = "emoji"))]
+    #[test]
+    fn test_shortcodes_stay_text_without_the_feature() {
+        assert!(emoji::lookup("rocket").is_none());
+        assert_eq!(
+            render(&Parser::new(":rocket:").parse_document()),
+            "<p>:rocket:</p>\n"
+        );
+    }
+}
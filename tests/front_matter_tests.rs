@@ -0,0 +1,122 @@
+#[cfg(test)]
+mod front_matter_tests {
+    use mdkit::ast::{Document, FrontMatterFormat, Node};
+    use mdkit::parser::{ParseOptions, Parser};
+    use mdkit::streaming::StreamingParser;
+    use mdkit::{markdown, renderer};
+    use serde_json::json;
+
+    #[test]
+    fn test_yaml_front_matter_becomes_metadata() {
+        let input = "---\ntitle: Guide\ntags: [intro, setup]\n---\n# Guide\n";
+        let nodes = Parser::new(input).parse_document();
+
+        assert!(matches!(
+            &nodes[0],
+            Node::FrontMatter { format: FrontMatterFormat::Yaml, raw, .. }
+                if raw == "title: Guide\ntags: [intro, setup]"
+        ));
+        assert!(matches!(&nodes[1], Node::Heading { id, .. } if id == "guide"));
+        assert_eq!(renderer::render(&nodes), "<h1 id=\"guide\">Guide</h1>\n");
+        assert_eq!(
+            markdown::render(&nodes),
+            "---\ntitle: Guide\ntags: [intro, setup]\n---\n\n# Guide\n"
+        );
+
+        let document = Document::new(nodes);
+        assert_eq!(document.metadata_str("title"), Some("Guide"));
+        assert_eq!(document.metadata["tags"], json!(["intro", "setup"]));
+    }
+
+    #[test]
+    fn test_toml_front_matter() {
+        let input =
+            "+++\ntitle = \"Notes\"\ndate = 2024-05-01\n\n[extra]\ndraft = true\n+++\n\nText";
+        let document = Document::new(Parser::new(input).parse_document());
+
+        assert_eq!(
+            serde_json::Value::Object(document.metadata),
+            json!({ "title": "Notes", "date": "2024-05-01", "extra": { "draft": true } })
+        );
+        assert_eq!(document.children.len(), 2);
+    }
+
+    #[test]
+    fn test_only_closed_leading_blocks_are_front_matter() {
+        let result = Parser::new("---\n: [\n---\n").parse_with_diagnostics();
+        assert_eq!(result.nodes[0], Node::HorizontalRule);
+        assert_eq!(result.diagnostics[0].code, "invalid-front-matter");
+        assert_eq!(result.diagnostics[0].span, 0..12);
+
+        let unclosed = Parser::new("---\ntitle: x\n").parse_document();
+        assert_eq!(unclosed[0], Node::HorizontalRule);
+
+        let later = Parser::new("Text\n\n---\ntitle: x\n---\n").parse_document();
+        assert!(
+            !later
+                .iter()
+                .any(|node| matches!(node, Node::FrontMatter { .. }))
+        );
+
+        let options = ParseOptions {
+            front_matter: false,
+            ..ParseOptions::default()
+        };
+        let disabled = Parser::with_options("---\ntitle: x\n---\n", options).parse_document();
+        assert_eq!(disabled[0], Node::HorizontalRule);
+    }
+
+    #[test]
+    fn test_text_between_rules_is_not_front_matter() {
+        let nodes = Parser::new("---\nJust an intro.\n---\n\nBody").parse_document();
+
+        assert_eq!(nodes.len(), 4);
+        assert_eq!(nodes[0], Node::HorizontalRule);
+        assert_eq!(
+            renderer::render(&nodes),
+            "<hr />\n<p>Just an intro.</p>\n<hr />\n<p>Body</p>\n"
+        );
+
+        let empty = Parser::new("---\n---\n").parse_document();
+        assert!(matches!(&empty[0], Node::FrontMatter { metadata, .. } if metadata.is_empty()));
+    }
+
+    #[test]
+    fn test_blocks_that_decode_to_null_stay_content() {
+        let nodes = Parser::new("---\n# Intro\n---\nBody").parse_document();
+        assert_eq!(
+            renderer::render(&nodes),
+            "<hr />\n<h1 id=\"intro\">Intro</h1>\n<hr />\n<p>Body</p>\n"
+        );
+        assert_eq!(
+            Parser::new(&markdown::render(&nodes)).parse_document(),
+            nodes
+        );
+
+        for input in ["---\n#just a comment\n---\n", "---\n\n---\n"] {
+            let nodes = Parser::new(input).parse_document();
+            assert_eq!(nodes[0], Node::HorizontalRule, "{:?}", input);
+        }
+    }
+
+    #[test]
+    fn test_streaming_keeps_front_matter_whole() {
+        let mut stream = StreamingParser::new();
+        stream.push("---\ntitle: Post\n\ntags: [a]\n");
+        stream.push("---\n\nBody\n\n---\nnot: metadata\n---\n\n");
+        stream.finish();
+
+        let nodes = stream.nodes();
+        assert!(matches!(
+            &nodes[0],
+            Node::FrontMatter { metadata, .. } if metadata["tags"] == json!(["a"])
+        ));
+        assert_eq!(
+            nodes
+                .iter()
+                .filter(|node| matches!(node, Node::FrontMatter { .. }))
+                .count(),
+            1
+        );
+    }
+}
@@ -0,0 +1,80 @@
+#[cfg(test)]
+mod incremental_tests {
+    use mdkit::incremental::{BlockChange, IncrementalDocument};
+    use mdkit::parser::Parser;
+
+    fn assert_matches_full_parse(doc: &IncrementalDocument) {
+        let expected = Parser::new(&doc.text()).parse_blocks();
+        assert_eq!(doc.blocks(), expected.as_slice());
+    }
+
+    fn offset(doc: &IncrementalDocument, needle: &str) -> usize {
+        let text = doc.text();
+        text[..text.find(needle).unwrap()].chars().count()
+    }
+
+    #[test]
+    fn test_edit_reparses_single_block() {
+        let mut doc = IncrementalDocument::new("# Title\n\nAlpha\n\n- a\n- b\n\nOmega\n");
+        let at = offset(&doc, "Alpha");
+        let change = doc.edit(at..at + 5, "Beta **bold**");
+
+        assert_eq!(
+            change,
+            BlockChange {
+                start: 1,
+                removed: 1,
+                inserted: 1
+            }
+        );
+        assert_matches_full_parse(&doc);
+    }
+
+    #[test]
+    fn test_opening_fence_extends_to_following_blocks() {
+        let mut doc = IncrementalDocument::new("Intro\n\nOne\n\nTwo\n\n```\nold\n```\n\nAfter\n");
+        let at = offset(&doc, "One");
+        let change = doc.edit(at..at, "```\n");
+
+        assert!(change.inserted < change.removed);
+        assert_matches_full_parse(&doc);
+
+        let at = offset(&doc, "```\nOne");
+        doc.edit(at..at + 4, "");
+        assert_matches_full_parse(&doc);
+    }
+
+    #[test]
+    fn test_edits_inside_containers_and_at_edges() {
+        let mut doc = IncrementalDocument::new(
+            ":::note\nInside\n:::\n\n- one\n- two\n\n| A |\n|---|\n| 1 |\n",
+        );
+
+        let at = offset(&doc, "Inside");
+        doc.edit(at..at + 6, "Changed\n\nParagraphs");
+        assert_matches_full_parse(&doc);
+
+        let at = offset(&doc, "- two");
+        doc.edit(at..at, "- middle\n");
+        assert_matches_full_parse(&doc);
+
+        let len = doc.text().chars().count();
+        doc.edit(len..len, "\nTrailing ⚡ text");
+        assert_matches_full_parse(&doc);
+
+        doc.edit(0..0, "# New title\n");
+        assert_matches_full_parse(&doc);
+        assert!(doc.render().contains("<h1 id=\"new-title\">"));
+    }
+
+    #[test]
+    fn test_joining_blocks_by_deleting_separator() {
+        let mut doc = IncrementalDocument::new("> one\n\n> two\n\ntext\n");
+        let at = offset(&doc, "\n\n> two");
+        let change = doc.edit(at + 1..at + 2, "");
+
+        assert_eq!(change.start, 0);
+        assert_matches_full_parse(&doc);
+        assert_eq!(doc.blocks().len(), 2);
+    }
+}
@@ -0,0 +1,86 @@
+#[cfg(test)]
+mod inline_extension_tests {
+    use mdkit::ast::Node;
+    use mdkit::markdown;
+    use mdkit::parser::{ParseOptions, Parser};
+    use mdkit::renderer::render;
+
+    fn enabled() -> ParseOptions {
+        ParseOptions {
+            highlight: true,
+            superscript: true,
+            subscript: true,
+            inserted: true,
+            ..ParseOptions::default()
+        }
+    }
+
+    fn html(input: &str, options: ParseOptions) -> String {
+        render(&Parser::with_options(input, options).parse_document())
+    }
+
+    #[test]
+    fn test_extensions_are_off_by_default() {
+        let input = "H~2~O, x^2^, ==marked== and ++added++";
+        assert_eq!(
+            html(input, ParseOptions::default()),
+            format!("<p>{}</p>\n", input)
+        );
+    }
+
+    #[test]
+    fn test_enabled_extensions_render() {
+        assert_eq!(
+            html(
+                "H~2~O, x^2^, ==**very** marked== and ++added text++ but ~~struck~~",
+                enabled()
+            ),
+            "<p>H<sub>2</sub>O, x<sup>2</sup>, <mark><strong>very</strong> marked</mark> and <ins>added text</ins> but <del>struck</del></p>\n"
+        );
+
+        let only_highlight = ParseOptions {
+            highlight: true,
+            ..ParseOptions::default()
+        };
+        assert_eq!(
+            html("==a== H~2~O", only_highlight),
+            "<p><mark>a</mark> H~2~O</p>\n"
+        );
+    }
+
+    #[test]
+    fn test_markers_need_a_tight_closing_pair() {
+        for input in [
+            "about ~5 min~ ago",
+            "2^10 and 3^",
+            "a == b == c",
+            "C++ and C++",
+            "x^^",
+        ] {
+            let nodes = Parser::with_options(input, enabled()).parse_document();
+            assert_eq!(
+                nodes,
+                vec![Node::Paragraph {
+                    children: vec![Node::Text {
+                        content: input.to_string()
+                    }]
+                }],
+                "{:?}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn test_markdown_round_trip() {
+        let input = "H~2~O and x^2^ with ==*marked*== and ++new++\n";
+        let nodes = Parser::with_options(input, enabled()).parse_document();
+        let output = markdown::render(&nodes);
+
+        assert_eq!(output, input);
+        assert_eq!(
+            Parser::with_options(&output, enabled()).parse_document(),
+            nodes
+        );
+    }
+}
@@ -0,0 +1,167 @@
+#[cfg(test)]
+mod limits_tests {
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    use mdkit::ast::Node;
+    use mdkit::parser::{ParseOptions, ParseResult, Parser};
+    use mdkit::{markdown, renderer};
+
+    // Parses and renders on a thread with a stack about the size of a wasm
+    // instance's, so unbounded recursion fails here rather than in production.
+    fn parse_on_small_stack(input: String, options: ParseOptions) -> ParseResult {
+        thread::Builder::new()
+            .stack_size(1024 * 1024)
+            .spawn(move || {
+                let result = Parser::with_options(&input, options).parse_with_diagnostics();
+                renderer::render(&result.nodes);
+                markdown::render(&result.nodes);
+                result
+            })
+            .unwrap()
+            .join()
+            .expect("parser panicked")
+    }
+
+    fn has_code(result: &ParseResult, code: &str) -> bool {
+        result.diagnostics.iter().any(|d| d.code == code)
+    }
+
+    #[test]
+    fn test_deeply_nested_input_degrades_to_text() {
+        let inputs = [
+            ">".repeat(100_000),
+            "> ".repeat(50_000) + "text",
+            "**__~~".repeat(20_000),
+            "*[".repeat(50_000),
+            "[".repeat(100_000),
+            ":::a\n".repeat(10_000),
+            (0..2_000)
+                .map(|i| format!("{}- item\n", " ".repeat(i)))
+                .collect(),
+        ];
+
+        for input in inputs {
+            let result = parse_on_small_stack(input, ParseOptions::default());
+            assert!(!result.nodes.is_empty());
+        }
+
+        let result = parse_on_small_stack("> ".repeat(1_000) + "text", ParseOptions::default());
+        assert!(has_code(&result, "nesting-too-deep"));
+    }
+
+    #[test]
+    fn test_depth_limit_is_configurable() {
+        let options = ParseOptions {
+            max_depth: 2,
+            ..ParseOptions::default()
+        };
+        let result = parse_on_small_stack("> > > deep".to_string(), options);
+
+        let Node::BlockQuote { children } = &result.nodes[0] else {
+            panic!("expected a blockquote");
+        };
+        let Node::BlockQuote { children } = &children[0] else {
+            panic!("expected a nested blockquote");
+        };
+        assert_eq!(
+            children[0],
+            Node::BlockQuote {
+                children: vec![Node::Text {
+                    content: "deep\n".to_string()
+                }]
+            }
+        );
+        assert!(has_code(&result, "nesting-too-deep"));
+    }
+
+    #[test]
+    fn test_size_limits_keep_the_rest_as_text() {
+        let options = ParseOptions {
+            max_input_len: 12,
+            ..ParseOptions::default()
+        };
+        let result = parse_on_small_stack("# Title\n\n**not bold**".to_string(), options);
+        assert_eq!(result.nodes.len(), 3);
+        assert_eq!(
+            result.nodes[2],
+            Node::Paragraph {
+                children: vec![Node::Text {
+                    content: "ot bold**".to_string()
+                }]
+            }
+        );
+        assert!(has_code(&result, "input-too-long"));
+
+        let options = ParseOptions {
+            max_nodes: 10,
+            ..ParseOptions::default()
+        };
+        let input = "*a* ".repeat(100) + "\n\n| a | b |\n|---|---|\n" + &"|x".repeat(1_000);
+        let result = parse_on_small_stack(input, options);
+        assert!(has_code(&result, "node-limit-exceeded"));
+        assert!(result.nodes.len() <= 2);
+    }
+
+    #[test]
+    fn test_non_ascii_lines_do_not_overrun() {
+        let inputs = [
+            ":::é\nnested ünïcödé\n:::\n",
+            ":::outer\n:::ïnner títle\nbody\n:::\n:::\n",
+            "---é\n# héading\n| é | ü |\n|---|---|\n| ä | ö |\n",
+            "- éé\n  - üü\n\n> ä\n> ö",
+        ];
+
+        for input in inputs {
+            let result = parse_on_small_stack(input.to_string(), ParseOptions::default());
+            let text = mdkit::ast::plain_text(&result.nodes);
+            assert!(!text.contains('\u{0}'));
+        }
+
+        let nodes = Parser::new(":::note\n:::tïp\nInner\n:::\n:::\n\nAfter").parse_document();
+        assert_eq!(nodes.len(), 2);
+    }
+
+    #[test]
+    fn test_unclosed_directives_parse_in_linear_time() {
+        let inputs = [
+            ":a{".repeat(40_000),
+            ":a[x]{".repeat(40_000),
+            " :a[[x]".repeat(20_000),
+            ":a{'".repeat(40_000),
+            format!(":a[{}{{", "\\".repeat(100_000)),
+        ];
+
+        for input in inputs {
+            let started = Instant::now();
+            let result = parse_on_small_stack(input, ParseOptions::default());
+            assert!(!result.nodes.is_empty());
+            assert!(started.elapsed() < Duration::from_secs(2));
+        }
+    }
+
+    #[test]
+    fn test_unclosed_inline_extensions_parse_in_linear_time() {
+        let options = ParseOptions {
+            highlight: true,
+            superscript: true,
+            subscript: true,
+            inserted: true,
+            ..ParseOptions::default()
+        };
+        let inputs = [
+            "==a ".repeat(25_000),
+            "++a ".repeat(25_000),
+            "^a ".repeat(33_000),
+            "~a ".repeat(33_000),
+            "==a ++b ^c ~d ".repeat(10_000),
+        ];
+
+        for input in inputs {
+            let started = Instant::now();
+            let result = parse_on_small_stack(input, options);
+            assert!(!result.nodes.is_empty());
+            assert!(started.elapsed() < Duration::from_secs(2));
+        }
+    }
+}
@@ -0,0 +1,129 @@
+#[cfg(test)]
+mod links_tests {
+    use std::fs;
+
+    use mdkit::links::{CheckedLink, LinkChecker, LinkElement, LinkKind};
+
+    fn broken(links: &[CheckedLink]) -> Vec<(&str, &str)> {
+        links
+            .iter()
+            .filter_map(|link| Some((link.url.as_str(), link.error.as_deref()?)))
+            .collect()
+    }
+
+    #[test]
+    fn test_relative_links_and_anchors_across_files() {
+        let mut checker = LinkChecker::new("does-not-exist");
+        checker
+            .add(
+                "guide/start.md",
+                "# Start\n\n[Up](../index.md#api) [Root](/index.md) [Here](#start)\n\n\
+                 [Gone](../index.md#gone) [Self](#nope) [Missing](./other.md)",
+            )
+            .add("index.md", "# Index\n\n> ## API\n");
+
+        let links = checker.check();
+        assert_eq!(links.len(), 6);
+        assert!(
+            links
+                .iter()
+                .all(|link| link.path.as_path() == std::path::Path::new("guide/start.md"))
+        );
+        assert_eq!(links[2].kind, LinkKind::Anchor);
+        assert_eq!(
+            broken(&links),
+            vec![
+                ("../index.md#gone", "no heading `#gone` in `index.md`"),
+                ("#nope", "no heading `#nope` in `guide/start.md`"),
+                ("./other.md", "`guide/other.md` does not exist"),
+            ]
+        );
+        let source = "# Start\n\n[Up](../index.md#api) [Root](/index.md) [Here](#start)\n\n";
+        let start = source.chars().count();
+        assert_eq!(
+            links[3].span,
+            start..start + "[Gone](../index.md#gone)".len()
+        );
+    }
+
+    #[test]
+    fn test_external_urls_are_classified_not_fetched() {
+        let mut checker = LinkChecker::new(".");
+        checker.add(
+            "README.md",
+            "![badge](https://ci.invalid/badge.svg) [CI](https://ci.invalid) \
+             [mail](mailto:a@b.invalid) [cdn](//cdn.invalid/x.js) ![logo](no-logo.png)",
+        );
+
+        let links = checker.check();
+        let kinds: Vec<_> = links.iter().map(|link| (link.element, link.kind)).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (LinkElement::Image, LinkKind::External),
+                (LinkElement::Link, LinkKind::External),
+                (LinkElement::Link, LinkKind::External),
+                (LinkElement::Link, LinkKind::External),
+                (LinkElement::Image, LinkKind::File),
+            ]
+        );
+        assert_eq!(links[1].span, 39..63);
+        assert_eq!(
+            broken(&links),
+            vec![("no-logo.png", "`no-logo.png` does not exist")]
+        );
+    }
+
+    #[test]
+    fn test_reference_definitions() {
+        let mut checker = LinkChecker::new(".");
+        checker.add(
+            "doc.md",
+            "See [the docs][Docs], [Cargo.toml][] and [x][missing].\n\n\
+             `not[a][ref]`\n\n\
+             [docs]: #doc\n\
+             [cargo.toml]: Cargo.toml\n\n\
+             ```\n[a][fenced]\n```\n\n# Doc\n",
+        );
+
+        let links = checker.check();
+        let references: Vec<_> = links
+            .iter()
+            .filter(|link| link.kind == LinkKind::Reference)
+            .map(|link| link.url.as_str())
+            .collect();
+        assert_eq!(references, vec!["Docs", "Cargo.toml", "missing"]);
+        assert_eq!(
+            links
+                .iter()
+                .filter(|link| link.element == LinkElement::Definition)
+                .count(),
+            2
+        );
+        assert_eq!(
+            broken(&links),
+            vec![("missing", "no definition for `[missing]`")]
+        );
+    }
+
+    #[test]
+    fn test_files_on_disk_are_checked() {
+        let dir = std::env::temp_dir().join(format!("mdkit-links-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("img")).unwrap();
+        fs::write(dir.join("img/my logo.png"), "png").unwrap();
+        fs::write(dir.join("notes.md"), "# Notes\n\n## Todo\n").unwrap();
+
+        let mut checker = LinkChecker::new(&dir);
+        checker.add(
+            "index.md",
+            "![Logo](img/my%20logo.png) [Todo](notes.md#todo) [Done](notes.md#done) [Img](img/)",
+        );
+
+        assert_eq!(
+            broken(&checker.check()),
+            vec![("notes.md#done", "no heading `#done` in `notes.md`")]
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
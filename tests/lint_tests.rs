@@ -0,0 +1,97 @@
+#[cfg(test)]
+mod lint_tests {
+    use mdkit::diagnostics::{Diagnostic, Severity, apply_fixes};
+    use mdkit::lint::{Level, Linter, Rule, lint};
+
+    fn codes(diagnostics: &[Diagnostic]) -> Vec<&str> {
+        diagnostics.iter().map(|d| d.code.as_str()).collect()
+    }
+
+    fn source_at(input: &str, diagnostic: &Diagnostic) -> String {
+        input
+            .chars()
+            .skip(diagnostic.span.start)
+            .take(diagnostic.span.len())
+            .collect()
+    }
+
+    #[test]
+    fn test_rules_report_spans_in_source_order() {
+        let input = "# Intro\n\n### Deep\n\n# Intro\n\n![](cat.png) and [](/empty)\n\nSee https://example.com.\n\n- one\n* two\n\n| a | b |\n|---|---|\n| 1 |\n";
+        let diagnostics = lint(input);
+
+        assert_eq!(
+            codes(&diagnostics),
+            vec![
+                "heading-increment",
+                "duplicate-heading",
+                "image-alt",
+                "empty-link",
+                "bare-url",
+                "list-marker",
+                "table-column-mismatch",
+            ]
+        );
+
+        let sources: Vec<String> = diagnostics.iter().map(|d| source_at(input, d)).collect();
+        assert_eq!(sources[0], "###");
+        assert_eq!(sources[1], "# Intro");
+        assert_eq!(sources[2], "![](cat.png)");
+        assert_eq!(sources[3], "[](/empty)");
+        assert_eq!(sources[4], "https://example.com");
+        assert_eq!(sources[5], "*");
+    }
+
+    #[test]
+    fn test_rules_can_be_configured() {
+        let input = "Trailing  \n\n:::note\nBody\n";
+        let diagnostics = lint(input);
+        assert_eq!(
+            codes(&diagnostics),
+            vec!["trailing-spaces", "unclosed-custom-block"]
+        );
+        assert_eq!(diagnostics[0].severity, Severity::Info);
+        assert_eq!(diagnostics[1].severity, Severity::Error);
+
+        let mut linter = Linter::new();
+        linter
+            .set(Rule::TrailingSpaces, Level::Off)
+            .set(Rule::UnclosedCustomBlock, Level::Warning);
+        let diagnostics = linter.lint(input);
+        assert_eq!(codes(&diagnostics), vec!["unclosed-custom-block"]);
+        assert_eq!(diagnostics[0].severity, Severity::Warning);
+
+        let linter: Linter = serde_json::from_str(
+            r#"{ "unclosed-custom-block": "off", "trailing-spaces": "error" }"#,
+        )
+        .unwrap();
+        assert_eq!(linter.level(Rule::TrailingSpaces), Level::Error);
+        assert_eq!(codes(&linter.lint(input)), vec!["trailing-spaces"]);
+    }
+
+    #[test]
+    fn test_fixes_resolve_their_diagnostics() {
+        let input = "# Title\n\n### Section \n\n- a\n+ b\n  * c\n\nGo to https://rust-lang.org now\n\n:::note\nBody";
+        let fixed = apply_fixes(input, &lint(input));
+
+        assert_eq!(
+            fixed,
+            "# Title\n\n## Section\n\n- a\n- b\n  - c\n\nGo to [https://rust-lang.org](https://rust-lang.org) now\n\n:::note\nBody\n:::"
+        );
+        assert!(lint(&fixed).is_empty());
+    }
+
+    #[test]
+    fn test_code_blocks_and_links_are_not_flagged() {
+        let input = "```\nlet x = 1;   \n```\n\n[https://example.com](https://example.com)\n\n`https://example.com`\n\n![Logo](logo.png)\n";
+        assert!(lint(input).is_empty());
+
+        assert_eq!(codes(&lint("[Home]()\n")), vec!["empty-link"]);
+
+        let nested = ":::outer\n:::inner";
+        assert_eq!(
+            apply_fixes(nested, &lint(nested)),
+            ":::outer\n:::inner\n:::\n:::"
+        );
+    }
+}
@@ -25,6 +25,17 @@ mod parser_tests {
         assert!(html.contains("<h2 id=\"project-setup\">Project Setup</h2>"));
     }
 
+    #[test]
+    fn test_seven_hashes_are_not_a_heading() {
+        let input = "###### Six\n####### Seven";
+        let mut parser = Parser::new(input);
+        let nodes = parser.parse_document();
+        let html = render(&nodes);
+
+        assert!(html.contains("<h6 id=\"six\">Six</h6>"));
+        assert!(html.contains("<p>####### Seven</p>"));
+    }
+
     #[test]
     fn test_code_block_with_filename() {
         let input = "```rust main.rs\nfn main() {}\n```";
@@ -68,4 +79,48 @@ mod parser_tests {
         assert!(html.contains("checked"));
         assert!(html.contains("<input type='checkbox'"));
     }
+
+    #[test]
+    fn test_backslash_escapes() {
+        let input = "\\*not italic\\* and \\[x\\](y)\n\nline\\break";
+        let mut parser = Parser::new(input);
+        let nodes = parser.parse_document();
+        let html = render(&nodes);
+
+        assert!(html.contains("<p>*not italic* and [x](y)</p>"));
+        assert!(html.contains("line<br />"));
+    }
+
+    #[test]
+    fn test_backslash_at_line_end_continues_the_paragraph() {
+        let input = "One\\\n(two)\\\n- three";
+        let mut parser = Parser::new(input);
+        let nodes = parser.parse_document();
+        let html = render(&nodes);
+
+        assert!(html.starts_with("<p>One<br />\n(two)<br />\n</p>\n<ul>"));
+    }
+
+    #[test]
+    fn test_escaped_pipes_in_table_cells() {
+        let input = "| a \\| b | `c \\| d` |\n|---|---|\n| 1 | 2 |";
+        let mut parser = Parser::new(input);
+        let nodes = parser.parse_document();
+        let html = render(&nodes);
+
+        assert!(html.contains("<th>a | b</th>"));
+        assert!(html.contains("<code>c | d</code>"));
+        assert!(html.contains("<td>2</td>"));
+    }
+
+    #[test]
+    fn test_numbers_in_text_do_not_start_lists() {
+        let input = "Intro\n2024 was a year. Then more";
+        let mut parser = Parser::new(input);
+        let nodes = parser.parse_document();
+        let html = render(&nodes);
+
+        assert!(!html.contains("<ol>"));
+        assert!(html.contains("2024 was a year. Then more"));
+    }
 }
@@ -0,0 +1,44 @@
+#[cfg(test)]
+mod query_tests {
+    use mdkit::parser::Parser;
+    use mdkit::query::{NodeRef, Selector, node_at, query};
+
+    #[test]
+    fn test_query_by_type_and_attribute() {
+        let input = "# Title\n\n## Install\n\n### Details\n\n## Usage";
+        let nodes = Parser::new(input).parse_document();
+        let matches = query(&nodes, "heading[level=2]").unwrap();
+
+        let paths: Vec<Vec<usize>> = matches.iter().map(|m| m.path.clone()).collect();
+        assert_eq!(paths, vec![vec![1], vec![3]]);
+    }
+
+    #[test]
+    fn test_query_unchecked_tasks() {
+        let input = "- [x] Done\n- [ ] Open\n  - [ ] Nested open";
+        let nodes = Parser::new(input).parse_document();
+        let matches = query(&nodes, "list > listItem[checked=false]").unwrap();
+
+        assert_eq!(matches.len(), 2);
+        assert_eq!(matches[1].path, vec![0, 1, 1, 0]);
+        assert_eq!(node_at(&nodes, &matches[1].path), Some(matches[1].node));
+    }
+
+    #[test]
+    fn test_query_descendant_in_custom_block() {
+        let input = ":::tabs\n:::tab title=\"Rust\"\n```rust\nfn main() {}\n```\n:::\n:::\n\n```rust\nlet x = 1;\n```";
+        let nodes = Parser::new(input).parse_document();
+        let matches = query(&nodes, "customBlock[name=tabs] codeBlock[lang=rust]").unwrap();
+
+        assert_eq!(matches.len(), 1);
+        assert!(matches!(matches[0].node, NodeRef::Node(_)));
+        assert_eq!(matches[0].path, vec![0, 0, 0]);
+    }
+
+    #[test]
+    fn test_invalid_selector() {
+        assert!(Selector::parse("heading[level=2").is_err());
+        assert!(Selector::parse("list >").is_err());
+        assert!(Selector::parse("heading, list > listItem").is_ok());
+    }
+}
@@ -0,0 +1,158 @@
+#[cfg(test)]
+mod reference_tests {
+    use mdkit::ast::Node;
+    use mdkit::markdown;
+    use mdkit::parser::{ParseOptions, Parser};
+    use mdkit::renderer::{ReferenceTemplates, Renderer, render};
+
+    fn options() -> ParseOptions {
+        ParseOptions {
+            mentions: true,
+            issues: true,
+            commits: true,
+            hashtags: true,
+            ..ParseOptions::default()
+        }
+    }
+
+    fn inline(input: &str) -> Vec<Node> {
+        match Parser::with_options(input, options())
+            .parse_document()
+            .remove(0)
+        {
+            Node::Paragraph { children } => children,
+            other => panic!("expected a paragraph, got {:?}", other),
+        }
+    }
+
+    fn text(content: &str) -> Node {
+        Node::Text {
+            content: content.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_references_in_text() {
+        let input =
+            "#12 by @octo-cat in a5c3785, see owner/repo#3 and owner/repo@4e1f0c2d #release";
+
+        assert_eq!(
+            inline(input),
+            vec![
+                Node::IssueReference {
+                    repository: None,
+                    number: 12
+                },
+                text(" by "),
+                Node::Mention {
+                    username: "octo-cat".to_string()
+                },
+                text(" in "),
+                Node::CommitReference {
+                    repository: None,
+                    sha: "a5c3785".to_string()
+                },
+                text(", see "),
+                Node::IssueReference {
+                    repository: Some("owner/repo".to_string()),
+                    number: 3
+                },
+                text(" and "),
+                Node::CommitReference {
+                    repository: Some("owner/repo".to_string()),
+                    sha: "4e1f0c2d".to_string()
+                },
+                text(" "),
+                Node::Hashtag {
+                    tag: "release".to_string()
+                },
+            ]
+        );
+        assert_eq!(
+            markdown::render(&Parser::with_options(input, options()).parse_document()),
+            format!("{}\n", input)
+        );
+        assert_eq!(
+            Parser::new(input).parse_document()[0],
+            Node::Heading {
+                level: 1,
+                id: "12-by-octo-cat-in-a5c3785-see-owner-repo-3-and-owner-repo-4e1f0c2d-release"
+                    .to_string(),
+                children: vec![text(&input[1..])],
+            }
+        );
+    }
+
+    #[test]
+    fn test_code_links_and_lookalikes_stay_text() {
+        let input =
+            "me@mail.com `@code #1` [@link #2](u) https://x.io/a/b#3 #007 deadbeef 1234567 x#4";
+
+        assert_eq!(
+            inline(input),
+            vec![
+                text("me@mail.com "),
+                Node::InlineCode {
+                    content: "@code #1".to_string()
+                },
+                text(" "),
+                Node::Link {
+                    text: vec![text("@link #2")],
+                    url: "u".to_string(),
+                    attributes: Default::default(),
+                },
+                text(" https://x.io/a/b#3 #007 deadbeef 1234567 x#4"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_templates_link_references() {
+        let nodes =
+            Parser::with_options("@octocat #7 a5c3785f00 #news", options()).parse_document();
+        assert_eq!(
+            render(&nodes),
+            "<p><span class='mention'>@octocat</span> <span class='issue-reference'>#7</span> <span class='commit-reference'>a5c3785</span> <span class='hashtag'>#news</span></p>\n"
+        );
+
+        let mut renderer = Renderer::new();
+        renderer.references(ReferenceTemplates {
+            hashtag: Some("/tags/{tag}".to_string()),
+            ..ReferenceTemplates::github("acme/app")
+        });
+        assert_eq!(
+            renderer.render(&nodes),
+            "<p><a class='mention' href='https://github.com/octocat'>@octocat</a> \
+             <a class='issue-reference' href='https://github.com/acme/app/issues/7'>#7</a> \
+             <a class='commit-reference' href='https://github.com/acme/app/commit/a5c3785f00'>a5c3785</a> \
+             <a class='hashtag' href='/tags/news'>#news</a></p>\n"
+        );
+
+        let mut renderer = Renderer::new();
+        renderer.references(ReferenceTemplates {
+            repository: None,
+            ..ReferenceTemplates::github("acme/app")
+        });
+        assert!(
+            renderer
+                .render(&nodes)
+                .contains("<span class='issue-reference'>#7</span>")
+        );
+    }
+
+    #[test]
+    fn test_resolver_callback() {
+        let mut renderer = Renderer::new();
+        renderer.references(|reference: &Node| match reference {
+            Node::Mention { username } if username != "ghost" => {
+                Some(format!("/users/{}", username))
+            }
+            _ => None,
+        });
+
+        assert_eq!(
+            renderer.render(&Parser::with_options("@ada and @ghost", options()).parse_document()),
+            "<p><a class='mention' href='/users/ada'>@ada</a> and <span class='mention'>@ghost</span></p>\n"
+        );
+    }
+}
@@ -0,0 +1,92 @@
+#[cfg(test)]
+mod search_tests {
+    use mdkit::parser::Parser;
+    use mdkit::search::{SearchIndex, Section, sections};
+
+    fn index(pages: &[(&str, &str)]) -> SearchIndex {
+        let mut index = SearchIndex::new();
+        for (path, text) in pages {
+            index.add(path, &Parser::new(text).parse_document());
+        }
+        index
+    }
+
+    #[test]
+    fn test_sections_split_at_headings() {
+        let nodes = Parser::new(
+            "Intro **text**\n\n# Guide\n\n## Install\n\n- one\n- two\n\n```sh\ncargo add mdkit\n```\n\n# API",
+        )
+        .parse_document();
+
+        assert_eq!(
+            sections(&nodes),
+            vec![
+                Section {
+                    text: "Intro text".to_string(),
+                    ..Section::default()
+                },
+                Section {
+                    title: "Guide".to_string(),
+                    headings: vec!["Guide".to_string()],
+                    id: "guide".to_string(),
+                    ..Section::default()
+                },
+                Section {
+                    title: "Install".to_string(),
+                    headings: vec!["Guide".to_string(), "Install".to_string()],
+                    id: "install".to_string(),
+                    text: "one two".to_string(),
+                    code: vec!["cargo add mdkit".to_string()],
+                },
+                Section {
+                    title: "API".to_string(),
+                    headings: vec!["API".to_string()],
+                    id: "api".to_string(),
+                    ..Section::default()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_search_ranks_titles_and_requires_every_word() {
+        let index = index(&[
+            ("a.html", "# Parsing\n\nHow the parser works."),
+            (
+                "b.html",
+                "# Rendering\n\nRendering after parsing.\n\n## Options\n\nParse options.",
+            ),
+        ]);
+
+        let hits: Vec<_> = index
+            .search("pars")
+            .into_iter()
+            .map(|hit| (hit.entry.path, hit.entry.id))
+            .collect();
+        assert_eq!(
+            hits,
+            vec![
+                ("a.html".to_string(), "parsing".to_string()),
+                ("b.html".to_string(), "rendering".to_string()),
+                ("b.html".to_string(), "options".to_string()),
+            ]
+        );
+
+        let hits = index.search("PARSE opt");
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].entry.headings, vec!["Rendering", "Options"]);
+        assert!(index.search("missing").is_empty());
+        assert!(index.search("").is_empty());
+    }
+
+    #[test]
+    fn test_index_round_trips_through_json() {
+        let index = index(&[("api.html", "# API\n\n```rust\nparse_document(input)\n```")]);
+        let json = serde_json::to_string(&index).unwrap();
+        assert!(json.contains(r#""document":[[0,2]]"#));
+
+        let loaded: SearchIndex = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded, index);
+        assert_eq!(loaded.search("document")[0].entry.title, "API");
+    }
+}
@@ -0,0 +1,109 @@
+#[cfg(test)]
+mod site_tests {
+    use std::fs;
+    use std::path::{Path, PathBuf};
+
+    use mdkit::search::SearchIndex;
+    use mdkit::site::Site;
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("mdkit-site-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn write(path: &Path, contents: &str) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, contents).unwrap();
+    }
+
+    #[test]
+    fn test_build_renders_pages_and_copies_assets() {
+        let dir = scratch_dir("build");
+        let source = dir.join("docs");
+        write(
+            &source.join("intro.md"),
+            "# Intro\n\nRead [setup](guide/setup.md#install) or [Rust](https://rust-lang.org/a.md).",
+        );
+        write(&source.join("guide/setup.md"), "# Setup\n\n## Install\n");
+        write(&source.join("img/logo.png"), "png");
+        write(&source.join(".git/config"), "ignored");
+
+        let output = dir.join("site");
+        let report = Site::new(&source).build(&output).unwrap();
+
+        assert_eq!(
+            report.pages,
+            vec![
+                PathBuf::from("index.html"),
+                PathBuf::from("intro.html"),
+                PathBuf::from("guide/setup.html"),
+            ]
+        );
+        assert_eq!(report.assets, vec![PathBuf::from("img/logo.png")]);
+        assert!(!output.join(".git").exists());
+
+        let intro = fs::read_to_string(output.join("intro.html")).unwrap();
+        assert!(intro.contains("<title>Intro</title>"));
+        assert!(intro.contains("href='guide/setup.html#install'"));
+        assert!(intro.contains("href='https://rust-lang.org/a.md'"));
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_template_placeholders() {
+        let dir = scratch_dir("template");
+        let source = dir.join("docs");
+        write(
+            &source.join("index.md"),
+            "# Home\n\n## News\n\n{{title}} stays",
+        );
+        write(&source.join("api/client.md"), "# Client");
+
+        let output = dir.join("site");
+        Site::new(&source)
+            .template("[{{title}}|{{root}}]\n{{toc}}{{nav}}{{content}}")
+            .build(&output)
+            .unwrap();
+
+        let index = fs::read_to_string(output.join("index.html")).unwrap();
+        assert!(index.starts_with("[Home|]\n"));
+        assert!(index.contains("<a href='#news'>News</a>"));
+        assert!(index.contains("<p>{{title}} stays</p>"));
+
+        let client = fs::read_to_string(output.join("api/client.html")).unwrap();
+        assert!(client.starts_with("[Client|../]\n"));
+        assert!(client.contains("<a href='../index.html'>Home</a>"));
+        assert!(client.contains("<li><span>api</span><ul>"));
+        assert!(client.contains("<a href='../api/client.html' class='active'>Client</a>"));
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_generated_index_lists_pages() {
+        let dir = scratch_dir("index");
+        let source = dir.join("docs");
+        write(&source.join("b.md"), "No heading");
+        write(&source.join("a.md"), "# Alpha");
+
+        let output = dir.join("site");
+        let report = Site::new(&source)
+            .title("Handbook")
+            .search(true)
+            .build(&output)
+            .unwrap();
+
+        let index = fs::read_to_string(output.join("index.html")).unwrap();
+        assert!(index.contains("<h1>Handbook</h1>"));
+        let alpha = index.find("<a href='a.html'>Alpha</a>").unwrap();
+        let b = index.find("<a href='b.html'>b</a>").unwrap();
+        assert!(alpha < b);
+
+        assert_eq!(report.search_index, Some(PathBuf::from("search.json")));
+        let search: SearchIndex =
+            serde_json::from_str(&fs::read_to_string(output.join("search.json")).unwrap()).unwrap();
+        assert_eq!(search.search("heading")[0].entry.path, "b.html");
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
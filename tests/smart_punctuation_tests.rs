@@ -0,0 +1,61 @@
+#[cfg(test)]
+mod smart_punctuation_tests {
+    use mdkit::transform::{Pipeline, QuoteStyle, SmartPunctuation, SmartQuotes};
+
+    fn render(punctuation: SmartPunctuation, input: &str) -> String {
+        let mut pipeline = Pipeline::new();
+        pipeline.add(punctuation);
+        pipeline.render(input)
+    }
+
+    #[test]
+    fn test_quotes_dashes_and_ellipses() {
+        let html = render(
+            SmartPunctuation::default(),
+            "\"She said 'don't'\" -- pages 3--5 --- wait... `a -- b...` $x--y$",
+        );
+
+        assert_eq!(
+            html,
+            "<p>\u{201C}She said \u{2018}don\u{2019}t\u{2019}\u{201D} \u{2013} pages 3\u{2013}5 \
+             \u{2014} wait\u{2026} <code>a -- b...</code> <span class='math-inline'>\\( x--y \\)</span></p>\n"
+        );
+    }
+
+    #[test]
+    fn test_locale_quote_styles() {
+        let german = SmartPunctuation::locale("de-DE").unwrap();
+        assert_eq!(
+            render(german, "\"Er sagte 'nein'.\""),
+            "<p>\u{201E}Er sagte \u{201A}nein\u{2018}.\u{201C}</p>\n"
+        );
+
+        let french = SmartPunctuation::locale("fr").unwrap();
+        assert_eq!(
+            render(french, "Il a dit \"oui\" et 'non'."),
+            "<p>Il a dit \u{AB}\u{A0}oui\u{A0}\u{BB} et \u{201C}non\u{201D}.</p>\n"
+        );
+    }
+
+    #[test]
+    fn test_locale_lookup() {
+        assert_eq!(QuoteStyle::for_locale("en-GB"), Some(QuoteStyle::ENGLISH));
+        assert_eq!(QuoteStyle::for_locale("de_CH"), Some(QuoteStyle::SWISS));
+        assert_eq!(QuoteStyle::for_locale("fr-FR"), Some(QuoteStyle::FRENCH));
+        assert_eq!(QuoteStyle::for_locale("zh-Hant-TW"), Some(QuoteStyle::CJK));
+        assert_eq!(QuoteStyle::for_locale("zh-CN"), Some(QuoteStyle::ENGLISH));
+        assert_eq!(QuoteStyle::for_locale("xx"), None);
+        assert!(SmartPunctuation::locale("").is_none());
+    }
+
+    #[test]
+    fn test_smart_quotes_leave_dashes_alone() {
+        let mut pipeline = Pipeline::new();
+        pipeline.add(SmartQuotes);
+
+        assert_eq!(
+            pipeline.render("\"a\" -- b..."),
+            "<p>\u{201C}a\u{201D} -- b...</p>\n"
+        );
+    }
+}
@@ -0,0 +1,81 @@
+#[cfg(test)]
+mod streaming_tests {
+    use mdkit::markdown_to_html;
+    use mdkit::streaming::StreamingParser;
+
+    fn stream(input: &str, chunk_size: usize) -> (String, Vec<String>) {
+        let mut parser = StreamingParser::new();
+        let chars: Vec<char> = input.chars().collect();
+        let mut stable = String::new();
+        let mut tails = Vec::new();
+
+        for chunk in chars.chunks(chunk_size) {
+            let update = parser.push(&chunk.iter().collect::<String>());
+            stable.push_str(&update.stable);
+            tails.push(update.tail);
+        }
+        stable.push_str(&parser.finish().stable);
+        (stable, tails)
+    }
+
+    #[test]
+    fn test_stream_matches_full_render() {
+        let input = "# Title\n\nSome **bold** text.\n\n- one\n- two\n\n```rust\nfn main() {\n\n}\n```\n\n| A | B |\n|---|---|\n| 1 | 2 |\n";
+        for chunk_size in [1, 3, 7, 64] {
+            let (html, _) = stream(input, chunk_size);
+            assert_eq!(html, markdown_to_html(input), "chunk size {}", chunk_size);
+        }
+    }
+
+    #[test]
+    fn test_open_fence_is_rendered_closed() {
+        let mut parser = StreamingParser::new();
+        let first = parser.push("Intro\n\n```rust\nlet x = 1;\n\nlet y");
+        let update = parser.push(" = 2;");
+
+        assert_eq!(first.stable, "<p>Intro</p>\n");
+        assert!(update.stable.is_empty());
+        assert!(
+            update
+                .tail
+                .contains("let x = 1;\n\nlet y = 2;</code></pre>")
+        );
+    }
+
+    #[test]
+    fn test_dangling_markers_do_not_flicker() {
+        let mut parser = StreamingParser::new();
+        assert_eq!(parser.push("Hello *").tail, "<p>Hello </p>\n");
+        assert_eq!(parser.push("*").tail, "<p>Hello </p>\n");
+        assert_eq!(
+            parser.push("wor").tail,
+            "<p>Hello <strong>wor</strong></p>\n"
+        );
+    }
+
+    #[test]
+    fn test_finalized_blocks_are_not_reparsed() {
+        let mut parser = StreamingParser::new();
+        let first = parser.push("First paragraph\n\n");
+        assert_eq!(first.stable, "<p>First paragraph</p>\n");
+
+        let second = parser.push("Second");
+        assert!(second.stable.is_empty());
+        assert_eq!(second.tail, "<p>Second</p>\n");
+        assert_eq!(parser.nodes().len(), 1);
+    }
+
+    #[test]
+    fn test_unclosed_front_matter_is_released() {
+        let mut parser = StreamingParser::new();
+        let update = parser.push("---\nThe rule above is decoration.\n\nMore text\n\n");
+        assert_eq!(
+            update.stable,
+            "<hr />\n<p>The rule above is decoration.</p>\n<p>More text</p>\n"
+        );
+
+        let mut parser = StreamingParser::new();
+        let update = parser.push(&("---\n".to_string() + &"key: value\n\n".repeat(200)));
+        assert!(update.stable.starts_with("<hr />\n<p>key: value</p>\n"));
+    }
+}
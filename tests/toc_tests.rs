@@ -0,0 +1,52 @@
+#[cfg(test)]
+mod toc_tests {
+    use mdkit::parser::Parser;
+    use mdkit::toc::{TocEntry, render, toc};
+
+    #[test]
+    fn test_toc_collects_top_level_headings() {
+        let nodes = Parser::new("# **Bold** Title\n\n> ## Quoted\n\nText\n\n### `code` ref")
+            .parse_document();
+
+        assert_eq!(
+            toc(&nodes),
+            vec![
+                TocEntry {
+                    level: 1,
+                    id: "bold-title".to_string(),
+                    text: "Bold Title".to_string(),
+                },
+                TocEntry {
+                    level: 3,
+                    id: "code-ref".to_string(),
+                    text: "code ref".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_render_nests_under_closest_lower_level() {
+        let nodes = Parser::new("## A\n\n#### B\n\n### C\n\n# D\n\n## E").parse_document();
+
+        assert_eq!(
+            render(&toc(&nodes)),
+            "- [A](#a)\n  - [B](#b)\n  - [C](#c)\n- [D](#d)\n  - [E](#e)\n"
+        );
+    }
+
+    #[test]
+    fn test_rendered_toc_parses_as_nested_list() {
+        let nodes = Parser::new("# A\n\n## B\n\n## C").parse_document();
+        let list = Parser::new(&render(&toc(&nodes))).parse_document();
+
+        let mdkit::ast::Node::List { items, .. } = &list[0] else {
+            panic!("expected a list");
+        };
+        assert_eq!(items.len(), 1);
+        assert!(matches!(
+            &items[0].children[0],
+            mdkit::ast::Node::List { items, .. } if items.len() == 2
+        ));
+    }
+}
@@ -0,0 +1,66 @@
+#[cfg(test)]
+mod transform_tests {
+    use mdkit::ast::Node;
+    use mdkit::parser::ParseOptions;
+    use mdkit::transform::{ExternalLinks, HeadingShift, LazyImages, Pipeline, SmartQuotes};
+
+    #[test]
+    fn test_heading_shift_is_clamped() {
+        let mut pipeline = Pipeline::new();
+        pipeline.add(HeadingShift(2));
+        let html = pipeline.render("# Title\n\n##### Deep");
+
+        assert!(html.contains("<h3 id=\"title\">Title</h3>"));
+        assert!(html.contains("<h6 id=\"deep\">Deep</h6>"));
+    }
+
+    #[test]
+    fn test_external_links_and_lazy_images() {
+        let mut pipeline = Pipeline::new();
+        pipeline.add(ExternalLinks::default()).add(LazyImages);
+        let html =
+            pipeline.render("[Rust](https://rust-lang.org) [Guide](/guide) ![Logo](logo.png)");
+
+        assert!(html.contains(
+            "<a href='https://rust-lang.org' rel='noopener noreferrer' target='_blank'>Rust</a>"
+        ));
+        assert!(html.contains("<a href='/guide'>Guide</a>"));
+        assert!(html.contains("<img src='logo.png' alt='Logo' decoding='async' loading='lazy' />"));
+    }
+
+    #[test]
+    fn test_smart_quotes_skip_code() {
+        let mut pipeline = Pipeline::new();
+        pipeline.add(SmartQuotes);
+        let html = pipeline.render("\"Hello,\" she said. It's `\"raw\"`");
+
+        assert!(html.contains("\u{201C}Hello,\u{201D} she said. It\u{2019}s"));
+        assert!(html.contains("<code>\"raw\"</code>"));
+    }
+
+    #[test]
+    fn test_custom_closure_transform_runs_in_order() {
+        let mut pipeline = Pipeline::new();
+        pipeline
+            .add(|nodes: &mut Vec<Node>| nodes.retain(|n| !matches!(n, Node::HorizontalRule)))
+            .add(HeadingShift(1));
+        let nodes = pipeline.parse("# A\n\n---\n\ntext");
+
+        assert_eq!(nodes.len(), 2);
+        assert!(matches!(nodes[0], Node::Heading { level: 2, .. }));
+    }
+
+    #[test]
+    fn test_pipeline_parse_options() {
+        let options = ParseOptions {
+            highlight: true,
+            ..ParseOptions::default()
+        };
+        let mut pipeline = Pipeline::with_options(options);
+        pipeline.add(HeadingShift(1));
+
+        let html = pipeline.render("# ==Key== point");
+        assert!(html.contains("<h2 id=\"key-point\"><mark>Key</mark> point</h2>"));
+        assert!(!Pipeline::new().render("==Key==").contains("<mark>"));
+    }
+}
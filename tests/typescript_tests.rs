@@ -0,0 +1,490 @@
+#[cfg(test)]
+mod typescript_tests {
+    use std::collections::{BTreeSet, HashMap};
+
+    use mdkit::ast::typescript::DEFINITIONS;
+    use mdkit::ast::{
+        Document, Fold, FrontMatterFormat, ListItem, ListType, Node, TableAlignment, TableCell,
+    };
+    use mdkit::diagnostics::{Diagnostic, Fix, Severity};
+    use mdkit::diff::{diff, diff_text};
+    use mdkit::lint::{Level, Linter, Rule};
+    use mdkit::parser::{ParseOptions, Parser};
+    use mdkit::search::SearchIndex;
+    use serde_json::Value;
+
+    // Exhaustive on purpose: a new variant fails to compile here until it
+    // gets a sample below and a declaration in `ast::typescript`.
+    fn sample(node: &Node) -> Node {
+        let attributes = HashMap::from([("key".to_string(), "value".to_string())]);
+        let text = || {
+            vec![Node::Text {
+                content: "x".to_string(),
+            }]
+        };
+
+        match node {
+            Node::FrontMatter { .. } => Node::FrontMatter {
+                format: FrontMatterFormat::Yaml,
+                raw: "title: x".to_string(),
+                metadata: serde_json::Map::from_iter([("title".to_string(), Value::from("x"))]),
+            },
+            Node::Heading { .. } => Node::Heading {
+                level: 1,
+                id: "x".to_string(),
+                children: text(),
+            },
+            Node::HorizontalRule => Node::HorizontalRule,
+            Node::Paragraph { .. } => Node::Paragraph { children: text() },
+            Node::LineBreak => Node::LineBreak,
+            Node::Link { .. } => Node::Link {
+                text: text(),
+                url: "/".to_string(),
+                attributes,
+            },
+            Node::Image { .. } => Node::Image {
+                alt: "x".to_string(),
+                url: "/".to_string(),
+                attributes,
+            },
+            Node::Bold { .. } => Node::Bold { children: text() },
+            Node::Italic { .. } => Node::Italic { children: text() },
+            Node::Strikethrough { .. } => Node::Strikethrough { children: text() },
+            Node::Underline { .. } => Node::Underline { children: text() },
+            Node::Highlight { .. } => Node::Highlight { children: text() },
+            Node::Superscript { .. } => Node::Superscript { children: text() },
+            Node::Subscript { .. } => Node::Subscript { children: text() },
+            Node::Inserted { .. } => Node::Inserted { children: text() },
+            Node::Text { .. } => Node::Text {
+                content: "x".to_string(),
+            },
+            Node::InlineMath { .. } => Node::InlineMath {
+                content: "x".to_string(),
+            },
+            Node::BlockMath { .. } => Node::BlockMath {
+                content: "x".to_string(),
+            },
+            Node::InlineCode { .. } => Node::InlineCode {
+                content: "x".to_string(),
+            },
+            Node::CodeBlock { .. } => Node::CodeBlock {
+                lang: "rust".to_string(),
+                filename: Some("main.rs".to_string()),
+                code: "x".to_string(),
+            },
+            Node::BlockQuote { .. } => Node::BlockQuote { children: text() },
+            Node::Callout { .. } => Node::Callout {
+                kind: "note".to_string(),
+                title: text(),
+                foldable: Some(Fold::Closed),
+                children: text(),
+            },
+            Node::List { .. } => Node::List {
+                kind: ListType::Ordered,
+                items: vec![ListItem {
+                    content: text(),
+                    children: Vec::new(),
+                    checked: Some(true),
+                }],
+            },
+            Node::DefinitionList { .. } => Node::DefinitionList {
+                children: vec![Node::DefinitionTerm { children: text() }],
+            },
+            Node::DefinitionTerm { .. } => Node::DefinitionTerm { children: text() },
+            Node::DefinitionDescription { .. } => Node::DefinitionDescription { children: text() },
+            Node::Table { .. } => Node::Table {
+                header: vec![TableCell {
+                    children: text(),
+                    alignment: TableAlignment::Left,
+                }],
+                rows: Vec::new(),
+            },
+            Node::InlineDirective { .. } => Node::InlineDirective {
+                name: "abbr".to_string(),
+                label: text(),
+                attributes,
+            },
+            Node::WikiLink { .. } => Node::WikiLink {
+                target: "Page".to_string(),
+                heading: Some("Intro".to_string()),
+                alias: Some("page".to_string()),
+            },
+            Node::Mention { .. } => Node::Mention {
+                username: "octocat".to_string(),
+            },
+            Node::IssueReference { .. } => Node::IssueReference {
+                repository: Some("owner/repo".to_string()),
+                number: 12,
+            },
+            Node::CommitReference { .. } => Node::CommitReference {
+                repository: None,
+                sha: "a5c3785".to_string(),
+            },
+            Node::Hashtag { .. } => Node::Hashtag {
+                tag: "release".to_string(),
+            },
+            Node::Emoji { .. } => Node::Emoji {
+                shortcode: "rocket".to_string(),
+                unicode: "\u{1F680}".to_string(),
+            },
+            Node::LeafDirective { .. } => Node::LeafDirective {
+                name: "youtube".to_string(),
+                label: text(),
+                attributes,
+            },
+            Node::CustomBlock { .. } => Node::CustomBlock {
+                name: "note".to_string(),
+                label: text(),
+                attributes,
+                children: text(),
+            },
+        }
+    }
+
+    fn all_samples() -> Vec<Node> {
+        let seeds = vec![
+            Node::FrontMatter {
+                format: FrontMatterFormat::Toml,
+                raw: String::new(),
+                metadata: serde_json::Map::new(),
+            },
+            Node::Heading {
+                level: 1,
+                id: String::new(),
+                children: Vec::new(),
+            },
+            Node::HorizontalRule,
+            Node::Paragraph {
+                children: Vec::new(),
+            },
+            Node::LineBreak,
+            Node::Link {
+                text: Vec::new(),
+                url: String::new(),
+                attributes: HashMap::new(),
+            },
+            Node::Image {
+                alt: String::new(),
+                url: String::new(),
+                attributes: HashMap::new(),
+            },
+            Node::Bold {
+                children: Vec::new(),
+            },
+            Node::Italic {
+                children: Vec::new(),
+            },
+            Node::Strikethrough {
+                children: Vec::new(),
+            },
+            Node::Underline {
+                children: Vec::new(),
+            },
+            Node::Highlight {
+                children: Vec::new(),
+            },
+            Node::Superscript {
+                children: Vec::new(),
+            },
+            Node::Subscript {
+                children: Vec::new(),
+            },
+            Node::Inserted {
+                children: Vec::new(),
+            },
+            Node::Text {
+                content: String::new(),
+            },
+            Node::InlineMath {
+                content: String::new(),
+            },
+            Node::BlockMath {
+                content: String::new(),
+            },
+            Node::InlineCode {
+                content: String::new(),
+            },
+            Node::CodeBlock {
+                lang: String::new(),
+                filename: None,
+                code: String::new(),
+            },
+            Node::BlockQuote {
+                children: Vec::new(),
+            },
+            Node::Callout {
+                kind: String::new(),
+                title: Vec::new(),
+                foldable: None,
+                children: Vec::new(),
+            },
+            Node::List {
+                kind: ListType::Unordered,
+                items: Vec::new(),
+            },
+            Node::DefinitionList {
+                children: Vec::new(),
+            },
+            Node::DefinitionTerm {
+                children: Vec::new(),
+            },
+            Node::DefinitionDescription {
+                children: Vec::new(),
+            },
+            Node::Table {
+                header: Vec::new(),
+                rows: Vec::new(),
+            },
+            Node::InlineDirective {
+                name: String::new(),
+                label: Vec::new(),
+                attributes: HashMap::new(),
+            },
+            Node::WikiLink {
+                target: String::new(),
+                heading: None,
+                alias: None,
+            },
+            Node::Mention {
+                username: String::new(),
+            },
+            Node::IssueReference {
+                repository: None,
+                number: 0,
+            },
+            Node::CommitReference {
+                repository: None,
+                sha: String::new(),
+            },
+            Node::Hashtag { tag: String::new() },
+            Node::Emoji {
+                shortcode: String::new(),
+                unicode: String::new(),
+            },
+            Node::LeafDirective {
+                name: String::new(),
+                label: Vec::new(),
+                attributes: HashMap::new(),
+            },
+            Node::CustomBlock {
+                name: String::new(),
+                label: Vec::new(),
+                attributes: HashMap::new(),
+                children: Vec::new(),
+            },
+        ];
+        seeds.iter().map(sample).collect()
+    }
+
+    /// Body of the declaration that contains `marker`.
+    fn declaration_containing(marker: &str) -> &'static str {
+        let at = DEFINITIONS
+            .find(marker)
+            .unwrap_or_else(|| panic!("no TypeScript declaration for `{}`", marker));
+        let start = DEFINITIONS[..at].rfind('{').unwrap();
+        let end = at + DEFINITIONS[at..].find('}').unwrap();
+        &DEFINITIONS[start..end]
+    }
+
+    fn assert_keys_declared(value: &Value, body: &str) {
+        for key in value.as_object().unwrap().keys() {
+            assert!(
+                body.contains(&format!("{}:", key)) || body.contains(&format!("{}?:", key)),
+                "field `{}` is missing from declaration:\n{}",
+                key,
+                body
+            );
+        }
+    }
+
+    #[test]
+    fn test_every_node_variant_is_declared() {
+        for node in all_samples() {
+            let value = serde_json::to_value(&node).unwrap();
+            let tag = value["type"].as_str().unwrap();
+            let body = declaration_containing(&format!("type: \"{}\";", tag));
+
+            assert_keys_declared(&value, body);
+            assert!(
+                DEFINITIONS.contains(&format!("| {}Node\n", capitalize(tag)))
+                    || DEFINITIONS.contains(&format!("| {}Node;", capitalize(tag))),
+                "`{}` is not part of the Node union",
+                tag
+            );
+        }
+    }
+
+    #[test]
+    fn test_no_stale_declarations() {
+        let rust_tags: BTreeSet<String> = all_samples()
+            .iter()
+            .map(|node| {
+                serde_json::to_value(node).unwrap()["type"]
+                    .as_str()
+                    .unwrap()
+                    .to_string()
+            })
+            .collect();
+
+        let union = &DEFINITIONS[DEFINITIONS.find("export type Node =").unwrap()..];
+        let union = &union[..union.find(';').unwrap()];
+        let declared: BTreeSet<String> = union
+            .split('|')
+            .skip(1)
+            .map(|name| {
+                let name = name.trim().trim_end_matches("Node");
+                let mut chars = name.chars();
+                let first = chars.next().unwrap().to_ascii_lowercase();
+                std::iter::once(first).chain(chars).collect()
+            })
+            .collect();
+
+        assert_eq!(rust_tags, declared);
+    }
+
+    #[test]
+    fn test_list_item_and_table_cell_fields() {
+        let list = serde_json::to_value(sample(&Node::List {
+            kind: ListType::Ordered,
+            items: Vec::new(),
+        }))
+        .unwrap();
+        assert_keys_declared(&list["items"][0], declaration_containing("checked?:"));
+        assert_eq!(list["kind"], "Ordered");
+
+        let table = serde_json::to_value(sample(&Node::Table {
+            header: Vec::new(),
+            rows: Vec::new(),
+        }))
+        .unwrap();
+        assert_keys_declared(
+            &table["header"][0],
+            declaration_containing("alignment: TableAlignment"),
+        );
+        assert!(DEFINITIONS.contains(&format!(
+            "\"{}\"",
+            table["header"][0]["alignment"].as_str().unwrap()
+        )));
+    }
+
+    /// Body of `export interface name`.
+    fn interface(name: &str) -> &'static str {
+        let start = DEFINITIONS
+            .find(&format!("export interface {} {{", name))
+            .unwrap_or_else(|| panic!("no TypeScript declaration for `{}`", name));
+        let end = start + DEFINITIONS[start..].find("\n}").unwrap();
+        &DEFINITIONS[start..end]
+    }
+
+    /// Fields declared in a declaration body, without their `?`.
+    fn declared_fields(body: &str) -> BTreeSet<String> {
+        body.split([';', '{', '\n'])
+            .filter_map(|field| field.split_once(':'))
+            .map(|(name, _)| name.trim().trim_end_matches('?').to_string())
+            .filter(|name| !name.is_empty() && !name.contains(' '))
+            .collect()
+    }
+
+    /// Declaration of `export type name`.
+    fn type_alias(name: &str) -> &'static str {
+        let start = DEFINITIONS
+            .find(&format!("export type {} =", name))
+            .unwrap_or_else(|| panic!("no TypeScript declaration for `{}`", name));
+        let end = start + DEFINITIONS[start..].find(";\n\n").unwrap();
+        &DEFINITIONS[start..end]
+    }
+
+    /// The variant line of a union type declared as `| { ... }` lines.
+    fn union_member(union: &str, marker: &str) -> &'static str {
+        type_alias(union)
+            .lines()
+            .find(|line| line.contains(marker))
+            .unwrap_or_else(|| panic!("`{}` has no member with `{}`", union, marker))
+    }
+
+    #[test]
+    fn test_parse_options_diagnostics_and_lint_config() {
+        let options = serde_json::to_value(ParseOptions::default()).unwrap();
+        let body = interface("ParseOptions");
+        let keys: BTreeSet<String> = options.as_object().unwrap().keys().cloned().collect();
+        assert_eq!(keys, declared_fields(body));
+
+        let mut diagnostic = Diagnostic::new(Severity::Warning, "x", "message", 0..1);
+        diagnostic.fix = Some(Fix {
+            span: 0..1,
+            replacement: String::new(),
+        });
+        let diagnostic = serde_json::to_value(diagnostic).unwrap();
+        assert_keys_declared(&diagnostic, interface("Diagnostic"));
+        assert_keys_declared(&diagnostic["span"], "start: number; end: number");
+        assert_keys_declared(&diagnostic["fix"], interface("Fix"));
+        for severity in [Severity::Error, Severity::Warning, Severity::Info] {
+            let severity = serde_json::to_value(severity).unwrap();
+            assert!(type_alias("Severity").contains(&severity.to_string()));
+        }
+
+        let mut linter = Linter::new();
+        for rule in Rule::ALL {
+            linter.set(rule, Level::Off);
+        }
+        let config = serde_json::to_value(linter).unwrap();
+        let rules = type_alias("LintRule");
+        assert_eq!(rules.matches('|').count(), Rule::ALL.len());
+        for (rule, level) in config.as_object().unwrap() {
+            assert!(
+                rules.contains(&format!("\"{}\"", rule)),
+                "`{}` is not a LintRule",
+                rule
+            );
+            assert_eq!(level, "off");
+        }
+        assert!(type_alias("LintConfig").contains("Record<LintRule, \"off\" | Severity>"));
+    }
+
+    #[test]
+    fn test_search_diff_and_document_types() {
+        let nodes = Parser::new("---\ntitle: Guide\n---\n\n# Install\n\nRun it.").parse_document();
+
+        let mut index = SearchIndex::new();
+        index.add("guide.html", &nodes);
+        let hits = serde_json::to_value(index.search("install")).unwrap();
+        let index = serde_json::to_value(&index).unwrap();
+        assert_keys_declared(&index, interface("SearchIndex"));
+        assert_keys_declared(&index["entries"][0], interface("SearchEntry"));
+        assert_keys_declared(&hits[0], interface("SearchHit"));
+        assert!(index["terms"]["install"][0].is_array());
+
+        let old = Parser::new("# A\n\nOne two\n\n---\n\n## B\n\n## C").parse_document();
+        let new = Parser::new("# A\n\nOne three\n\n## C\n\n## B\n\n> New").parse_document();
+        let changes = serde_json::to_value(diff(&old, &new)).unwrap();
+        let mut ops = BTreeSet::new();
+        for change in changes.as_array().unwrap() {
+            let op = change["op"].as_str().unwrap();
+            ops.insert(op.to_string());
+            let member = union_member("Change", &format!("op: \"{}\"", op));
+            assert_keys_declared(change, member);
+        }
+        assert_eq!(ops.len(), 4, "{:?}", ops);
+        for edit in serde_json::to_value(diff_text("a b", "a c"))
+            .unwrap()
+            .as_array()
+            .unwrap()
+        {
+            let member = union_member(
+                "TextEdit",
+                &format!("op: \"{}\"", edit["op"].as_str().unwrap()),
+            );
+            assert_keys_declared(edit, member);
+        }
+
+        let document = serde_json::to_value(Document::new(nodes)).unwrap();
+        assert_keys_declared(&document, interface("Document"));
+        assert_eq!(document["metadata"]["title"], "Guide");
+    }
+
+    fn capitalize(tag: &str) -> String {
+        let mut chars = tag.chars();
+        let first = chars.next().unwrap().to_ascii_uppercase();
+        std::iter::once(first).chain(chars).collect()
+    }
+}
@@ -0,0 +1,67 @@
+#[cfg(test)]
+mod visit_tests {
+    use mdkit::ast::Node;
+    use mdkit::ast::visit::{
+        Visitor, VisitorMut, walk_node, walk_node_mut, walk_nodes, walk_nodes_mut,
+    };
+    use mdkit::parser::Parser;
+    use mdkit::renderer::render;
+
+    struct LinkCollector(Vec<String>);
+
+    impl Visitor for LinkCollector {
+        fn visit_node(&mut self, node: &Node) {
+            if let Node::Link { url, .. } = node {
+                self.0.push(url.clone());
+            }
+            walk_node(self, node);
+        }
+    }
+
+    struct HttpsRewriter;
+
+    impl VisitorMut for HttpsRewriter {
+        fn visit_node_mut(&mut self, node: &mut Node) {
+            if let Node::Link { url, .. } = node
+                && let Some(rest) = url.strip_prefix("http://")
+            {
+                *url = format!("https://{}", rest);
+            }
+            walk_node_mut(self, node);
+        }
+    }
+
+    #[test]
+    fn test_visitor_reaches_nested_collections() {
+        let input = "- [a](/list)\n  - [b](/nested)\n\n| [c](/cell) |\n|---|\n| **[d](/row)** |\n\n:::note\n> [e](/quote)\n:::";
+        let nodes = Parser::new(input).parse_document();
+
+        let mut collector = LinkCollector(Vec::new());
+        walk_nodes(&mut collector, &nodes);
+
+        assert_eq!(
+            collector.0,
+            vec!["/list", "/nested", "/cell", "/row", "/quote"]
+        );
+    }
+
+    #[test]
+    fn test_visitor_mut_rewrites_links() {
+        let input = "See [docs](http://example.com) and [home](/).";
+        let mut nodes = Parser::new(input).parse_document();
+
+        walk_nodes_mut(&mut HttpsRewriter, &mut nodes);
+        let html = render(&nodes);
+
+        assert!(html.contains("<a href='https://example.com'>docs</a>"));
+        assert!(html.contains("<a href='/'>home</a>"));
+    }
+
+    #[test]
+    fn test_heading_id_skips_link_text() {
+        let nodes = Parser::new("## Using **[mdkit](https://example.com)** `v2`").parse_document();
+        let html = render(&nodes);
+
+        assert!(html.contains("id=\"using-v2\""));
+    }
+}
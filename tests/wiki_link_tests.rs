@@ -0,0 +1,86 @@
+#[cfg(test)]
+mod wiki_link_tests {
+    use mdkit::ast::Node;
+    use mdkit::markdown;
+    use mdkit::parser::{ParseOptions, Parser};
+    use mdkit::renderer::{Renderer, render};
+    use mdkit::wiki::LinkGraph;
+
+    fn parse(input: &str) -> Vec<Node> {
+        let options = ParseOptions {
+            wiki_links: true,
+            ..ParseOptions::default()
+        };
+        Parser::with_options(input, options).parse_document()
+    }
+
+    fn wiki_link(target: &str, heading: Option<&str>, alias: Option<&str>) -> Node {
+        Node::WikiLink {
+            target: target.to_string(),
+            heading: heading.map(str::to_string),
+            alias: alias.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_wiki_link_forms() {
+        let nodes = parse("[[Page Name]] [[ Page#Set Up | setup ]] [[#Local]]");
+
+        assert_eq!(
+            nodes,
+            vec![Node::Paragraph {
+                children: vec![
+                    wiki_link("Page Name", None, None),
+                    Node::Text {
+                        content: " ".to_string()
+                    },
+                    wiki_link("Page", Some("Set Up"), Some("setup")),
+                    Node::Text {
+                        content: " ".to_string()
+                    },
+                    wiki_link("", Some("Local"), None),
+                ],
+            }]
+        );
+        assert_eq!(
+            markdown::render(&nodes),
+            "[[Page Name]] [[Page#Set Up|setup]] [[#Local]]\n"
+        );
+        assert!(!render(&Parser::new("[[Page]]").parse_document()).contains("wiki-link"));
+    }
+
+    #[test]
+    fn test_default_urls_link_to_site_pages() {
+        assert_eq!(
+            render(&parse("[[Page Name#Set Up]] and [[#Local|here]]")),
+            "<p><a class='wiki-link' href='Page%20Name.html#set-up'>Page Name > Set Up</a> and <a class='wiki-link' href='#local'>here</a></p>\n"
+        );
+    }
+
+    #[test]
+    fn test_resolver_maps_pages_and_marks_missing_ones() {
+        let mut renderer = Renderer::new();
+        renderer.wiki_links(|target: &str| (target == "Rust").then(|| "/kb/rust".to_string()));
+
+        assert_eq!(
+            renderer.render(&parse("[[Rust|the language]], [[Go]]")),
+            "<p><a class='wiki-link' href='/kb/rust'>the language</a>, <span class='wiki-link missing'>Go</span></p>\n"
+        );
+    }
+
+    #[test]
+    fn test_link_graph_backlinks_and_orphans() {
+        let mut graph = LinkGraph::new();
+        graph
+            .add("Home", "# Home\n\n- [[Rust]]\n- [[rust#install]]\n- [[Go]]")
+            .add("Rust", "Back [[home]], see [[#Install]].")
+            .add("Drafts", "Links to [[Rust]] only.");
+
+        let pages = graph.pages();
+        assert_eq!(pages["Home"].links, vec!["Rust"]);
+        assert_eq!(pages["Home"].missing, vec!["Go"]);
+        assert_eq!(pages["Home"].backlinks, vec!["Rust"]);
+        assert_eq!(pages["Rust"].backlinks, vec!["Drafts", "Home"]);
+        assert_eq!(graph.orphans(), vec!["Drafts"]);
+    }
+}